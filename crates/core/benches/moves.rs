@@ -0,0 +1,97 @@
+//! Benchmarks of move generation.
+//!
+//! Run with `cargo bench -p chessagon-core`.
+
+use chessagon_core::{Board, Color};
+
+#[global_allocator]
+static ALLOC: divan::AllocProfiler = divan::AllocProfiler::system();
+
+fn main() {
+    divan::main();
+}
+
+/// A position from the middle of a game, with pieces of both colors in play.
+fn middlegame() -> Board {
+    let mut board = Board::default();
+    for ply in 0..20 {
+        let color = board.turn();
+        let moves = board.possible_moves(color).collect::<Vec<_>>();
+        board.apply_move_unchecked(moves[(ply * 7) % moves.len()], color);
+    }
+
+    board
+}
+
+#[divan::bench]
+fn possible_moves_initial(bencher: divan::Bencher) {
+    let board = Board::default();
+    bencher.bench(|| {
+        divan::black_box(&board)
+            .possible_moves(Color::White)
+            .count()
+    });
+}
+
+#[divan::bench]
+fn possible_moves_middlegame(bencher: divan::Bencher) {
+    let board = middlegame();
+    let color = board.turn();
+    bencher.bench(|| divan::black_box(&board).possible_moves(color).count());
+}
+
+/// The king safety check of every legal move, by [peeking](Board::peek) at the board.
+#[divan::bench]
+fn king_safety_peek(bencher: divan::Bencher) {
+    let board = middlegame();
+    let color = board.turn();
+    let moves = board.possible_moves(color).collect::<Vec<_>>();
+    bencher.bench(|| {
+        let board = divan::black_box(&board);
+        moves
+            .iter()
+            .filter(|&&mov| board.peek(mov, color).in_check(color).is_none())
+            .count()
+    });
+}
+
+/// The same as [`king_safety_peek`], by applying each move to a copy of the board, for
+/// comparison.
+#[divan::bench]
+fn king_safety_clone(bencher: divan::Bencher) {
+    let board = middlegame();
+    let color = board.turn();
+    let moves = board.possible_moves(color).collect::<Vec<_>>();
+    bencher.bench(|| {
+        let board = divan::black_box(&board);
+        moves
+            .iter()
+            .filter(|&&mov| {
+                let mut test_board = board.clone();
+                test_board.apply_move_unchecked(mov, color);
+                test_board.in_check(color).is_none()
+            })
+            .count()
+    });
+}
+
+/// The same as [`king_safety_clone`], but copying each move into the same board with
+/// [`Board::clone_into`].
+#[divan::bench]
+fn king_safety_clone_into(bencher: divan::Bencher) {
+    let board = middlegame();
+    let color = board.turn();
+    let moves = board.possible_moves(color).collect::<Vec<_>>();
+    bencher.bench(|| {
+        let board = divan::black_box(&board);
+        let mut test_board = Board::empty();
+        moves
+            .iter()
+            .filter(|&&mov| {
+                board.clone_into(&mut test_board);
+                test_board.apply_move_unchecked(mov, color);
+                test_board.in_check(color).is_none()
+            })
+            .count()
+    });
+}
@@ -1,8 +1,16 @@
+pub(crate) mod fen;
+pub(crate) mod peek;
+mod zobrist;
+
+use alloc::{string::String, vec::Vec};
+#[cfg(any(feature = "std", test))]
+use hext_boards::HexagonalBoard;
+
 use crate::{
     Color,
     coordinate::Vec2,
     mov::{Move, MoveMeta},
-    piece::{MoveError, Piece},
+    piece::{MoveError, Piece, PlacedPiece, king, movement, pawn},
 };
 
 /// A hexagonal chess board.
@@ -12,21 +20,77 @@ use crate::{
 /// # Invariants
 /// - The board is always in a valid state. This implies:
 ///     - There is exactly one king of each color.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Board {
     #[cfg_attr(feature = "serde", serde(with = "serde_piece_nested_array"))]
-    pieces: [[Option<Piece>; 91]; 2],
+    pieces: [[Option<Piece>; Board::NUMBER_OF_TILES as usize]; 2],
     last_move: Option<Move>,
 }
 
+/// The identity of a position for the purposes of transpositions and repetitions.
+///
+/// Two boards have the same key if and only if they have:
+/// - The same placement of pieces.
+/// - The same color to move (see [`Board::transposition_key`] for how it is inferred).
+/// - The same file in which an en passant capture would be possible, if any.
+///
+/// In particular, it ignores which move led to the position, except for the en passant file.
+/// There is no castling in hexagonal chess, so there are no castling rights to include.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TranspositionKey {
+    pieces: [[Option<Piece>; Board::NUMBER_OF_TILES as usize]; 2],
+    turn: Color,
+    en_passant_file: Option<u8>,
+}
+
+/// Read-only access to the pieces on a board.
+///
+/// This is implemented both by [`Board`] and by [`PeekBoard`](peek::PeekBoard), so that piece
+/// geometry (such as [`Piece::attacks`]) can be evaluated on either.
+pub trait BoardView {
+    /// Gets the piece with its color at the specified position.
+    fn get_either(&self, position: Vec2) -> Option<(Piece, Color)>;
+
+    /// Gets the piece at the specified position, if it's of the given color.
+    fn get(&self, position: Vec2, color: Color) -> Option<Piece> {
+        self.get_either(position)
+            .filter(|&(_, piece_color)| piece_color == color)
+            .map(|(piece, _)| piece)
+    }
+}
+
+impl BoardView for Board {
+    #[inline]
+    fn get_either(&self, position: Vec2) -> Option<(Piece, Color)> {
+        Board::get_either(self, position)
+    }
+
+    #[inline]
+    fn get(&self, position: Vec2, color: Color) -> Option<Piece> {
+        Board::get(self, position, color)
+    }
+}
+
+/// Whether a board position has ended, according only to the rules of movement.
+///
+/// See [`Board::status`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BoardStatus {
+    /// The player to move has at least one legal move.
+    Ongoing,
+
+    /// The player to move has no legal moves and is in check.
+    Checkmate,
+
+    /// The player to move has no legal moves but is not in check.
+    Stalemate,
+}
+
 impl Default for Board {
     fn default() -> Self {
-        let mut output = Board {
-            pieces: [[None; 91]; 2],
-            last_move: None,
-        };
-
+        let mut output = Board::empty();
         for (piece, position, color) in Piece::initial_configuration() {
             output.get_mut(position, color).replace(piece);
         }
@@ -37,16 +101,46 @@ impl Default for Board {
 
 impl Board {
     /// The maximum absolute value difference that a set of coordinates can have.
-    pub const SIZE: u8 = 5;
+    pub const SIZE: u8 = Vec2::WIDTH;
 
-    #[allow(missing_docs)]
-    pub const NUMBER_OF_TILES: u8 = 91;
+    /// The total number of tiles, which is the centered hexagonal number of [`Self::SIZE`].
+    pub const NUMBER_OF_TILES: u8 = 3 * Self::SIZE * (Self::SIZE + 1) + 1;
 
     /// The total number of [files](`Vec2::file`).
-    pub const NUMBER_OF_FILES: u8 = 11;
+    pub const NUMBER_OF_FILES: u8 = Vec2::MAX_FILE + 1;
+
+    /// The number of tiles in the longest [file](`Vec2::file`), i.e., how many tiles tall the
+    /// board is.
+    pub const NUMBER_OF_RANKS: u8 = Vec2::MAX + 1;
+
+    /// A board without any pieces, where white moves first.
+    ///
+    /// This is not a [valid](Self::validate) board (there are no kings, which most methods
+    /// assume there are), it is meant as a starting point for building positions piece by piece
+    /// with [`Self::get_mut`]. See also [`Self::from_pieces`].
+    pub const fn empty() -> Self {
+        Board {
+            pieces: [[None; Self::NUMBER_OF_TILES as usize]; 2],
+            last_move: None,
+        }
+    }
+
+    /// Builds a [valid](Self::validate) board with the given pieces, where white moves first.
+    pub fn from_pieces(
+        pieces: impl IntoIterator<Item = (Vec2, Piece, Color)>,
+    ) -> Result<Self, InvalidBoardError> {
+        let mut board = Self::empty();
+        for (position, piece, color) in pieces {
+            if board.get_either(position).is_some() {
+                return Err(InvalidBoardError::OverlappingPieces { position });
+            }
+
+            board.get_mut(position, color).replace(piece);
+        }
 
-    /// The total number of [ranks](`Vec2::rank`).
-    pub const NUMBER_OF_RANKS: u8 = 11;
+        board.validate(Color::White)?;
+        Ok(board)
+    }
 
     /// Creates a new board with the minimal number of pieces (i.e, two kings).
     ///
@@ -57,11 +151,7 @@ impl Board {
             return None;
         }
 
-        let mut output = Self {
-            pieces: [[None; Self::NUMBER_OF_TILES as usize]; 2],
-            last_move: None,
-        };
-
+        let mut output = Self::empty();
         output.pieces[Color::White][Board::index(white_king_position)] = Some(Piece::King);
         output.pieces[Color::Black][Board::index(black_king_position)] = Some(Piece::King);
 
@@ -71,7 +161,18 @@ impl Board {
     /// Returns the index where the position is stored in the array.
     ///
     /// See also [`Self::index_to_vec`]
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, if `position` is not valid (which can only happen if it was created with
+    /// [`Vec2::new_unchecked`]). Every `get*` method goes through here, so this also catches
+    /// invalid accesses to the board.
     pub fn index(position: Vec2) -> usize {
+        debug_assert!(
+            Vec2::is_valid(position.x(), position.y()),
+            "Tried to index the board with an invalid position {position}"
+        );
+
         let rank = position.rank();
         let tiles_before_rank = (0..rank).map(Vec2::rank_width).sum::<u8>();
 
@@ -83,7 +184,9 @@ impl Board {
         let first_valid_y = Vec2::min_valid_rank_coordinate(rank);
         let index_on_rank = position.y() - first_valid_y;
 
-        (tiles_before_rank + index_on_rank) as usize
+        let index = (tiles_before_rank + index_on_rank) as usize;
+        debug_assert!(index < Self::NUMBER_OF_TILES as usize);
+        index
     }
 
     /// Returns the position that would result into the given index.
@@ -150,15 +253,26 @@ impl Board {
         self.pieces[color].iter().copied().flatten()
     }
 
+    /// Enumerates all `(index, piece)` pairs of the given color, where `index` is the same as
+    /// [`Self::index`] of the position of the piece.
+    ///
+    /// This is cheaper than [`Self::piece_positions`] when the position itself is not needed
+    /// (e.g., for piece-square tables).
+    pub fn indexed_pieces(&self, color: Color) -> impl Iterator<Item = (usize, Piece)> {
+        self.pieces[color]
+            .iter()
+            .enumerate()
+            .filter_map(|(index, &piece)| Some((index, piece?)))
+    }
+
     /// Enumerates all `(position, piece)` pairs of the given color.
     ///
     /// See also [`Self::all_piece_positions`] and [`Self::pieces`].
     pub fn piece_positions(&self, color: Color) -> impl Iterator<Item = (Vec2, Piece)> {
-        // TODO: This could be `get_unchecked`
         self.pieces[color]
             .iter()
-            .enumerate()
-            .filter_map(|(i, &piece)| Some((Board::index_to_vec(i), piece?)))
+            .zip(Vec2::iter_by_index())
+            .filter_map(|(&piece, position)| Some((position, piece?)))
     }
 
     /// Enumerates the positions of all pieces as a `(position, piece, color)` triplet.
@@ -173,11 +287,88 @@ impl Board {
             )
     }
 
+    /// Gets the piece at the specified position, of either color, as a [`PlacedPiece`].
+    ///
+    /// See also [`Self::get_either`].
+    pub fn piece_at(&self, position: Vec2) -> Option<PlacedPiece> {
+        self.get_either(position).map(|(piece, color)| PlacedPiece {
+            piece,
+            color,
+            position,
+        })
+    }
+
+    /// Enumerates all pieces on the board, of both colors, as [`PlacedPiece`]s.
+    ///
+    /// The order is the same as in [`Self::all_piece_positions`].
+    pub fn placed_pieces(&self) -> impl Iterator<Item = PlacedPiece> {
+        self.all_piece_positions()
+            .map(|(position, piece, color)| PlacedPiece {
+                piece,
+                color,
+                position,
+            })
+    }
+
     /// Verifies whether the given move is legal or not.
+    ///
+    /// Pawns reaching a promotion tile have to do so with a [`Move::Promotion`], since a
+    /// [`Move::Regular`] doesn't say what they promote to (see [`Self::is_pawn_promotion_move`]).
+    /// The [`captures`](Move::Regular::captures) flag of regular moves has to match whether
+    /// there is a piece to capture in the destination.
     pub fn check_move(&self, mov: Move, color: Color) -> Result<(), MoveError> {
+        if let Move::Promotion {
+            file,
+            captures,
+            promoting_to,
+        } = mov
+            && (!pawn::PROMOTION_PIECES.contains(&promoting_to)
+                || pawn::promotion_origin(file, captures, color).is_none())
+        {
+            return Err(MoveError::InvalidPromotion { mov });
+        }
+
         // TODO: Make this use direct logic instead of reusing `get_move`
-        self.get_move(mov.origin(), mov.destination(), color)?;
-        Ok(())
+        let origin = mov.origin_for(color);
+        let destination = mov.destination_for(color);
+        let (legal, _) = self.get_move(origin, destination, color)?;
+        match (mov, legal) {
+            (Move::Regular { .. }, Move::Promotion { .. }) => Err(MoveError::PromotionRequired {
+                origin,
+                destination,
+            }),
+            (Move::Promotion { .. }, Move::Regular { .. }) => {
+                Err(MoveError::InvalidPromotion { mov })
+            }
+            // Applying the move trusts the flag, so it has to match the board.
+            (
+                Move::Regular { captures, .. },
+                Move::Regular {
+                    captures: legal_captures,
+                    ..
+                },
+            ) if captures != legal_captures => Err(MoveError::WrongCaptureFlag { mov }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Whether `mov` is a pawn of the given color reaching a promotion tile, either as a
+    /// [`Move::Promotion`] or as a [`Move::Regular`] that should have been one.
+    ///
+    /// The move is not checked for legality.
+    pub fn is_pawn_promotion_move(&self, mov: Move, color: Color) -> bool {
+        match mov {
+            Move::Regular {
+                origin,
+                destination,
+                ..
+            } => {
+                self.get(origin, color) == Some(Piece::Pawn)
+                    && pawn::is_promotion(origin, destination, color)
+            }
+            Move::EnPassant { .. } => false,
+            Move::Promotion { .. } => true,
+        }
     }
 
     /// Applies the given move.
@@ -189,6 +380,17 @@ impl Board {
         Ok(self.apply_move_unchecked(mov, color))
     }
 
+    /// Same as [`Self::apply_move`], but also returns the [status](Self::status) of the
+    /// opponent after the move, i.e., whether the move ended the game.
+    pub fn apply_move_with_status(
+        &mut self,
+        mov: Move,
+        color: Color,
+    ) -> Result<(Option<Piece>, BoardStatus), MoveError> {
+        let capture = self.apply_move(mov, color)?;
+        Ok((capture, self.status(color.other())))
+    }
+
     /// Makes the specified move. Doesn't check for any legality. May leave the board in an inconsistent state.
     ///
     /// Returns the captured piece, if any.
@@ -216,7 +418,24 @@ impl Board {
             }
 
             Move::EnPassant { .. } => todo!(),
-            Move::Promotion { .. } => todo!(),
+            Move::Promotion {
+                captures,
+                promoting_to,
+                ..
+            } => {
+                let origin = mov.origin_for(color);
+                let destination = mov.destination_for(color);
+                let capture = captures.map(|_| {
+                    self.get_mut(destination, color.other()).take().expect(
+                        "There should be a piece in the destination if the promotion captures",
+                    )
+                });
+
+                self.get_mut(origin, color).take();
+                self.get_mut(destination, color).replace(promoting_to);
+
+                capture
+            }
         };
 
         self.last_move = Some(mov);
@@ -263,15 +482,242 @@ impl Board {
     // }
 
     /// An iterator over all legal moves in the current for position that the player of the given color can do.
+    ///
+    /// Promotions are yielded once for each of the [`pawn::PROMOTION_PIECES`].
     pub fn possible_moves(&self, color: Color) -> impl Iterator<Item = Move> {
-        Vec2::iter()
-            .flat_map(move |origin| {
-                Vec2::iter().filter_map(move |destination| {
+        Vec2::iter().flat_map(move |origin| {
+            Vec2::iter()
+                .filter_map(move |destination| {
                     self.get_move(origin, destination, color)
                         .ok()
                         .map(|(mov, _)| mov)
                 })
+                .flat_map(with_promotions)
+        })
+    }
+
+    /// An iterator over the legal moves of the piece of the given color at `origin`.
+    ///
+    /// Yields the same moves as filtering [`Self::possible_moves`] by origin, but only looks at
+    /// that one piece, so it is much cheaper. It is empty if there is no such piece.
+    pub fn moves_from(&self, origin: Vec2, color: Color) -> impl Iterator<Item = Move> {
+        let has_piece = self.get(origin, color).is_some();
+        Vec2::iter()
+            .filter(move |_| has_piece)
+            .filter_map(move |destination| {
+                self.get_move(origin, destination, color)
+                    .ok()
+                    .map(|(mov, _)| mov)
+            })
+            .flat_map(with_promotions)
+    }
+
+    /// An iterator over the legal moves of every piece of the given color and kind (e.g., all
+    /// knight moves), useful for move ordering and targeted analysis.
+    ///
+    /// Yields the same moves as filtering [`Self::possible_moves`] by the piece that moves.
+    pub fn moves_of_kind(&self, color: Color, kind: Piece) -> impl Iterator<Item = Move> {
+        self.piece_positions(color)
+            .filter(move |&(_, piece)| piece == kind)
+            .flat_map(move |(origin, _)| self.moves_from(origin, color))
+    }
+
+    /// How many legal moves each kind of piece of the given color has, indexed by [`Piece`].
+    ///
+    /// Shows which pieces are mobile, e.g., to point them out to beginners. Promotions count
+    /// once for each piece they can promote to, as in [`Self::moves_of_kind`].
+    pub fn legal_move_count_by_piece(&self, color: Color) -> [usize; 6] {
+        let mut counts = [0; 6];
+        for (origin, piece) in self.piece_positions(color) {
+            counts[piece as usize] += self.moves_from(origin, color).count();
+        }
+
+        counts
+    }
+
+    /// The tiles that the piece at `origin` (of either color) can move to, without checking
+    /// whether the move would leave its king in check.
+    ///
+    /// These are the destinations of the pseudo-legal moves of the piece, which is useful for
+    /// previews and for building attack maps. See [`Self::moves_from`] for legal moves instead.
+    /// It is empty if there is no piece at `origin`.
+    pub fn reachable_tiles(&self, origin: Vec2) -> impl Iterator<Item = Vec2> {
+        let piece = self.get_either(origin);
+        Vec2::iter().filter(move |&destination| {
+            piece.is_some_and(|(piece, color)| {
+                piece
+                    .get_move_no_checks(origin, destination, self, color)
+                    .is_ok()
+            })
+        })
+    }
+
+    /// Clears `out` and fills it with every legal move of the given color.
+    ///
+    /// Yields the same moves as [`Self::possible_moves`], but lets the caller reuse the
+    /// allocation (e.g., one buffer per depth in a search).
+    pub fn collect_moves_into(&self, color: Color, out: &mut Vec<Move>) {
+        out.clear();
+        for (origin, _) in self.piece_positions(color) {
+            out.extend(
+                Vec2::iter()
+                    .filter_map(|destination| {
+                        self.get_move(origin, destination, color)
+                            .ok()
+                            .map(|(mov, _)| mov)
+                    })
+                    .flat_map(with_promotions),
+            );
+        }
+    }
+
+    /// Whose turn it is.
+    ///
+    /// The board doesn't store whose turn it is, so it is inferred as the opponent of whoever
+    /// made the last move (or white, if there was none).
+    pub fn turn(&self) -> Color {
+        self.last_moved()
+            .map_or(Color::White, |(_, color)| color.other())
+    }
+
+    /// The piece that made the last move (after promoting, if it did), and its color.
+    fn last_moved(&self) -> Option<(Piece, Color)> {
+        match self.last_move? {
+            // The tiles of a promotion depend on the color, so look for the promoted piece on
+            // each color's destination.
+            mov @ Move::Promotion {
+                file,
+                captures,
+                promoting_to,
+            } => [Color::White, Color::Black]
+                .into_iter()
+                .find(|&color| {
+                    pawn::promotion_origin(file, captures, color).is_some()
+                        && self.get(mov.destination_for(color), color) == Some(promoting_to)
+                })
+                .map(|color| (promoting_to, color)),
+            mov => self.get_either(mov.destination()),
+        }
+    }
+
+    /// The [`TranspositionKey`] of this board.
+    ///
+    /// The color to move is the one given by [`Self::turn`].
+    ///
+    /// The en passant file is the file of the pawn that just moved two tiles forward, if the last
+    /// move was one. This is included regardless of whether there is an enemy pawn in position
+    /// to capture it.
+    pub fn transposition_key(&self) -> TranspositionKey {
+        let last_moved = self.last_moved();
+
+        let en_passant_file = match self.last_move {
+            Some(Move::Regular {
+                origin,
+                destination,
+                captures: false,
+            }) if last_moved.is_some_and(|(piece, color)| {
+                piece == Piece::Pawn && pawn::is_intial_tile(origin, color)
+            }) =>
+            {
+                let (_stride, distance) = movement::get_stride(destination - origin);
+                (distance == 2).then(|| destination.file())
+            }
+            _ => None,
+        };
+
+        TranspositionKey {
+            pieces: self.pieces,
+            turn: self.turn(),
+            en_passant_file,
+        }
+    }
+
+    /// The positions of the pieces of the opponent of `color` that are giving check to its king.
+    ///
+    /// There can be at most two of them (a double check).
+    pub fn checkers(&self, color: Color) -> impl Iterator<Item = Vec2> {
+        let king_position = self.find_king(color);
+        self.piece_positions(color.other())
+            .filter(move |&(origin, piece)| {
+                piece.attacks(origin, king_position, self, color.other())
             })
+            .map(|(origin, _)| origin)
+    }
+
+    /// The legal moves of `color`, generated knowing that its king is in check.
+    ///
+    /// Instead of trying every move, this only tries:
+    /// - Moving the king.
+    /// - Capturing the checking piece, if there is only one.
+    /// - Blocking the line between the checking piece and the king, if there is only one and
+    ///   it is a sliding piece.
+    ///
+    /// If the king is not in check, this is the same as [`Self::possible_moves`].
+    pub fn check_evasions(&self, color: Color) -> impl Iterator<Item = Move> {
+        let checkers = self.checkers(color).collect::<Vec<_>>();
+        if checkers.is_empty() {
+            return self.possible_moves(color).collect::<Vec<_>>().into_iter();
+        }
+
+        self.evasions_from(color, &checkers)
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    /// The moves that get the king of `color` out of the check given by `checkers` (which must
+    /// not be empty), generated lazily so that callers can stop at the first one.
+    fn evasions_from(&self, color: Color, checkers: &[Vec2]) -> impl Iterator<Item = Move> {
+        let king_position = self.find_king(color);
+        let king_moves = king::VALID_DELTAS
+            .into_iter()
+            .filter_map(move |delta| king_position.checked_add(delta))
+            .filter_map(move |destination| self.get_move(king_position, destination, color).ok())
+            .map(|(mov, _)| mov);
+
+        // Under double check, only the king can move.
+        let targets = match *checkers {
+            [checker] => checker
+                .between(king_position)
+                .into_iter()
+                .flatten()
+                .chain([checker])
+                .collect::<Vec<_>>(),
+            _ => Vec::new(),
+        };
+
+        let blocks_and_captures = self
+            .piece_positions(color)
+            .filter(|&(_, piece)| piece != Piece::King)
+            .flat_map(move |(origin, _)| {
+                targets
+                    .clone()
+                    .into_iter()
+                    .filter_map(move |destination| self.get_move(origin, destination, color).ok())
+                    .flat_map(|(mov, _)| with_promotions(mov))
+            });
+
+        king_moves.chain(blocks_and_captures)
+    }
+
+    /// Copies the board into `dst`, overwriting it.
+    ///
+    /// Same as `*dst = self.clone()`, but makes it explicit that searches can keep reusing the
+    /// same boards (for example, one for each depth) instead of creating new ones for each node.
+    pub fn clone_into(&self, dst: &mut Board) {
+        dst.pieces = self.pieces;
+        dst.last_move = self.last_move;
+    }
+
+    /// Returns the same board, but with every white piece turned black and vice versa.
+    ///
+    /// Pieces stay in the same tiles. Note that this means that pawns will move in the opposite
+    /// direction than they used to.
+    pub fn with_colors_swapped(&self) -> Board {
+        let [white, black] = self.pieces;
+        Board {
+            pieces: [black, white],
+            last_move: self.last_move,
+        }
     }
 
     /// The sum of the [`Piece::value`]s of the pieces of the given color.
@@ -281,7 +727,118 @@ impl Board {
             .sum()
     }
 
+    /// How far the game is into the endgame, from `0.0` (all the non-pawn material of the
+    /// starting position is on the board) to `1.0` (only kings and pawns are left).
+    ///
+    /// It is computed from the remaining knights and bishops (weighing 1 each), rooks (2) and
+    /// queens (4), so that evaluation terms that matter differently in the middlegame and the
+    /// endgame (such as king safety and king activity) can be blended.
+    pub fn phase(&self) -> f32 {
+        let weight = |piece| match piece {
+            Piece::Knight | Piece::Bishop => 1,
+            Piece::Rook => 2,
+            Piece::Queen => 4,
+            Piece::King | Piece::Pawn => 0,
+        };
+
+        // Each color starts with 2 knights, 3 bishops, 2 rooks and a queen.
+        const FULL: u16 = 2 * (2 + 3 + 2 * 2 + 4);
+
+        let remaining = [Color::White, Color::Black]
+            .into_iter()
+            .flat_map(|color| self.pieces(color))
+            .map(weight)
+            .sum::<u16>()
+            .min(FULL);
+        1.0 - f32::from(remaining) / f32::from(FULL)
+    }
+
+    /// How many pieces of the given kind and color are on the board.
+    pub fn count(&self, piece: Piece, color: Color) -> u8 {
+        self.pieces(color).filter(|&p| p == piece).count() as u8
+    }
+
+    /// The pieces of each color, as their [letters](Piece::representing_letter) from most to
+    /// least valuable (starting with the king), with white's and black's separated by a `v`.
+    ///
+    /// For example, a white king and queen against a lone black king is `"KQvK"`. Positions with
+    /// the same material have the same signature, so it can be used as a key for endgames (see
+    /// [`Self::is_drawn_material_signature`]).
+    pub fn material_signature(&self) -> String {
+        const ORDER: [Piece; 6] = [
+            Piece::King,
+            Piece::Queen,
+            Piece::Rook,
+            Piece::Bishop,
+            Piece::Knight,
+            Piece::Pawn,
+        ];
+
+        let side = |color| {
+            ORDER.iter().flat_map(move |&piece| {
+                core::iter::repeat_n(
+                    piece.representing_letter(),
+                    self.count(piece, color) as usize,
+                )
+            })
+        };
+
+        side(Color::White)
+            .chain(['v'])
+            .chain(side(Color::Black))
+            .collect()
+    }
+
+    /// Whether a [material signature](Self::material_signature) can't lead to a checkmate by
+    /// either side, i.e., a lone king against a lone king, or against a king and a single minor
+    /// piece.
+    pub fn is_drawn_material_signature(signature: &str) -> bool {
+        matches!(signature, "KvK" | "KBvK" | "KvKB" | "KNvK" | "KvKN")
+    }
+
+    /// Whether the player of the given color has enough material to checkmate, which is anything
+    /// more than a lone king or a king and a single knight or bishop (as in
+    /// [`Self::is_drawn_material_signature`]).
+    ///
+    /// Bishops on the same [index](Self::bishops_by_index) cover the same tiles, so any number
+    /// of them can't checkmate either, and they count as a single bishop.
+    pub fn has_mating_material(&self, color: Color) -> bool {
+        let mut knights = 0;
+        for piece in self.pieces(color) {
+            match piece {
+                Piece::King | Piece::Bishop => (),
+                Piece::Knight => knights += 1,
+                Piece::Pawn | Piece::Rook | Piece::Queen => return true,
+            }
+        }
+
+        let bishop_indices = self
+            .bishops_by_index(color)
+            .into_iter()
+            .filter(|&count| count > 0)
+            .count();
+
+        knights + bishop_indices > 1
+    }
+
+    /// How many bishops of the given color are on tiles of each [index](Vec2::index).
+    ///
+    /// Bishops never leave the index of their tile, so two bishops on the same index cover the
+    /// same tiles.
+    pub fn bishops_by_index(&self, color: Color) -> [u8; 3] {
+        let mut counts = [0; 3];
+        for (position, piece) in self.piece_positions(color) {
+            if piece == Piece::Bishop {
+                counts[position.index() as usize] += 1;
+            }
+        }
+
+        counts
+    }
+
     /// Returns the position of the king of the given color.
+    ///
+    /// Assumes that there is exactly one. See [`Self::kings`] for boards that might not be valid.
     pub fn find_king(&self, color: Color) -> Vec2 {
         for (index, &piece) in self.pieces[color].iter().enumerate() {
             if piece == Some(Piece::King) {
@@ -292,6 +849,49 @@ impl Board {
         unreachable!("Boards should always have at least one king of each color");
     }
 
+    /// The positions of every king of the given color.
+    ///
+    /// Unlike [`Self::find_king`], this doesn't assume that there is exactly one king, so it can
+    /// be used to detect (and repair) boards that are being built before they are
+    /// [validated](Self::validate).
+    pub fn kings(&self, color: Color) -> impl Iterator<Item = Vec2> {
+        self.piece_positions(color)
+            .filter(|&(_, piece)| piece == Piece::King)
+            .map(|(position, _)| position)
+    }
+
+    /// The tiles around the king of the given color: its own tile and its
+    /// [neighbors](Vec2::neighbors).
+    ///
+    /// This is useful for evaluating king safety, e.g., by counting enemy attacks on the zone.
+    pub fn king_zone(&self, color: Color) -> impl Iterator<Item = Vec2> {
+        let king_position = self.find_king(color);
+        core::iter::once(king_position).chain(king_position.neighbors())
+    }
+
+    /// Whether any piece of color `by` [attacks](Piece::attacks) the given position.
+    ///
+    /// See also [`Self::attacked_tiles`] to compute this for every tile at once.
+    pub fn is_attacked(&self, position: Vec2, by: Color) -> bool {
+        self.piece_positions(by)
+            .any(|(origin, piece)| piece.attacks(origin, position, self, by))
+    }
+
+    /// Every tile that is [attacked](Piece::attacks) by the pieces of color `by`, indexed by
+    /// [`Self::index`].
+    ///
+    /// Note that tiles with pieces of color `by` can also be attacked (i.e., they are defended).
+    pub fn attacked_tiles(&self, by: Color) -> [bool; Board::NUMBER_OF_TILES as usize] {
+        let mut attacked = [false; Board::NUMBER_OF_TILES as usize];
+        for (origin, piece) in self.piece_positions(by) {
+            for (target, is_attacked) in Vec2::iter_by_index().zip(&mut attacked) {
+                *is_attacked |= piece.attacks(origin, target, self, by);
+            }
+        }
+
+        attacked
+    }
+
     /// Verifies whether the king of the given color could be attacked next move.
     ///
     /// If it is, returns a move that would capture the king.
@@ -308,12 +908,147 @@ impl Board {
             .map(|(mov, _)| mov)
             .next()
     }
+
+    /// Whether the game would be over if it was `to_move`'s turn.
+    ///
+    /// This only takes into account the board itself. Conditions that depend on the history of
+    /// the game (such as timeouts or the fifty-move rule) are handled by [`Game`](crate::Game).
+    ///
+    /// When `to_move` is in check, only the [evasions](Self::check_evasions) are tried, stopping
+    /// at the first legal one, which is much faster than going through every move.
+    pub fn status(&self, to_move: Color) -> BoardStatus {
+        let checkers = self.checkers(to_move).collect::<Vec<_>>();
+        if checkers.is_empty() {
+            match self.possible_moves(to_move).next() {
+                Some(_) => BoardStatus::Ongoing,
+                None => BoardStatus::Stalemate,
+            }
+        } else {
+            match self.evasions_from(to_move, &checkers).next() {
+                Some(_) => BoardStatus::Ongoing,
+                None => BoardStatus::Checkmate,
+            }
+        }
+    }
+
+    /// Whether `color` is checkmated. See [`Self::status`].
+    pub fn is_checkmate(&self, color: Color) -> bool {
+        self.status(color) == BoardStatus::Checkmate
+    }
+
+    /// Whether `color` is stalemated. See [`Self::status`].
+    pub fn is_stalemate(&self, color: Color) -> bool {
+        self.status(color) == BoardStatus::Stalemate
+    }
+
+    /// Whether playing `mov` as `color` puts the opponent's king in check.
+    ///
+    /// The move is applied to a copy of the board, so it is assumed to be legal.
+    pub fn gives_check(&self, mov: Move, color: Color) -> bool {
+        let mut board = self.clone();
+        board.apply_move_unchecked(mov, color);
+        board.in_check(color.other()).is_some()
+    }
+
+    /// Checks that the board is a position that could be played from.
+    ///
+    /// That is, that each color has exactly one king, that no tile has pieces of both colors,
+    /// and that the player who just moved (the opponent of `to_move`) is not in check.
+    ///
+    /// The side to move is given explicitly rather than inferred with [`Self::turn`], since
+    /// [`Game`](crate::Game)s always start with white to move, whatever the last move of the
+    /// board was.
+    pub fn validate(&self, to_move: Color) -> Result<(), InvalidBoardError> {
+        for color in [Color::White, Color::Black] {
+            let count = self.kings(color).count();
+            if count != 1 {
+                return Err(InvalidBoardError::WrongNumberOfKings { color, count });
+            }
+        }
+
+        let overlapping = (0..Self::NUMBER_OF_TILES as usize).find(|&index| {
+            self.pieces[Color::White][index].is_some() && self.pieces[Color::Black][index].is_some()
+        });
+        if let Some(index) = overlapping {
+            return Err(InvalidBoardError::OverlappingPieces {
+                position: Self::index_to_vec(index),
+            });
+        }
+
+        let waiting = to_move.other();
+        if self.in_check(waiting).is_some() {
+            return Err(InvalidBoardError::WaitingPlayerInCheck { color: waiting });
+        }
+
+        Ok(())
+    }
+}
+
+/// Yields `mov`, or if it is a promotion, the same promotion to each of the
+/// [`pawn::PROMOTION_PIECES`].
+fn with_promotions(mov: Move) -> impl Iterator<Item = Move> {
+    let choices = match mov {
+        Move::Promotion { .. } => pawn::PROMOTION_PIECES.len(),
+        _ => 1,
+    };
+
+    pawn::PROMOTION_PIECES
+        .into_iter()
+        .take(choices)
+        .map(move |promoting_to| match mov {
+            Move::Promotion { file, captures, .. } => Move::Promotion {
+                file,
+                captures,
+                promoting_to,
+            },
+            mov => mov,
+        })
+}
+
+/// The contents of each tile in a diagram, in reading order.
+///
+/// Tiles are drawn as `⟨  x  ⟩`, so it finds every `⟨` that is closed 6 characters later.
+#[cfg(any(feature = "std", test))]
+fn diagram_tiles(diagram: &str) -> impl Iterator<Item = char> {
+    diagram.lines().flat_map(|line| {
+        let chars = line.chars().collect::<Vec<_>>();
+        chars
+            .windows(7)
+            .filter(|window| matches!((window[0], window[6]), ('⟨', '⟩') | ('<', '>')))
+            .map(|window| window[3])
+            .collect::<Vec<_>>()
+    })
+}
+
+#[allow(missing_docs)]
+#[derive(Debug, thiserror::Error)]
+pub enum DiagramError {
+    #[error("Expected {expected} tiles in the diagram, but found {found}")]
+    WrongNumberOfTiles { expected: usize, found: usize },
+
+    #[error("Unknown piece '{tile}' at {position}")]
+    UnknownPiece { tile: char, position: Vec2 },
+
+    #[error("There should be exactly one {color} king, but there are {count}")]
+    WrongNumberOfKings { color: Color, count: usize },
 }
 
-impl std::fmt::Display for Board {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
-        use hext_boards::HexagonalBoard;
+#[allow(missing_docs)]
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum InvalidBoardError {
+    #[error("There should be exactly one {color} king, but there are {count}")]
+    WrongNumberOfKings { color: Color, count: usize },
+
+    #[error("There are pieces of both colors at {position}")]
+    OverlappingPieces { position: Vec2 },
+
+    #[error("The {color} king is in check, but it is not {color}'s turn")]
+    WaitingPlayerInCheck { color: Color },
+}
 
+#[cfg(any(feature = "std", test))]
+impl core::fmt::Display for Board {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
         let hex_board: HexagonalBoard<_> = Vec2::iter()
             .map(|position| {
                 let vec = [position.x() as i32, position.y() as i32];
@@ -330,19 +1065,144 @@ impl std::fmt::Display for Board {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::{board::Board, coordinate::Vec2, diagrams};
-    use std::collections::HashSet;
-
-    #[test]
-    fn number_of_tiles_const_matches_computation() {
-        assert_eq!(Vec2::iter().count(), Board::NUMBER_OF_TILES as usize);
+/// Without `std` there is no diagram rendering, so boards are displayed in [FEN](fen) instead.
+#[cfg(not(any(feature = "std", test)))]
+impl core::fmt::Display for Board {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
+        f.write_str(&self.to_fen())
     }
+}
 
-    #[test]
-    fn number_of_files_const_matches_computation() {
-        assert_eq!(
+#[cfg(any(feature = "std", test))]
+impl Board {
+    /// Parses a board from a diagram like the ones produced by [`Board`]'s `Display`
+    /// implementation.
+    ///
+    /// Pieces can be written either as their [emoji](Piece::emoji) or as letters (`P`, `N`,
+    /// `B`, `R`, `Q` and `K`), uppercase for white and lowercase for black. Empty tiles are
+    /// spaces. Tiles can be delimited by `⟨` and `⟩` or, as in [`Board::to_ascii`], by `<` and
+    /// `>`. The diagram can be indented and have surrounding whitespace.
+    pub fn from_ascii_diagram(diagram: &str) -> Result<Self, DiagramError> {
+        // Render a diagram with a unique character on each tile to find out where each tile is.
+        const MARKER_OFFSET: u32 = 0xE000;
+        let template = HexagonalBoard::from_iter(
+            Vec2::iter().map(|position| ([position.x() as i32, position.y() as i32], position)),
+        )
+        .render_with(|position| {
+            char::from_u32(MARKER_OFFSET + Board::index(*position) as u32)
+                .expect("Private use area characters are valid")
+        });
+
+        let positions = diagram_tiles(&template)
+            .map(|marker| Board::index_to_vec((marker as u32 - MARKER_OFFSET) as usize));
+
+        let tiles = diagram_tiles(diagram).collect::<Vec<_>>();
+        if tiles.len() != Board::NUMBER_OF_TILES as usize {
+            return Err(DiagramError::WrongNumberOfTiles {
+                expected: Board::NUMBER_OF_TILES as usize,
+                found: tiles.len(),
+            });
+        }
+
+        let mut board = Board {
+            pieces: [[None; Self::NUMBER_OF_TILES as usize]; 2],
+            last_move: None,
+        };
+
+        for (position, tile) in positions.zip(tiles) {
+            if tile == ' ' {
+                continue;
+            }
+
+            let (piece, color) =
+                Piece::from_char(tile).ok_or(DiagramError::UnknownPiece { tile, position })?;
+
+            board.get_mut(position, color).replace(piece);
+        }
+
+        for color in [Color::White, Color::Black] {
+            let count = board.pieces(color).filter(|&p| p == Piece::King).count();
+            if count != 1 {
+                return Err(DiagramError::WrongNumberOfKings { color, count });
+            }
+        }
+
+        Ok(board)
+    }
+
+    /// Renders the board like its `Display` implementation, but using only ASCII characters.
+    ///
+    /// Pieces are written as their [letter](Piece::representing_letter), uppercase for white and
+    /// lowercase for black, and the angle brackets of the tiles as `<` and `>`, so the diagram
+    /// lines up in any monospace font. It can be parsed back with [`Board::from_ascii_diagram`].
+    pub fn to_ascii(&self) -> String {
+        let hex_board: HexagonalBoard<_> = Vec2::iter()
+            .map(|position| {
+                let vec = [position.x() as i32, position.y() as i32];
+                let val = self.get_either(position);
+
+                (vec, val)
+            })
+            .collect();
+
+        hex_board
+            .render_with(|val| {
+                val.map_or(' ', |(piece, color)| match color {
+                    Color::White => piece.representing_letter(),
+                    Color::Black => piece.representing_letter().to_ascii_lowercase(),
+                })
+            })
+            .replace('⟨', "<")
+            .replace('⟩', ">")
+    }
+
+    /// Renders the board marking, for every tile, whether the piece at `origin` can move there.
+    ///
+    /// Legal destinations are marked with `x`, the origin with the piece itself and every other
+    /// tile with the [`MoveError::code`] of why the move is not possible.
+    #[cfg(any(test, feature = "debug-diagrams"))]
+    pub fn debug_moves(&self, origin: Vec2, color: Color) -> String {
+        let hex_board: HexagonalBoard<_> = Vec2::iter()
+            .map(|destination| {
+                let vec = [destination.x() as i32, destination.y() as i32];
+                let val = if destination == origin {
+                    self.get(origin, color)
+                        .map_or('o', |piece| piece.emoji(color))
+                } else {
+                    match self.get_move(origin, destination, color) {
+                        Ok(_) => 'x',
+                        Err(err) => err.code(),
+                    }
+                };
+
+                (vec, val)
+            })
+            .collect();
+
+        hex_board.render_with(|&c| c)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        Color, Move,
+        board::{Board, BoardStatus, DiagramError, InvalidBoardError},
+        coordinate::Vec2,
+        diagrams,
+        piece::{MoveError, Piece},
+        vec2,
+    };
+    use std::collections::HashSet;
+
+    #[test]
+    fn number_of_tiles_const_matches_computation() {
+        assert_eq!(Vec2::iter().count(), Board::NUMBER_OF_TILES as usize);
+    }
+
+    #[test]
+    fn number_of_files_const_matches_computation() {
+        assert_eq!(
             Vec2::iter()
                 .map(|pos| pos.file())
                 .collect::<HashSet<_>>()
@@ -351,6 +1211,74 @@ mod tests {
         );
     }
 
+    #[test]
+    fn number_of_ranks_const_matches_longest_file() {
+        let longest_file = (0..Board::NUMBER_OF_FILES)
+            .map(|file| Vec2::iter().filter(|pos| pos.file() == file).count())
+            .max();
+
+        assert_eq!(longest_file, Some(Board::NUMBER_OF_RANKS as usize));
+    }
+
+    #[test]
+    fn coordinate_consts_match_computation() {
+        assert_eq!(Vec2::iter().map(|pos| pos.x()).max(), Some(Vec2::MAX));
+        assert_eq!(Vec2::iter().map(|pos| pos.y()).max(), Some(Vec2::MAX));
+        assert_eq!(
+            Vec2::iter().map(|pos| pos.rank()).max(),
+            Some(Vec2::MAX_RANK)
+        );
+        assert_eq!(
+            Vec2::iter().map(|pos| pos.file()).max(),
+            Some(Vec2::MAX_FILE)
+        );
+        assert_eq!(
+            Vec2::iter().map(|pos| pos.x().abs_diff(pos.y())).max(),
+            Some(Vec2::WIDTH)
+        );
+        assert_eq!(
+            Vec2::iter().map(|pos| pos.distance(Vec2::CENTER)).max(),
+            Some(Board::SIZE)
+        );
+    }
+
+    #[test]
+    fn placed_pieces_match_all_piece_positions() {
+        let board = Board::default();
+        let placed = board.placed_pieces().collect::<Vec<_>>();
+        let positions = board.all_piece_positions().collect::<Vec<_>>();
+
+        assert_eq!(placed.len(), positions.len());
+        for (placed, (position, piece, color)) in placed.into_iter().zip(positions) {
+            assert_eq!(
+                (placed.position, placed.piece, placed.color),
+                (position, piece, color)
+            );
+            assert_eq!(board.piece_at(position), Some(placed));
+        }
+
+        assert_eq!(board.piece_at(Vec2::CENTER), None);
+    }
+
+    #[test]
+    fn index_is_always_in_bounds() {
+        for position in Vec2::iter() {
+            let index = Board::index(position);
+            assert!(
+                index < Board::NUMBER_OF_TILES as usize,
+                "{position} has index {index}"
+            );
+        }
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "invalid position")]
+    fn indexing_with_invalid_position_panics_in_debug() {
+        let board = Board::default();
+        board.get(Vec2::new_unchecked(10, 0), Color::White);
+    }
+
     #[test]
     fn index_is_dense_and_unique() {
         let mut indices = (0..Board::NUMBER_OF_TILES as usize).collect::<HashSet<_>>();
@@ -368,14 +1296,24 @@ mod tests {
 
     #[test]
     fn indices_match_diagram() {
-        let rendered = diagrams::visualize_tile_property(
-            Board::index,
-            |width| char::from_digit(*width as u32 % 36, 36).unwrap(),
-        );
+        let rendered = diagrams::visualize_tile_property(Board::index, |width| {
+            char::from_digit(*width as u32 % 36, 36).unwrap()
+        });
 
         assert_eq!(rendered.trim(), diagrams::INDICES.trim());
     }
 
+    #[test]
+    fn iter_by_index_matches_index() {
+        let mut count = 0;
+        for (i, position) in Vec2::iter_by_index().enumerate() {
+            assert_eq!(Board::index(position), i, "{position} is not at index {i}");
+            count += 1;
+        }
+
+        assert_eq!(count, Board::NUMBER_OF_TILES as usize);
+    }
+
     #[test]
     fn vec_to_index_to_vec_is_identity() {
         for position in Vec2::iter() {
@@ -396,11 +1334,1009 @@ mod tests {
         }
     }
 
+    #[test]
+    fn indexed_pieces_match_piece_positions() {
+        let board = Board::default();
+        for color in [Color::White, Color::Black] {
+            let indexed = board.indexed_pieces(color).collect::<Vec<_>>();
+            let positioned = board
+                .piece_positions(color)
+                .map(|(position, piece)| (Board::index(position), piece))
+                .collect::<Vec<_>>();
+
+            assert_eq!(indexed, positioned);
+        }
+    }
+
+    #[test]
+    fn collect_moves_into_matches_possible_moves() {
+        let mut board = Board::default();
+        let mut buffer = vec![Move::Regular {
+            origin: Vec2::ZERO,
+            destination: Vec2::CENTER,
+            captures: false,
+        }];
+
+        for color in [Color::White, Color::Black, Color::White, Color::Black] {
+            board.collect_moves_into(color, &mut buffer);
+
+            let expected = board.possible_moves(color).collect::<HashSet<_>>();
+            assert_eq!(buffer.iter().copied().collect::<HashSet<_>>(), expected);
+            assert_eq!(buffer.len(), expected.len());
+
+            board.apply_move_unchecked(buffer[0], color);
+        }
+    }
+
+    #[test]
+    fn moves_of_kind_yields_the_moves_of_those_pieces() {
+        let board = Board::default();
+        for color in [Color::White, Color::Black] {
+            let pawn_moves = board
+                .moves_of_kind(color, Piece::Pawn)
+                .collect::<HashSet<_>>();
+            let expected = board
+                .possible_moves(color)
+                .filter(|mov| board.get(mov.origin_for(color), color) == Some(Piece::Pawn))
+                .collect::<HashSet<_>>();
+
+            assert_eq!(pawn_moves, expected);
+            assert!(!pawn_moves.is_empty());
+            assert!(pawn_moves.iter().all(|mov| matches!(
+                mov,
+                Move::Regular {
+                    captures: false,
+                    ..
+                }
+            )));
+        }
+    }
+
+    #[test]
+    fn legal_move_counts_by_piece() {
+        let board = Board::default();
+        for color in [Color::White, Color::Black] {
+            let counts = board.legal_move_count_by_piece(color);
+            assert!(counts[Piece::Pawn] > 0);
+            assert!(counts[Piece::Knight] > 0);
+
+            // Unlike in regular chess, the other pieces are not boxed in at the start.
+            for piece in [
+                Piece::Pawn,
+                Piece::Knight,
+                Piece::Bishop,
+                Piece::Rook,
+                Piece::Queen,
+                Piece::King,
+            ] {
+                assert_eq!(
+                    counts[piece],
+                    board.moves_of_kind(color, piece).count(),
+                    "{piece}"
+                );
+            }
+
+            assert_eq!(
+                counts.iter().sum::<usize>(),
+                board.possible_moves(color).count()
+            );
+        }
+    }
+
+    #[test]
+    fn attacked_tiles_matches_is_attacked() {
+        let mut board = Board::default();
+        for color in [Color::White, Color::Black, Color::White, Color::Black] {
+            for by in [Color::White, Color::Black] {
+                let attacked = board.attacked_tiles(by);
+                for position in Vec2::iter() {
+                    assert_eq!(
+                        attacked[Board::index(position)],
+                        board.is_attacked(position, by),
+                        "{position} attacked by {by}"
+                    );
+                }
+            }
+
+            let mov = board.possible_moves(color).last().unwrap();
+            board.apply_move_unchecked(mov, color);
+        }
+    }
+
+    #[test]
+    fn pawns_attack_only_in_capture_directions() {
+        let mut board = Board::new_minimal(vec2!(0, 0), vec2!(10, 10)).unwrap();
+        board
+            .get_mut(vec2!(4, 2), Color::White)
+            .replace(Piece::Pawn);
+        board
+            .get_mut(vec2!(6, 8), Color::Black)
+            .replace(Piece::Pawn);
+
+        let white = board.attacked_tiles(Color::White);
+        assert!(white[Board::index(vec2!(5, 2))]);
+        assert!(white[Board::index(vec2!(4, 3))]);
+        assert!(!white[Board::index(vec2!(5, 3))]);
+        assert!(!white[Board::index(vec2!(3, 2))]);
+
+        let black = board.attacked_tiles(Color::Black);
+        assert!(black[Board::index(vec2!(5, 8))]);
+        assert!(black[Board::index(vec2!(6, 7))]);
+        assert!(!black[Board::index(vec2!(5, 7))]);
+    }
+
+    #[test]
+    fn knight_move_gives_check() {
+        let mut board = Board::new_minimal(vec2!(0, 0), vec2!(10, 10)).unwrap();
+        board
+            .get_mut(vec2!(4, 8), Color::White)
+            .replace(Piece::Knight);
+
+        let (checking, _) = board
+            .get_move(vec2!(4, 8), vec2!(7, 9), Color::White)
+            .unwrap();
+        assert!(board.gives_check(checking, Color::White));
+
+        let (quiet, _) = board
+            .get_move(vec2!(4, 8), vec2!(3, 5), Color::White)
+            .unwrap();
+        assert!(!board.gives_check(quiet, Color::White));
+    }
+
+    #[test]
+    fn transpositions_share_key() {
+        let play = |moves: [(Vec2, Vec2); 4]| {
+            let mut board = Board::default();
+            for ((origin, destination), color) in moves
+                .into_iter()
+                .zip([Color::White, Color::Black].into_iter().cycle())
+            {
+                board.try_move(origin, destination, color).unwrap();
+            }
+
+            board
+        };
+
+        let a = play([
+            (vec2!(0, 2), vec2!(3, 3)),
+            (vec2!(10, 8), vec2!(7, 7)),
+            (vec2!(2, 0), vec2!(5, 1)),
+            (vec2!(8, 10), vec2!(5, 9)),
+        ]);
+
+        let b = play([
+            (vec2!(2, 0), vec2!(5, 1)),
+            (vec2!(8, 10), vec2!(5, 9)),
+            (vec2!(0, 2), vec2!(3, 3)),
+            (vec2!(10, 8), vec2!(7, 7)),
+        ]);
+
+        assert_ne!(a, b);
+        assert_eq!(a.transposition_key(), b.transposition_key());
+    }
+
+    #[test]
+    fn en_passant_file_is_part_of_the_key() {
+        let mut double_step = Board::default();
+        double_step
+            .try_move(vec2!(4, 2), vec2!(6, 4), Color::White)
+            .unwrap();
+
+        let mut single_steps = Board::default();
+        // The knight moves in a triangle so that it's black's turn in both boards.
+        for (origin, destination, color) in [
+            (vec2!(0, 2), vec2!(3, 3), Color::White),
+            (vec2!(10, 8), vec2!(7, 7), Color::Black),
+            (vec2!(3, 3), vec2!(2, 5), Color::White),
+            (vec2!(7, 7), vec2!(10, 8), Color::Black),
+            (vec2!(2, 5), vec2!(0, 2), Color::White),
+            (vec2!(10, 8), vec2!(7, 7), Color::Black),
+            (vec2!(4, 2), vec2!(5, 3), Color::White),
+            (vec2!(7, 7), vec2!(10, 8), Color::Black),
+            (vec2!(5, 3), vec2!(6, 4), Color::White),
+        ] {
+            single_steps.try_move(origin, destination, color).unwrap();
+        }
+
+        assert_ne!(
+            double_step.transposition_key(),
+            single_steps.transposition_key()
+        );
+
+        // Same placement, same turn, but only one of them can capture en passant.
+        let key = double_step.transposition_key();
+        assert_eq!(key.pieces, single_steps.transposition_key().pieces);
+        assert_eq!(key.turn, single_steps.transposition_key().turn);
+        assert_eq!(key.en_passant_file, Some(vec2!(6, 4).file()));
+    }
+
+    #[test]
+    fn status_of_initial_position_is_ongoing() {
+        let board = Board::default();
+        assert_eq!(board.status(Color::White), BoardStatus::Ongoing);
+        assert_eq!(board.status(Color::Black), BoardStatus::Ongoing);
+    }
+
+    #[test]
+    fn moves_from_matches_filtering_possible_moves() {
+        let mut board = Board::default();
+        let mov = board.possible_moves(Color::White).next().unwrap();
+        board.apply_move(mov, Color::White).unwrap();
+
+        for color in [Color::White, Color::Black] {
+            for origin in Vec2::iter() {
+                let expected = board
+                    .possible_moves(color)
+                    .filter(|mov| mov.origin() == origin)
+                    .collect::<Vec<_>>();
+                assert_eq!(
+                    board.moves_from(origin, color).collect::<Vec<_>>(),
+                    expected
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn reachable_tiles_are_pseudo_legal_destinations() {
+        let mut board = Board::new_minimal(vec2!(0, 0), vec2!(10, 10)).unwrap();
+        // The rook is pinned by the bishop, so it can reach tiles it can't legally move to.
+        board
+            .get_mut(vec2!(2, 1), Color::White)
+            .replace(Piece::Rook);
+        board
+            .get_mut(vec2!(6, 3), Color::Black)
+            .replace(Piece::Bishop);
+        board
+            .get_mut(vec2!(5, 5), Color::Black)
+            .replace(Piece::Knight);
+
+        let reachable = |origin| board.reachable_tiles(origin).collect::<HashSet<_>>();
+        let tiles = |tiles: &[(u8, u8)]| {
+            tiles
+                .iter()
+                .map(|&(x, y)| Vec2::new(x, y).unwrap())
+                .collect::<HashSet<_>>()
+        };
+
+        // Every line of the rook, up to the edge of the board.
+        assert_eq!(
+            reachable(vec2!(2, 1)),
+            tiles(&[
+                (2, 0),
+                (1, 0),
+                (0, 1),
+                (1, 1),
+                (2, 2),
+                (2, 3),
+                (2, 4),
+                (2, 5),
+                (2, 6),
+                (2, 7),
+                (3, 1),
+                (4, 1),
+                (5, 1),
+                (6, 1),
+                (3, 2),
+                (4, 3),
+                (5, 4),
+                (6, 5),
+                (7, 6),
+                (8, 7),
+                (9, 8),
+                (10, 9),
+            ])
+        );
+
+        // The twelve jumps of the knight, except the one onto its own bishop.
+        let knight = reachable(vec2!(5, 5));
+        assert_eq!(knight.len(), 11);
+        assert!(!knight.contains(&vec2!(6, 3)));
+
+        // The bishop can capture the rook.
+        assert!(reachable(vec2!(6, 3)).contains(&vec2!(2, 1)));
+
+        // Kings in the corners, where the white one can't step onto its own rook.
+        assert_eq!(
+            reachable(vec2!(0, 0)),
+            tiles(&[(0, 1), (1, 0), (1, 1), (1, 2)])
+        );
+        assert_eq!(
+            reachable(vec2!(10, 10)),
+            tiles(&[(9, 10), (10, 9), (9, 9), (8, 9), (9, 8)])
+        );
+
+        let rook = vec2!(2, 1);
+        assert!(board.moves_from(rook, Color::White).count() < board.reachable_tiles(rook).count());
+        assert_eq!(board.reachable_tiles(vec2!(4, 4)).count(), 0);
+    }
+
+    #[test]
+    fn validate_rejects_impossible_positions() {
+        assert_eq!(Board::default().validate(Color::White), Ok(()));
+
+        let minimal = Board::new_minimal(vec2!(0, 0), vec2!(10, 10)).unwrap();
+        assert_eq!(minimal.validate(Color::White), Ok(()));
+
+        let mut no_king = minimal.clone();
+        no_king.get_mut(vec2!(0, 0), Color::White).take();
+        assert_eq!(
+            no_king.validate(Color::White),
+            Err(InvalidBoardError::WrongNumberOfKings {
+                color: Color::White,
+                count: 0
+            })
+        );
+
+        let mut overlapping = minimal.clone();
+        overlapping
+            .get_mut(vec2!(0, 0), Color::Black)
+            .replace(Piece::Pawn);
+        assert_eq!(
+            overlapping.validate(Color::White),
+            Err(InvalidBoardError::OverlappingPieces {
+                position: vec2!(0, 0)
+            })
+        );
+
+        let mut checked = minimal;
+        checked
+            .get_mut(vec2!(10, 8), Color::White)
+            .replace(Piece::Rook);
+        assert_eq!(
+            checked.validate(Color::White),
+            Err(InvalidBoardError::WaitingPlayerInCheck {
+                color: Color::Black
+            })
+        );
+    }
+
+    #[test]
+    fn from_pieces_builds_valid_boards_only() {
+        assert_eq!(
+            Board::from_pieces(Board::default().all_piece_positions()),
+            Ok(Board::default())
+        );
+
+        let kings = [
+            (vec2!(0, 0), Piece::King, Color::White),
+            (vec2!(10, 10), Piece::King, Color::Black),
+        ];
+        assert_eq!(
+            Board::from_pieces(kings),
+            Ok(Board::new_minimal(vec2!(0, 0), vec2!(10, 10)).unwrap())
+        );
+
+        assert_eq!(
+            Board::from_pieces(
+                kings
+                    .into_iter()
+                    .chain([(vec2!(0, 0), Piece::Pawn, Color::Black)])
+            ),
+            Err(InvalidBoardError::OverlappingPieces {
+                position: vec2!(0, 0)
+            })
+        );
+        assert_eq!(
+            Board::from_pieces(kings.into_iter().take(1)),
+            Err(InvalidBoardError::WrongNumberOfKings {
+                color: Color::Black,
+                count: 0
+            })
+        );
+        assert_eq!(Board::empty().kings(Color::White).count(), 0);
+    }
+
+    #[test]
+    fn kings_handles_any_number_of_kings() {
+        let mut board = Board::new_minimal(vec2!(0, 0), vec2!(10, 10)).unwrap();
+        assert_eq!(board.kings(Color::White).collect::<Vec<_>>(), [vec2!(0, 0)]);
+
+        board.get_mut(vec2!(0, 0), Color::White).take();
+        assert_eq!(board.kings(Color::White).count(), 0);
+
+        board
+            .get_mut(vec2!(8, 9), Color::Black)
+            .replace(Piece::King);
+        let mut kings = board.kings(Color::Black).collect::<Vec<_>>();
+        kings.sort_by_key(|&position| Board::index(position));
+        assert_eq!(kings, [vec2!(8, 9), vec2!(10, 10)]);
+    }
+
+    #[test]
+    fn clone_into_overwrites_the_destination() {
+        let mut board = Board::default();
+        let mov = board.possible_moves(Color::White).next().unwrap();
+        board.apply_move(mov, Color::White).unwrap();
+
+        let mut dst = Board::new_minimal(vec2!(0, 0), vec2!(10, 10)).unwrap();
+        board.clone_into(&mut dst);
+        assert_eq!(dst, board);
+        assert_eq!(dst.turn(), Color::Black);
+    }
+
+    #[test]
+    fn default_bishops_are_on_different_indices() {
+        let board = Board::default();
+        for color in [Color::White, Color::Black] {
+            assert_eq!(board.bishops_by_index(color), [1, 1, 1]);
+        }
+
+        let mut board = Board::new_minimal(vec2!(0, 0), vec2!(10, 10)).unwrap();
+        assert_eq!(board.bishops_by_index(Color::White), [0, 0, 0]);
+
+        // Two tiles along a bishop stride, which have the same index.
+        board
+            .get_mut(vec2!(5, 5), Color::White)
+            .replace(Piece::Bishop);
+        board
+            .get_mut(vec2!(6, 7), Color::White)
+            .replace(Piece::Bishop);
+        let mut expected = [0; 3];
+        expected[vec2!(5, 5).index() as usize] = 2;
+        assert_eq!(board.bishops_by_index(Color::White), expected);
+    }
+
+    #[test]
+    fn bishops_on_the_same_index_are_not_mating_material() {
+        let mut board = Board::new_minimal(vec2!(0, 0), vec2!(10, 10)).unwrap();
+        board
+            .get_mut(vec2!(5, 5), Color::White)
+            .replace(Piece::Bishop);
+        board
+            .get_mut(vec2!(6, 7), Color::White)
+            .replace(Piece::Bishop);
+        assert!(!board.has_mating_material(Color::White));
+
+        let third = Vec2::iter()
+            .find(|tile| tile.index() != vec2!(5, 5).index() && board.get_either(*tile).is_none())
+            .unwrap();
+        board.get_mut(third, Color::White).replace(Piece::Bishop);
+        assert!(board.has_mating_material(Color::White));
+
+        let mut board = Board::new_minimal(vec2!(0, 0), vec2!(10, 10)).unwrap();
+        board
+            .get_mut(vec2!(5, 5), Color::White)
+            .replace(Piece::Knight);
+        assert!(!board.has_mating_material(Color::White));
+        board
+            .get_mut(vec2!(6, 7), Color::White)
+            .replace(Piece::Bishop);
+        assert!(board.has_mating_material(Color::White));
+    }
+
+    #[test]
+    fn lone_queen_mates_in_the_corner() {
+        let mut board = Board::new_minimal(vec2!(0, 0), vec2!(10, 10)).unwrap();
+        board
+            .get_mut(vec2!(8, 8), Color::White)
+            .replace(Piece::Queen);
+
+        assert_eq!(board.status(Color::Black), BoardStatus::Checkmate);
+        assert!(board.is_checkmate(Color::Black));
+        assert!(!board.is_stalemate(Color::Black));
+        assert_eq!(board.status(Color::White), BoardStatus::Ongoing);
+    }
+
+    #[test]
+    fn mating_move_returns_checkmate() {
+        let mut board = Board::new_minimal(vec2!(0, 0), vec2!(10, 10)).unwrap();
+        board
+            .get_mut(vec2!(8, 4), Color::White)
+            .replace(Piece::Queen);
+
+        let mut quiet = board.clone();
+        let (mov, _) = quiet
+            .get_move(vec2!(8, 4), vec2!(8, 5), Color::White)
+            .unwrap();
+        assert_eq!(
+            quiet.apply_move_with_status(mov, Color::White).unwrap(),
+            (None, BoardStatus::Ongoing)
+        );
+
+        let (mov, _) = board
+            .get_move(vec2!(8, 4), vec2!(8, 8), Color::White)
+            .unwrap();
+        assert_eq!(
+            board.apply_move_with_status(mov, Color::White).unwrap(),
+            (None, BoardStatus::Checkmate)
+        );
+        assert!(board.apply_move_with_status(mov, Color::White).is_err());
+    }
+
+    #[test]
+    fn cornered_king_is_stalemated() {
+        let mut board = Board::new_minimal(vec2!(7, 7), vec2!(10, 10)).unwrap();
+        board
+            .get_mut(vec2!(9, 7), Color::White)
+            .replace(Piece::Queen);
+
+        assert_eq!(board.status(Color::Black), BoardStatus::Stalemate);
+        assert!(board.is_stalemate(Color::Black));
+        assert!(!board.is_checkmate(Color::Black));
+    }
+
+    #[test]
+    fn debug_moves_of_pinned_rook() {
+        let mut board = Board::new_minimal(vec2!(0, 0), vec2!(10, 10)).unwrap();
+        board
+            .get_mut(vec2!(3, 3), Color::White)
+            .replace(Piece::Rook);
+        board
+            .get_mut(vec2!(7, 7), Color::Black)
+            .replace(Piece::Rook);
+
+        let rendered = board.debug_moves(vec2!(3, 3), Color::White);
+        assert_eq!(
+            rendered.trim_end(),
+            diagrams::DEBUG_MOVES_PINNED_ROOK.trim_end()
+        );
+    }
+
+    /// Asserts that [`Board::check_evasions`] yields the same moves as [`Board::possible_moves`].
+    fn assert_evasions_match_possible_moves(board: &Board, color: Color) {
+        let evasions = board.check_evasions(color).collect::<Vec<_>>();
+        let unique_evasions = evasions.iter().copied().collect::<HashSet<_>>();
+        assert_eq!(evasions.len(), unique_evasions.len(), "duplicated evasions");
+
+        let possible_moves = board.possible_moves(color).collect::<HashSet<_>>();
+        assert_eq!(unique_evasions, possible_moves, "\n{board}");
+    }
+
+    #[test]
+    fn check_evasions_without_check_are_all_moves() {
+        assert_evasions_match_possible_moves(&Board::default(), Color::White);
+        assert_evasions_match_possible_moves(&Board::default(), Color::Black);
+    }
+
+    #[test]
+    fn check_evasions_of_sliding_check_include_blocks_and_captures() {
+        let mut board = Board::new_minimal(vec2!(0, 0), vec2!(10, 10)).unwrap();
+        board
+            .get_mut(vec2!(5, 5), Color::Black)
+            .replace(Piece::Rook);
+        board
+            .get_mut(vec2!(2, 4), Color::White)
+            .replace(Piece::Knight);
+        board
+            .get_mut(vec2!(3, 0), Color::White)
+            .replace(Piece::Rook);
+        board
+            .get_mut(vec2!(4, 8), Color::White)
+            .replace(Piece::Queen);
+
+        let evasions = board.check_evasions(Color::White).collect::<Vec<_>>();
+        let capture = board.get_move(vec2!(2, 4), vec2!(5, 5), Color::White);
+        let block = board.get_move(vec2!(3, 0), vec2!(3, 3), Color::White);
+        assert!(evasions.contains(&capture.unwrap().0));
+        assert!(evasions.contains(&block.unwrap().0));
+
+        assert_evasions_match_possible_moves(&board, Color::White);
+    }
+
+    #[test]
+    fn check_evasions_of_knight_check() {
+        let mut board = Board::new_minimal(vec2!(0, 0), vec2!(10, 10)).unwrap();
+        board
+            .get_mut(vec2!(3, 1), Color::Black)
+            .replace(Piece::Knight);
+        board
+            .get_mut(vec2!(3, 0), Color::White)
+            .replace(Piece::Rook);
+        board
+            .get_mut(vec2!(1, 2), Color::White)
+            .replace(Piece::Bishop);
+        board
+            .get_mut(vec2!(0, 3), Color::White)
+            .replace(Piece::Pawn);
+
+        assert_evasions_match_possible_moves(&board, Color::White);
+    }
+
+    #[test]
+    fn check_evasions_of_double_check_only_move_the_king() {
+        let mut board = Board::new_minimal(vec2!(0, 0), vec2!(10, 10)).unwrap();
+        board
+            .get_mut(vec2!(5, 5), Color::Black)
+            .replace(Piece::Rook);
+        board
+            .get_mut(vec2!(1, 3), Color::Black)
+            .replace(Piece::Knight);
+        board
+            .get_mut(vec2!(2, 4), Color::White)
+            .replace(Piece::Knight);
+        board
+            .get_mut(vec2!(3, 0), Color::White)
+            .replace(Piece::Rook);
+
+        let king = board.find_king(Color::White);
+        assert!(
+            board
+                .check_evasions(Color::White)
+                .all(|mov| mov.origin() == king)
+        );
+
+        assert_evasions_match_possible_moves(&board, Color::White);
+    }
+
+    /// The legal moves of `color`, checking king safety by applying each candidate move to a copy
+    /// of the board (which is what [`Piece::get_move`] used to do).
+    fn legal_moves_by_cloning(board: &Board, color: Color) -> HashSet<Move> {
+        let mut moves = HashSet::new();
+        for (origin, piece) in board.piece_positions(color) {
+            for destination in Vec2::iter() {
+                let Ok((mov, _)) = piece.get_move_no_checks(origin, destination, board, color)
+                else {
+                    continue;
+                };
+
+                let mut test_board = board.clone();
+                test_board.apply_move_unchecked(mov, color);
+                if test_board.in_check(color).is_none() {
+                    moves.insert(mov);
+                }
+            }
+        }
+
+        moves
+    }
+
+    #[test]
+    fn king_safety_filter_matches_cloning_the_board() {
+        let mut board = Board::new_minimal(vec2!(0, 0), vec2!(10, 10)).unwrap();
+        board
+            .get_mut(vec2!(5, 5), Color::Black)
+            .replace(Piece::Rook);
+        board
+            .get_mut(vec2!(1, 1), Color::White)
+            .replace(Piece::Bishop);
+        board
+            .get_mut(vec2!(2, 4), Color::White)
+            .replace(Piece::Knight);
+        board
+            .get_mut(vec2!(3, 0), Color::White)
+            .replace(Piece::Rook);
+        assert_eq!(
+            board.possible_moves(Color::White).collect::<HashSet<_>>(),
+            legal_moves_by_cloning(&board, Color::White),
+        );
+
+        // A deterministic playout, to cover a variety of positions.
+        let mut board = Board::default();
+        for ply in 0..30 {
+            let color = board.turn();
+            let moves = board.possible_moves(color).collect::<Vec<_>>();
+            assert_eq!(
+                moves.iter().copied().collect::<HashSet<_>>(),
+                legal_moves_by_cloning(&board, color),
+                "\n{board}"
+            );
+
+            let Some(&mov) = moves.get((ply * 7) % moves.len().max(1)) else {
+                break;
+            };
+            board.apply_move_unchecked(mov, color);
+        }
+    }
+
+    /// The status of the board found by trying every move, without the shortcuts of
+    /// [`Board::status`].
+    fn status_by_brute_force(board: &Board, to_move: Color) -> BoardStatus {
+        if !legal_moves_by_cloning(board, to_move).is_empty() {
+            BoardStatus::Ongoing
+        } else if board.in_check(to_move).is_some() {
+            BoardStatus::Checkmate
+        } else {
+            BoardStatus::Stalemate
+        }
+    }
+
+    #[test]
+    fn status_matches_brute_force() {
+        let mut verdicts = Vec::new();
+
+        // A lone king in the corner against a king and a queen, with every placement of the
+        // queen, gives plenty of mates and stalemates.
+        let corner = Vec2::iter().filter(|king| king.rank() <= 3);
+        for (king, queen) in corner.flat_map(|king| Vec2::iter().map(move |q| (king, q))) {
+            let Some(mut board) = Board::new_minimal(king, vec2!(2, 2)) else {
+                continue;
+            };
+            if board.get_either(queen).is_some() {
+                continue;
+            }
+
+            board.get_mut(queen, Color::Black).replace(Piece::Queen);
+            if board.validate(Color::White).is_err() {
+                continue;
+            }
+
+            let status = board.status(Color::White);
+            assert_eq!(
+                status,
+                status_by_brute_force(&board, Color::White),
+                "\n{board}"
+            );
+            if !verdicts.contains(&status) {
+                verdicts.push(status);
+            }
+        }
+
+        // A few deterministic playouts, for positions with more pieces.
+        for stride in [3, 7, 11] {
+            let mut board = Board::default();
+            for ply in 0..40 {
+                let color = board.turn();
+                let status = board.status(color);
+                assert_eq!(status, status_by_brute_force(&board, color), "\n{board}");
+
+                let moves = board.possible_moves(color).collect::<Vec<_>>();
+                let Some(&mov) = moves.get((ply * stride) % moves.len().max(1)) else {
+                    break;
+                };
+                board.apply_move_unchecked(mov, color);
+            }
+        }
+
+        assert_eq!(verdicts.len(), 3, "{verdicts:?}");
+    }
+
+    #[test]
+    fn king_zone_is_smaller_at_the_edge() {
+        let board = Board::new_minimal(Vec2::CENTER, vec2!(0, 0)).unwrap();
+        let zone = board.king_zone(Color::White).collect::<HashSet<_>>();
+        assert_eq!(zone.len(), 7);
+        assert!(zone.iter().all(|&tile| tile.distance(Vec2::CENTER) <= 1));
+
+        assert_eq!(board.king_zone(Color::Black).count(), 4);
+
+        let edge = vec2!(3, 0);
+        let board = Board::new_minimal(edge, vec2!(10, 10)).unwrap();
+        assert_eq!(board.king_zone(Color::White).count(), 5);
+    }
+
+    #[test]
+    fn swapping_colors_twice_is_identity() {
+        let board = Board::default();
+        assert_eq!(board.with_colors_swapped().with_colors_swapped(), board);
+    }
+
+    #[test]
+    fn swapping_colors_exchanges_piece_values() {
+        let mut board = Board::default();
+        board.get_mut(vec2!(1, 0), Color::White).take();
+
+        let swapped = board.with_colors_swapped();
+        assert_eq!(
+            swapped.total_piece_value(Color::White),
+            board.total_piece_value(Color::Black)
+        );
+        assert_eq!(
+            swapped.total_piece_value(Color::Black),
+            board.total_piece_value(Color::White)
+        );
+        assert_ne!(
+            swapped.total_piece_value(Color::White),
+            swapped.total_piece_value(Color::Black)
+        );
+    }
+
+    #[test]
+    fn default_board_round_trips_through_diagram() {
+        let board = Board::default();
+        assert_eq!(
+            Board::from_ascii_diagram(&board.to_string()).unwrap(),
+            board
+        );
+        assert_eq!(
+            Board::from_ascii_diagram(diagrams::INITIAL_BOARD).unwrap(),
+            board
+        );
+    }
+
+    #[test]
+    fn diagrams_can_use_letters_and_indentation() {
+        let mut board = Board::new_minimal(vec2!(0, 0), vec2!(10, 10)).unwrap();
+        board
+            .get_mut(vec2!(3, 3), Color::White)
+            .replace(Piece::Rook);
+        board
+            .get_mut(vec2!(7, 7), Color::Black)
+            .replace(Piece::Queen);
+
+        let diagram = board
+            .to_string()
+            .replace('♚', "K")
+            .replace('♜', "R")
+            .replace('♔', "k")
+            .replace('♕', "q")
+            .lines()
+            .map(|line| format!("        {line}\n"))
+            .collect::<String>();
+
+        assert_eq!(Board::from_ascii_diagram(&diagram).unwrap(), board);
+    }
+
+    #[test]
+    fn diagrams_without_kings_are_rejected() {
+        let diagram = Board::default().to_string().replace('♔', " ");
+        assert!(matches!(
+            Board::from_ascii_diagram(&diagram),
+            Err(DiagramError::WrongNumberOfKings {
+                color: Color::Black,
+                count: 0
+            })
+        ));
+    }
+
     #[test]
     fn intial_board_matches_diagram() {
         let rendered = Board::default().to_string();
         assert_eq!(rendered.trim(), diagrams::INITIAL_BOARD.trim());
     }
+
+    #[test]
+    fn initial_board_matches_ascii_diagram() {
+        let rendered = Board::default().to_ascii();
+        assert!(rendered.is_ascii());
+        assert_eq!(rendered.trim(), diagrams::INITIAL_BOARD_ASCII.trim());
+        assert_eq!(
+            Board::from_ascii_diagram(&rendered).unwrap(),
+            Board::default()
+        );
+    }
+
+    #[test]
+    fn phase_goes_from_full_material_to_bare_kings() {
+        assert_eq!(Board::default().phase(), 0.0);
+
+        let mut board = Board::new_minimal(vec2!(0, 0), vec2!(10, 10)).unwrap();
+        assert_eq!(board.phase(), 1.0);
+
+        board
+            .get_mut(Vec2::CENTER, Color::White)
+            .replace(Piece::Pawn);
+        assert_eq!(board.phase(), 1.0);
+
+        board
+            .get_mut(Vec2::CENTER, Color::White)
+            .replace(Piece::Queen);
+        assert!(board.phase() > 0.0 && board.phase() < 1.0);
+    }
+
+    #[test]
+    fn material_signatures() {
+        assert_eq!(
+            Board::default().material_signature(),
+            "KQRRBBBNNPPPPPPPPPvKQRRBBBNNPPPPPPPPP"
+        );
+        assert!(!Board::is_drawn_material_signature(
+            &Board::default().material_signature()
+        ));
+
+        let mut board = Board::new_minimal(vec2!(0, 0), vec2!(10, 10)).unwrap();
+        assert_eq!(board.material_signature(), "KvK");
+        assert!(Board::is_drawn_material_signature(
+            &board.material_signature()
+        ));
+
+        board
+            .get_mut(Vec2::CENTER, Color::White)
+            .replace(Piece::Queen);
+        assert_eq!(board.material_signature(), "KQvK");
+        assert!(!Board::is_drawn_material_signature(
+            &board.material_signature()
+        ));
+    }
+
+    #[test]
+    fn pawns_reaching_a_promotion_tile_have_to_promote() {
+        let mut board = Board::new_minimal(vec2!(0, 0), vec2!(10, 10)).unwrap();
+        let (origin, destination) = (vec2!(9, 7), vec2!(10, 8));
+        board.get_mut(origin, Color::White).replace(Piece::Pawn);
+
+        let regular = Move::regular(origin, destination, &board, Color::White);
+        assert!(board.is_pawn_promotion_move(regular, Color::White));
+        assert!(matches!(
+            board.clone().apply_move(regular, Color::White),
+            Err(MoveError::PromotionRequired { origin: o, destination: d })
+                if (o, d) == (origin, destination)
+        ));
+
+        let promotion = Move::Promotion {
+            file: origin.file(),
+            captures: None,
+            promoting_to: Piece::Knight,
+        };
+        assert!(board.is_pawn_promotion_move(promotion, Color::White));
+        assert_eq!(
+            board.clone().apply_move(promotion, Color::White).ok(),
+            Some(None)
+        );
+
+        let pawn_to_king = Move::Promotion {
+            file: origin.file(),
+            captures: None,
+            promoting_to: Piece::King,
+        };
+        assert!(matches!(
+            board.apply_move(pawn_to_king, Color::White),
+            Err(MoveError::InvalidPromotion { .. })
+        ));
+
+        board.apply_move(promotion, Color::White).unwrap();
+        assert_eq!(board.get(origin, Color::White), None);
+        assert_eq!(board.get(destination, Color::White), Some(Piece::Knight));
+        assert_eq!(board.turn(), Color::Black);
+    }
+
+    #[test]
+    fn moves_with_a_wrong_capture_flag_are_rejected() {
+        let mut board = Board::default();
+        let mut captures = 0;
+        for _ in 0..20 {
+            let color = board.turn();
+            for mov in board.possible_moves(color) {
+                let Move::Regular {
+                    origin,
+                    destination,
+                    captures: legal_captures,
+                } = mov
+                else {
+                    continue;
+                };
+
+                captures += legal_captures as usize;
+                let flipped = Move::Regular {
+                    origin,
+                    destination,
+                    captures: !legal_captures,
+                };
+                assert!(matches!(
+                    board.clone().apply_move(flipped, color),
+                    Err(MoveError::WrongCaptureFlag { .. })
+                ));
+            }
+
+            let mov = board.possible_moves(color).last().unwrap();
+            board.apply_move(mov, color).unwrap();
+        }
+
+        assert!(captures > 0, "Both flags should be tested");
+    }
+
+    #[test]
+    fn promotions_are_generated_for_every_piece() {
+        let mut board = Board::new_minimal(vec2!(10, 10), vec2!(5, 5)).unwrap();
+        let (origin, destination) = (vec2!(1, 3), vec2!(0, 3));
+        board.get_mut(origin, Color::Black).replace(Piece::Pawn);
+        board
+            .get_mut(destination, Color::White)
+            .replace(Piece::Rook);
+
+        let promotions = board
+            .moves_from(origin, Color::Black)
+            .filter(|mov| mov.destination_for(Color::Black) == destination)
+            .collect::<Vec<_>>();
+        assert_eq!(promotions.len(), 4);
+        for mov in promotions {
+            assert!(matches!(
+                mov,
+                Move::Promotion {
+                    captures: Some(_),
+                    ..
+                }
+            ));
+            assert!(board.possible_moves(Color::Black).any(|m| m == mov));
+
+            let mut promoted = board.clone();
+            assert_eq!(
+                promoted.apply_move(mov, Color::Black).unwrap(),
+                Some(Piece::Rook)
+            );
+            assert_eq!(promoted.turn(), Color::White);
+        }
+    }
 }
 
 // Huge workaround for lack of const generics in serde...
@@ -408,7 +2344,7 @@ mod tests {
 // out to be a huge pain in the ass.
 #[cfg(feature = "serde")]
 mod serde_piece_nested_array {
-    use super::Piece;
+    use super::{Board, Piece};
     use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
     #[derive(Serialize, Deserialize)]
@@ -421,9 +2357,11 @@ mod serde_piece_nested_array {
         inner: [T; N],
     }
 
-    type Arr = SerializableArray<SerializableArray<Option<Piece>, 91>, 2>;
+    const TILES: usize = Board::NUMBER_OF_TILES as usize;
+
+    type Arr = SerializableArray<SerializableArray<Option<Piece>, TILES>, 2>;
 
-    pub fn serialize<S>(data: &[[Option<Piece>; 91]; 2], ser: S) -> Result<S::Ok, S::Error>
+    pub fn serialize<S>(data: &[[Option<Piece>; TILES]; 2], ser: S) -> Result<S::Ok, S::Error>
     where
         S: Serializer,
     {
@@ -437,7 +2375,7 @@ mod serde_piece_nested_array {
         data.serialize(ser)
     }
 
-    pub fn deserialize<'de, D>(deserializer: D) -> Result<[[Option<Piece>; 91]; 2], D::Error>
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<[[Option<Piece>; TILES]; 2], D::Error>
     where
         D: Deserializer<'de>,
     {
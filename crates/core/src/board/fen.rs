@@ -0,0 +1,371 @@
+//! A compact, single-line text representation of a [`Board`], in the spirit of chess FEN.
+//!
+//! The notation has two fields separated by a space:
+//!
+//! 1. The pieces, rank by rank (see [`Vec2::rank`]) from white's side, each rank from left to
+//!    right and separated by `/`. Pieces are written with their
+//!    [letter](Piece::representing_letter), uppercase for white and lowercase for black. Runs of
+//!    empty tiles are written as the number of tiles.
+//! 2. The last move, as `x,y-x,y` (or `x,y*x,y` if it was a capture), or `-` if there wasn't
+//!    one. Promotions are followed by `=` and the letter of the piece the pawn promoted to, in
+//!    the case of its color (e.g., `9,9-10,10=Q`). The board infers whose turn it is and whether
+//!    en passant is possible from it, so it takes the place of FEN's turn and en passant fields.
+//!
+//! There is no castling in hexagonal chess, and move counters belong to a game rather than to a
+//! board, so there are no more fields.
+//!
+//! The starting position is:
+//!
+//! ```text
+//! B/QK/NBN/R2R/P1B1P/1P2P1/1P1P1/2PP2/2P2/6/5/6/2p2/2pp2/1p1p1/1p2p1/p1b1p/r2r/nbn/qk/b -
+//! ```
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::Write as _;
+
+use crate::{
+    Color, Move, Vec2,
+    piece::{Piece, pawn},
+};
+
+use super::Board;
+
+impl Board {
+    /// Writes the board in [FEN](self).
+    pub fn to_fen(&self) -> String {
+        let mut output = String::new();
+        for rank in 0..=Vec2::MAX_RANK {
+            if rank > 0 {
+                output.push('/');
+            }
+
+            let mut empty = 0;
+            for position in rank_tiles(rank) {
+                let Some((piece, color)) = self.get_either(position) else {
+                    empty += 1;
+                    continue;
+                };
+
+                if empty > 0 {
+                    write!(output, "{empty}").unwrap();
+                    empty = 0;
+                }
+
+                let letter = piece.representing_letter();
+                output.push(color.choose(letter, letter.to_ascii_lowercase()));
+            }
+
+            if empty > 0 {
+                write!(output, "{empty}").unwrap();
+            }
+        }
+
+        match self.last_move {
+            Some(Move::Regular {
+                origin,
+                destination,
+                captures,
+            }) => write!(
+                output,
+                " {},{}{}{},{}",
+                origin.x(),
+                origin.y(),
+                if captures { '*' } else { '-' },
+                destination.x(),
+                destination.y()
+            )
+            .unwrap(),
+            Some(
+                mov @ Move::Promotion {
+                    captures,
+                    promoting_to,
+                    ..
+                },
+            ) => {
+                let color = self.turn().other();
+                let origin = mov.origin_for(color);
+                let destination = mov.destination_for(color);
+                let letter = promoting_to.representing_letter();
+                write!(
+                    output,
+                    " {},{}{}{},{}={}",
+                    origin.x(),
+                    origin.y(),
+                    if captures.is_some() { '*' } else { '-' },
+                    destination.x(),
+                    destination.y(),
+                    color.choose(letter, letter.to_ascii_lowercase())
+                )
+                .unwrap()
+            }
+            // En passant can't be applied yet (see `Board::apply_move_unchecked`), so boards only
+            // have it as their last move if they were deserialized. It is left out, as if there
+            // was no last move.
+            Some(Move::EnPassant { .. }) | None => output.push_str(" -"),
+        }
+
+        output
+    }
+
+    /// Reads a board in [FEN](self).
+    pub fn from_fen(fen: &str) -> Result<Self, FenError> {
+        let (placement, last_move) = fen.trim().split_once(' ').ok_or(FenError::MissingField)?;
+
+        let ranks = placement.split('/').collect::<Vec<_>>();
+        if ranks.len() != Vec2::MAX_RANK as usize + 1 {
+            return Err(FenError::WrongNumberOfRanks {
+                expected: Vec2::MAX_RANK as usize + 1,
+                found: ranks.len(),
+            });
+        }
+
+        let mut board = Board {
+            pieces: [[None; Self::NUMBER_OF_TILES as usize]; 2],
+            last_move: parse_last_move(last_move)?,
+        };
+
+        for (rank, pieces) in (0..).zip(ranks) {
+            let mut tiles = rank_tiles(rank);
+            let mut chars = pieces.chars().peekable();
+            while let Some(c) = chars.next() {
+                if let Some(digit) = c.to_digit(10) {
+                    let mut empty = digit;
+                    while let Some(digit) = chars.peek().and_then(|c| c.to_digit(10)) {
+                        empty = empty * 10 + digit;
+                        chars.next();
+                    }
+
+                    for _ in 0..empty {
+                        tiles.next().ok_or(FenError::WrongRankLength { rank })?;
+                    }
+
+                    continue;
+                }
+
+                let (piece, color) = Piece::from_char(c)
+                    .filter(|_| c.is_ascii_alphabetic())
+                    .ok_or(FenError::UnknownPiece { piece: c })?;
+
+                let position = tiles.next().ok_or(FenError::WrongRankLength { rank })?;
+                board.get_mut(position, color).replace(piece);
+            }
+
+            if tiles.next().is_some() {
+                return Err(FenError::WrongRankLength { rank });
+            }
+        }
+
+        for color in [Color::White, Color::Black] {
+            let count = board.pieces(color).filter(|&p| p == Piece::King).count();
+            if count != 1 {
+                return Err(FenError::WrongNumberOfKings { color, count });
+            }
+        }
+
+        Ok(board)
+    }
+}
+
+/// The tiles of a rank, from left to right.
+fn rank_tiles(rank: u8) -> impl Iterator<Item = Vec2> {
+    let first_y = Vec2::min_valid_rank_coordinate(rank);
+    (first_y..first_y + Vec2::rank_width(rank)).map(move |y| Vec2::new_unchecked(rank - y, y))
+}
+
+fn parse_last_move(field: &str) -> Result<Option<Move>, FenError> {
+    if field == "-" {
+        return Ok(None);
+    }
+
+    let invalid = || FenError::InvalidLastMove {
+        field: field.to_string(),
+    };
+
+    let (field_move, promotion) = match field.split_once('=') {
+        Some((field_move, letter)) => {
+            let mut chars = letter.chars();
+            let promotion = chars
+                .next()
+                .filter(|c| c.is_ascii_alphabetic())
+                .and_then(Piece::from_char)
+                .filter(|_| chars.next().is_none())
+                .ok_or_else(invalid)?;
+            (field_move, Some(promotion))
+        }
+        None => (field, None),
+    };
+
+    let (captures, (origin, destination)) = match field_move.split_once('-') {
+        Some(split) => (false, split),
+        None => (true, field_move.split_once('*').ok_or_else(invalid)?),
+    };
+
+    let parse_vec2 = |word: &str| {
+        let (x, y) = word.split_once(',')?;
+        Vec2::new(x.parse().ok()?, y.parse().ok()?)
+    };
+
+    let origin = parse_vec2(origin).ok_or_else(invalid)?;
+    let destination = parse_vec2(destination).ok_or_else(invalid)?;
+    let Some((promoting_to, color)) = promotion else {
+        return Ok(Some(Move::Regular {
+            origin,
+            destination,
+            captures,
+        }));
+    };
+
+    let captures = if captures {
+        Some(pawn::capture_side(color, destination - origin).ok_or_else(invalid)?)
+    } else {
+        None
+    };
+
+    // The tiles of a promotion are implied by the file, so check that they are the written ones.
+    let mov = Move::Promotion {
+        file: origin.file(),
+        captures,
+        promoting_to,
+    };
+    let resolves = pawn::PROMOTION_PIECES.contains(&promoting_to)
+        && pawn::promotion_origin(origin.file(), captures, color) == Some(origin)
+        && mov.destination_for(color) == destination;
+    resolves.then_some(Some(mov)).ok_or_else(invalid)
+}
+
+#[allow(missing_docs)]
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum FenError {
+    #[error("Expected the pieces and the last move, separated by a space")]
+    MissingField,
+
+    #[error("Expected {expected} ranks, but found {found}")]
+    WrongNumberOfRanks { expected: usize, found: usize },
+
+    #[error("Rank {rank} doesn't have the right number of tiles")]
+    WrongRankLength { rank: u8 },
+
+    #[error("Unknown piece '{piece}'")]
+    UnknownPiece { piece: char },
+
+    #[error("Invalid last move '{field}'")]
+    InvalidLastMove { field: String },
+
+    #[error("There should be exactly one {color} king, but there are {count}")]
+    WrongNumberOfKings { color: Color, count: usize },
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Board, Color, Move, Side, Vec2, piece::Piece};
+
+    use super::FenError;
+
+    #[test]
+    fn starting_position_fen() {
+        let fen = Board::default().to_fen();
+        assert_eq!(
+            fen,
+            "B/QK/NBN/R2R/P1B1P/1P2P1/1P1P1/2PP2/2P2/6/5/6/2p2/2pp2/1p1p1/1p2p1/p1b1p/r2r/nbn/qk/b -"
+        );
+        assert_eq!(Board::from_fen(&fen).unwrap(), Board::default());
+    }
+
+    #[test]
+    fn fen_round_trips_with_last_move() {
+        let mut board = Board::default();
+        let color = Color::White;
+        let mov = board.possible_moves(color).next().unwrap();
+        board.apply_move(mov, color).unwrap();
+
+        let fen = board.to_fen();
+        let parsed = Board::from_fen(&fen).unwrap();
+        assert_eq!(parsed, board);
+        assert_eq!(parsed.turn(), Color::Black);
+    }
+
+    #[test]
+    fn invalid_fens_are_rejected() {
+        assert_eq!(Board::from_fen("1B1"), Err(FenError::MissingField));
+
+        let fen = Board::default().to_fen();
+        assert_eq!(
+            Board::from_fen(&fen.replacen("B/", "1B/", 1)),
+            Err(FenError::WrongRankLength { rank: 0 })
+        );
+        assert_eq!(
+            Board::from_fen(&fen.replacen('Q', "X", 1)),
+            Err(FenError::UnknownPiece { piece: 'X' })
+        );
+        assert_eq!(
+            Board::from_fen(&fen.replacen('K', "Q", 1)),
+            Err(FenError::WrongNumberOfKings {
+                color: Color::White,
+                count: 0
+            })
+        );
+        assert!(matches!(
+            Board::from_fen(&fen.replace(" -", " 0,0")),
+            Err(FenError::InvalidLastMove { .. })
+        ));
+    }
+
+    #[test]
+    fn fen_round_trips_with_promotions() {
+        let mut board =
+            Board::new_minimal(Vec2::new(10, 10).unwrap(), Vec2::new(5, 5).unwrap()).unwrap();
+        let origin = Vec2::new(1, 3).unwrap();
+        board.get_mut(origin, Color::Black).replace(Piece::Pawn);
+        board
+            .get_mut(Vec2::new(0, 3).unwrap(), Color::White)
+            .replace(Piece::Rook);
+
+        let mov = board
+            .moves_from(origin, Color::Black)
+            .find(|mov| {
+                matches!(
+                    mov,
+                    Move::Promotion {
+                        promoting_to: Piece::Rook,
+                        captures: Some(_),
+                        ..
+                    }
+                )
+            })
+            .unwrap();
+        board.apply_move(mov, Color::Black).unwrap();
+
+        let fen = board.to_fen();
+        assert!(fen.ends_with(" 1,3*0,3=r"), "{fen}");
+        let parsed = Board::from_fen(&fen).unwrap();
+        assert_eq!(parsed, board);
+        assert_eq!(parsed.turn(), Color::White);
+
+        assert!(matches!(
+            Board::from_fen(&fen.replace("=r", "=R")),
+            Err(FenError::InvalidLastMove { .. })
+        ));
+    }
+
+    #[test]
+    fn en_passant_is_left_out_of_the_fen() {
+        let board = Board {
+            last_move: Some(Move::EnPassant {
+                file: 3,
+                direction: Side::King,
+            }),
+            ..Board::default()
+        };
+
+        assert!(board.to_fen().ends_with(" -"));
+    }
+
+    #[test]
+    fn minimal_board_fen() {
+        let board = Board::new_minimal(Vec2::ZERO, Vec2::new(10, 10).unwrap()).unwrap();
+        assert_eq!(Board::from_fen(&board.to_fen()).unwrap(), board);
+    }
+}
@@ -0,0 +1,267 @@
+//! Looking at the board as if a move was made, without copying it.
+
+use crate::{
+    Color, IVec2, Move, Side, Vec2,
+    piece::{Piece, bishop, king, knight, pawn, rook},
+};
+
+use super::{Board, BoardView};
+
+/// A view of a [`Board`] as if a move was applied to it, obtained with [`Board::peek`].
+///
+/// It only borrows the board, so it is much cheaper than cloning it and applying the move, which
+/// matters for things that are checked for every candidate move (such as whether the move leaves
+/// the king in check).
+///
+/// It doesn't update the last move of the board, so en passant is not taken into account.
+#[derive(Debug, Clone, Copy)]
+pub struct PeekBoard<'a> {
+    board: &'a Board,
+    origin: Vec2,
+    destination: Vec2,
+    color: Color,
+
+    /// The piece that the moving pawn promotes to, if the move is a promotion.
+    promoting_to: Option<Piece>,
+}
+
+impl Board {
+    /// Views the board as if `color` made the move `mov`, without modifying or cloning it.
+    ///
+    /// As with [`Self::apply_move_unchecked`], the move is not checked for legality.
+    ///
+    /// # Panics
+    ///
+    /// If `mov` is a [`Move::EnPassant`], which are not supported yet (same as in
+    /// [`Self::apply_move_unchecked`]).
+    pub fn peek(&self, mov: Move, color: Color) -> PeekBoard<'_> {
+        let promoting_to = match mov {
+            Move::Regular { .. } => None,
+            Move::EnPassant { .. } => todo!("Peeking {mov:?}"),
+            Move::Promotion { promoting_to, .. } => Some(promoting_to),
+        };
+
+        PeekBoard {
+            board: self,
+            origin: mov.origin_for(color),
+            destination: mov.destination_for(color),
+            color,
+            promoting_to,
+        }
+    }
+}
+
+impl<'a> PeekBoard<'a> {
+    /// The board before the move.
+    pub fn board(&self) -> &'a Board {
+        self.board
+    }
+
+    /// Enumerates all `(position, piece)` pairs of the given color, after the move.
+    ///
+    /// See [`Board::piece_positions`]. The order is not necessarily the same.
+    pub fn piece_positions(&self, color: Color) -> impl Iterator<Item = (Vec2, Piece)> + '_ {
+        self.board
+            .piece_positions(color)
+            .filter_map(move |(position, piece)| {
+                if color == self.color {
+                    if position == self.origin {
+                        Some((self.destination, self.promoting_to.unwrap_or(piece)))
+                    } else {
+                        Some((position, piece))
+                    }
+                } else {
+                    // The piece at the destination, if any, is captured.
+                    (position != self.destination).then_some((position, piece))
+                }
+            })
+    }
+
+    /// Returns the position of the king of the given color, after the move.
+    pub fn find_king(&self, color: Color) -> Vec2 {
+        if color == self.color && self.board.get(self.origin, color) == Some(Piece::King) {
+            self.destination
+        } else {
+            self.board.find_king(color)
+        }
+    }
+
+    /// Whether any piece of color `by` attacks the given position, after the move.
+    ///
+    /// See [`Board::is_attacked`].
+    pub fn is_attacked(&self, position: Vec2, by: Color) -> bool {
+        self.piece_positions(by)
+            .any(|(origin, piece)| piece.attacks(origin, position, self, by))
+    }
+
+    /// Verifies whether the king of the given color is attacked, after the move.
+    ///
+    /// If it is, returns a move that would capture the king. See [`Board::in_check`].
+    ///
+    /// Instead of checking every enemy piece, this looks outwards from the king: along each
+    /// rook and bishop stride for the first piece in the way, and at the tiles from which a
+    /// knight, king or pawn could capture it.
+    pub fn in_check(&self, color: Color) -> Option<Move> {
+        let king_position = self.find_king(color);
+        let by = color.other();
+        let is_enemy = |position: Vec2, piece: Piece| self.get(position, by) == Some(piece);
+
+        let sliders = [
+            (rook::strides(), Piece::Rook),
+            (bishop::strides(), Piece::Bishop),
+        ];
+        let slider_attacker = sliders.into_iter().find_map(|(strides, slider)| {
+            strides.into_iter().find_map(|stride| {
+                let mut position = king_position;
+                loop {
+                    position = position.checked_add(stride)?;
+                    if let Some((piece, piece_color)) = self.get_either(position) {
+                        let attacks =
+                            piece_color == by && (piece == slider || piece == Piece::Queen);
+                        return attacks.then_some(position);
+                    }
+                }
+            })
+        });
+
+        let pawn_deltas = [Side::Queen, Side::King].map(|side| {
+            let delta = pawn::capture_delta(by, side);
+            IVec2::new_unchecked(-delta.x(), -delta.y())
+        });
+
+        let leaper_attacker = || {
+            let leapers = [
+                (&knight::DELTAS[..], Piece::Knight),
+                (&king::VALID_DELTAS[..], Piece::King),
+                (&pawn_deltas[..], Piece::Pawn),
+            ];
+            leapers.into_iter().find_map(|(deltas, leaper)| {
+                deltas
+                    .iter()
+                    .filter_map(|&delta| king_position.checked_add(delta))
+                    .find(|&position| is_enemy(position, leaper))
+            })
+        };
+
+        slider_attacker
+            .or_else(leaper_attacker)
+            .map(|origin| Move::Regular {
+                origin,
+                destination: king_position,
+                captures: true,
+            })
+    }
+}
+
+impl BoardView for PeekBoard<'_> {
+    fn get_either(&self, position: Vec2) -> Option<(Piece, Color)> {
+        if position == self.origin {
+            None
+        } else if position == self.destination {
+            self.board
+                .get(self.origin, self.color)
+                .map(|piece| (self.promoting_to.unwrap_or(piece), self.color))
+        } else {
+            self.board.get_either(position)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Board, BoardView, Color, Move, Vec2, piece::Piece};
+
+    /// Asserts that peeking `mov` sees the same as applying it to a copy of the board.
+    fn assert_peek_matches(board: &Board, mov: Move, color: Color) {
+        let peek = board.peek(mov, color);
+        let mut applied = board.clone();
+        applied.apply_move_unchecked(mov, color);
+
+        for position in Vec2::iter() {
+            assert_eq!(
+                BoardView::get_either(&peek, position),
+                applied.get_either(position),
+                "{mov:?} at {position}"
+            );
+        }
+
+        for color in [Color::White, Color::Black] {
+            let mut peeked = peek.piece_positions(color).collect::<Vec<_>>();
+            peeked.sort_by_key(|&(position, _)| Board::index(position));
+            assert_eq!(
+                peeked,
+                applied.piece_positions(color).collect::<Vec<_>>(),
+                "{mov:?}"
+            );
+
+            assert_eq!(peek.find_king(color), applied.find_king(color), "{mov:?}");
+            assert_eq!(
+                peek.in_check(color).is_some(),
+                applied.in_check(color).is_some(),
+                "{mov:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn peek_matches_applying_regular_moves() {
+        let board = Board::default();
+        for color in [Color::White, Color::Black] {
+            for mov in board.possible_moves(color) {
+                assert_peek_matches(&board, mov, color);
+            }
+        }
+    }
+
+    #[test]
+    fn peek_matches_applying_captures() {
+        let white_king = Vec2::new(4, 4).unwrap();
+        let mut board = Board::new_minimal(white_king, Vec2::new(10, 10).unwrap()).unwrap();
+        let queen = Vec2::new(5, 6).unwrap();
+        board.get_mut(queen, Color::White).replace(Piece::Queen);
+        // Put black pieces in some of the tiles the queen can move to, so that it can capture them.
+        let targets = board
+            .possible_moves(Color::White)
+            .filter(|mov| mov.origin() == queen)
+            .step_by(4)
+            .map(|mov| mov.destination())
+            .collect::<Vec<_>>();
+        for (target, piece) in targets
+            .into_iter()
+            .zip([Piece::Rook, Piece::Knight, Piece::Bishop])
+        {
+            board.get_mut(target, Color::Black).replace(piece);
+        }
+
+        let captures = board
+            .possible_moves(Color::White)
+            .filter(|mov| matches!(mov, Move::Regular { captures: true, .. }))
+            .collect::<Vec<_>>();
+        assert!(!captures.is_empty());
+
+        for mov in captures {
+            assert_peek_matches(&board, mov, Color::White);
+        }
+    }
+
+    #[test]
+    fn peek_matches_applying_promotions() {
+        let mut board = Board::new_minimal(Vec2::ZERO, Vec2::new(10, 10).unwrap()).unwrap();
+        board
+            .get_mut(Vec2::new(9, 7).unwrap(), Color::White)
+            .replace(Piece::Pawn);
+        board
+            .get_mut(Vec2::new(10, 7).unwrap(), Color::Black)
+            .replace(Piece::Knight);
+
+        let promotions = board
+            .possible_moves(Color::White)
+            .filter(|mov| matches!(mov, Move::Promotion { .. }))
+            .collect::<Vec<_>>();
+        assert_eq!(promotions.len(), 8, "Forward and capturing, to each piece");
+
+        for mov in promotions {
+            assert_peek_matches(&board, mov, Color::White);
+        }
+    }
+}
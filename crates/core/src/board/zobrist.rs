@@ -0,0 +1,117 @@
+//! [Zobrist hashing](https://www.chessprogramming.org/Zobrist_Hashing) of positions.
+//!
+//! Each feature of a [`TranspositionKey`] (a piece on a tile, the color to move and the en
+//! passant file) gets a random number, and the hash of a position is the xor of the numbers of
+//! its features. The numbers are generated at compile time, so hashes are the same across runs.
+
+use crate::Color;
+
+use super::{Board, TranspositionKey};
+
+const TILES: usize = Board::NUMBER_OF_TILES as usize;
+const FILES: usize = Board::NUMBER_OF_FILES as usize;
+
+/// The random numbers of every feature of a position.
+struct Table {
+    pieces: [[[u64; TILES]; 6]; 2],
+    black_to_move: u64,
+    en_passant_file: [u64; FILES],
+}
+
+/// A step of the [SplitMix64](https://prng.di.unimi.it/splitmix64.c) generator.
+const fn splitmix64(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+static TABLE: Table = {
+    let mut state = 0x6368_6573_7361_676F; // "chessago"
+    let mut table = Table {
+        pieces: [[[0; TILES]; 6]; 2],
+        black_to_move: 0,
+        en_passant_file: [0; FILES],
+    };
+
+    let mut color = 0;
+    while color < 2 {
+        let mut piece = 0;
+        while piece < 6 {
+            let mut tile = 0;
+            while tile < TILES {
+                table.pieces[color][piece][tile] = splitmix64(&mut state);
+                tile += 1;
+            }
+            piece += 1;
+        }
+        color += 1;
+    }
+
+    table.black_to_move = splitmix64(&mut state);
+
+    let mut file = 0;
+    while file < FILES {
+        table.en_passant_file[file] = splitmix64(&mut state);
+        file += 1;
+    }
+
+    table
+};
+
+impl TranspositionKey {
+    /// The Zobrist hash of the position.
+    ///
+    /// Equal keys have equal hashes. Different keys have different hashes with overwhelming
+    /// probability, but not certainly.
+    pub fn zobrist_hash(&self) -> u64 {
+        let mut hash = 0;
+        for color in [Color::White, Color::Black] {
+            for (index, piece) in self.pieces[color].iter().enumerate() {
+                if let Some(piece) = piece {
+                    hash ^= TABLE.pieces[color][*piece][index];
+                }
+            }
+        }
+
+        if self.turn == Color::Black {
+            hash ^= TABLE.black_to_move;
+        }
+
+        if let Some(file) = self.en_passant_file {
+            hash ^= TABLE.en_passant_file[file as usize];
+        }
+
+        hash
+    }
+}
+
+impl Board {
+    /// The [Zobrist hash](TranspositionKey::zobrist_hash) of the board.
+    pub fn zobrist_hash(&self) -> u64 {
+        self.transposition_key().zobrist_hash()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Board, Color, Vec2, piece::Piece};
+
+    #[test]
+    fn hash_depends_on_pieces_and_turn() {
+        let board = Board::default();
+        let mut moved = board.clone();
+        let mov = moved.possible_moves(Color::White).next().unwrap();
+        moved.apply_move(mov, Color::White).unwrap();
+        assert_ne!(board.zobrist_hash(), moved.zobrist_hash());
+
+        let mut extra_piece = board.clone();
+        extra_piece
+            .get_mut(Vec2::CENTER, Color::White)
+            .replace(Piece::Knight);
+        assert_ne!(board.zobrist_hash(), extra_piece.zobrist_hash());
+
+        assert_eq!(board.zobrist_hash(), Board::default().zobrist_hash());
+    }
+}
@@ -17,24 +17,55 @@
 //! This sounds nice because it's easier to think about, but the problem then is that the two
 //! neighbors are at `1/√2` distances, which are very annoying. TODO: Is this necessarly true?
 
-use std::{
+use core::{
     cmp::{max, min},
     fmt, ops,
 };
 
-use crate::piece::movement;
+use crate::{
+    Color,
+    piece::{bishop, movement, rook},
+};
 
 mod tests;
 
 /// A vector in hexagonal coordinates, inside of the hexagonal chessboard.
+///
+/// When deserializing, the coordinates are checked to be [valid](Vec2::is_valid).
 #[repr(C)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "UncheckedVec2"))]
 pub struct Vec2 {
     x: u8,
     y: u8,
 }
 
+/// The fields of a [`Vec2`] before checking them, to deserialize it through [`Vec2::new`].
+#[cfg(feature = "serde")]
+#[derive(serde::Deserialize)]
+struct UncheckedVec2 {
+    x: u8,
+    y: u8,
+}
+
+/// The error of deserializing a [`Vec2`] outside of the board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("({x}, {y}) is not a tile of the board")]
+pub struct InvalidVec2 {
+    pub x: u8,
+    pub y: u8,
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<UncheckedVec2> for Vec2 {
+    type Error = InvalidVec2;
+
+    fn try_from(UncheckedVec2 { x, y }: UncheckedVec2) -> Result<Self, Self::Error> {
+        Vec2::new(x, y).ok_or(InvalidVec2 { x, y })
+    }
+}
+
 /// Construct a [`Vec2`] that is checked to be valid at compile-time
 #[macro_export]
 macro_rules! vec2 {
@@ -69,19 +100,22 @@ macro_rules! ivec2 {
 ///////////////////////////////////////////////////////////////////////////////
 
 impl Vec2 {
-    /// The maximum value that a coordinate can have.
-    pub const MAX: u8 = 10;
-
     /// The maximum allowed absolute difference of two coordinates.
+    ///
+    /// Every other size of the board is derived from this one.
     pub const WIDTH: u8 = 5;
 
+    /// The maximum value that a coordinate can have.
+    pub const MAX: u8 = 2 * Self::WIDTH;
+
     /// The maximum value for the rank of a tile.
-    pub const MAX_RANK: u8 = 20;
+    pub const MAX_RANK: u8 = 2 * Self::MAX;
 
     /// The maximum value for the file of a tile.
-    pub const MAX_FILE: u8 = 10;
+    pub const MAX_FILE: u8 = 2 * Self::WIDTH;
 
-    pub const CENTER: Self = vec2!(5, 5);
+    /// The tile in the middle of the board.
+    pub const CENTER: Self = Vec2::new_unchecked(Self::WIDTH, Self::WIDTH);
 
     /// Whether these coordinates are possible in a chesssagon board.
     ///
@@ -104,6 +138,10 @@ impl Vec2 {
     }
 
     /// Iterator over all valid hexagonal coordinates
+    ///
+    /// The coordinates are ordered by `x` and then by `y`, which is **not** the same order as
+    /// [`Board::index`](crate::Board::index). If you need to iterate in parallel to an array
+    /// indexed by [`Board::index`](crate::Board::index), use [`Self::iter_by_index`].
     pub fn iter() -> impl Iterator<Item = Self> {
         (0_u8..=Self::MAX).flat_map(|x| {
             // There are two conditions for `y`:
@@ -116,6 +154,18 @@ impl Vec2 {
         })
     }
 
+    /// Iterator over all valid hexagonal coordinates, in the order of [`Board::index`](crate::Board::index).
+    ///
+    /// That is, the `i`th element is the position `p` such that `Board::index(p) == i`. Tiles are
+    /// ordered by [rank](Self::rank) and then by `y` (i.e., bottom to top, left to right).
+    pub fn iter_by_index() -> impl Iterator<Item = Self> {
+        (0..=Self::MAX_RANK).flat_map(|rank| {
+            let first_y = Self::min_valid_rank_coordinate(rank);
+            (first_y..first_y + Self::rank_width(rank))
+                .map(move |y| Self::new_unchecked(rank - y, y))
+        })
+    }
+
     /// The "color" of the tile. Always one of 0, 1 or 2.
     ///
     /// Two touching tiles will never have the same index.
@@ -149,11 +199,11 @@ impl Vec2 {
     #[doc = include_str!("./diagrams/files.txt")]
     /// ```
     ///
-    /// Computed as the difference of the coordinates, plus 5.
+    /// Computed as the difference of the coordinates, plus [`Self::WIDTH`].
     #[inline]
     pub const fn file(&self) -> u8 {
-        // The 5 has to be at the start to avoid an underflow error.
-        5 + self.y - self.x
+        // The width has to be at the start to avoid an underflow error.
+        Self::WIDTH + self.y - self.x
     }
 
     /// The corresponding vector from the other side of the board.
@@ -162,6 +212,17 @@ impl Vec2 {
         Vec2::new_unchecked(Self::MAX - self.x, Self::MAX - self.y)
     }
 
+    /// The vector as seen by the player of the given color, with their side of the board at the
+    /// bottom. That is, the vector itself for white and the [flipped](Self::flipped) one for
+    /// black.
+    #[inline]
+    pub const fn oriented(self, perspective: Color) -> Self {
+        match perspective {
+            Color::White => self,
+            Color::Black => self.flipped(),
+        }
+    }
+
     /// Number of valid coordinates with a given rank
     ///
     /// Visually:
@@ -217,6 +278,95 @@ impl Vec2 {
     pub fn distance(self, other: Vec2) -> u8 {
         (other - self).length()
     }
+
+    /// Adds `delta` to `self`, returning `None` if the result is outside of the board.
+    ///
+    /// Prefer this over `+` (which wraps around, see [`ops::Add`]) whenever stepping in a
+    /// direction could leave the board.
+    pub const fn checked_add(self, delta: IVec2) -> Option<Self> {
+        match (
+            self.x.checked_add_signed(delta.x),
+            self.y.checked_add_signed(delta.y),
+        ) {
+            (Some(x), Some(y)) => Self::new(x, y),
+            _ => None,
+        }
+    }
+
+    /// The tiles that share an edge with `self` (i.e., at a [distance](Self::distance) of 1).
+    ///
+    /// There are six of them, except for tiles at the edge of the board.
+    pub fn neighbors(self) -> impl Iterator<Item = Vec2> {
+        rook::strides()
+            .into_iter()
+            .filter_map(move |stride| self.checked_add(stride))
+    }
+
+    /// The tiles strictly between `self` and `other`, from `self` towards `other`.
+    ///
+    /// Returns `None` if the tiles are not on the same line (i.e., they are not separated by
+    /// a rook-like or bishop-like stride) or if they are the same tile. Neighbouring tiles
+    /// return an empty iterator.
+    pub fn between(self, other: Vec2) -> Option<impl Iterator<Item = Vec2>> {
+        if self == other {
+            return None;
+        }
+
+        let (stride, distance) = movement::get_stride(other - self);
+        if !rook::valid_stride(stride) && !bishop::valid_stride(stride) {
+            return None;
+        }
+
+        // The board is convex, so tiles between two tiles of the board are in it too.
+        Some((1..distance).map(move |i| self + stride * i as i8))
+    }
+
+    /// The [axial coordinates](https://www.redblobgames.com/grids/hexagons/#coordinates-axial)
+    /// `(q, r)` of the tile, for interoperability with other hexagonal grid libraries.
+    ///
+    /// They follow the flat-top convention of Red Blob Games' guide, with the screen's `y` axis
+    /// pointing down and white at the bottom (as the board is drawn in the UI):
+    /// - [`Self::CENTER`] is the origin.
+    /// - `q` grows towards the bottom right, and is [`Self::file`] minus [`Self::WIDTH`].
+    /// - `r` grows straight down, towards white's side of the board.
+    ///
+    /// That is, `q = y - x` and `r = WIDTH - y`. See also [`Self::to_cube`].
+    pub const fn to_axial(self) -> (i32, i32) {
+        let (x, y) = (self.x as i32, self.y as i32);
+        (y - x, Self::WIDTH as i32 - y)
+    }
+
+    /// The [cube coordinates](https://www.redblobgames.com/grids/hexagons/#coordinates-cube)
+    /// `(q, r, s)` of the tile, where `q + r + s == 0`.
+    ///
+    /// `q` and `r` are the same as in [`Self::to_axial`], and `s = x - WIDTH`. The distance
+    /// between two tiles is the largest absolute difference of their cube coordinates, same as
+    /// [`Self::distance`].
+    pub const fn to_cube(self) -> (i32, i32, i32) {
+        let (q, r) = self.to_axial();
+        (q, r, -q - r)
+    }
+
+    /// The tile with the given [axial coordinates](Self::to_axial).
+    ///
+    /// Returns `None` if it's outside of the board.
+    pub fn from_axial(q: i32, r: i32) -> Option<Self> {
+        let y = (Self::WIDTH as i32).checked_sub(r)?;
+        let x = y.checked_sub(q)?;
+        Self::new(u8::try_from(x).ok()?, u8::try_from(y).ok()?)
+    }
+
+    /// The tile with the given [cube coordinates](Self::to_cube).
+    ///
+    /// Returns `None` if `q + r + s != 0` or if it's outside of the board.
+    pub fn from_cube(q: i32, r: i32, s: i32) -> Option<Self> {
+        // Summed in `i64`, so that extreme coordinates don't overflow.
+        if i64::from(q) + i64::from(r) + i64::from(s) != 0 {
+            return None;
+        }
+
+        Self::from_axial(q, r)
+    }
 }
 
 impl IVec2 {
@@ -390,6 +540,10 @@ impl ops::Sub<Vec2> for Vec2 {
     }
 }
 
+/// Adds the coordinates, wrapping around on overflow.
+///
+/// The result is **not** checked to be on the board, so this should only be used where it is
+/// known to be (e.g., between two tiles of the board). Otherwise, use [`Vec2::checked_add`].
 impl ops::Add<IVec2> for Vec2 {
     type Output = Vec2;
     fn add(self, rhs: IVec2) -> Self::Output {
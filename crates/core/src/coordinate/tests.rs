@@ -3,7 +3,7 @@
 use pretty_assertions::assert_eq;
 use std::collections::HashSet;
 
-use crate::{diagrams, ivec2};
+use crate::{diagrams, ivec2, vec2};
 
 use super::*;
 
@@ -143,3 +143,140 @@ fn fn_length_returns_correct_distances_for_a_few_values() {
     assert_eq!(ivec2!(-1, 5).length(), 6);
     assert_eq!(ivec2!(2, 4).length(), 4);
 }
+
+#[test]
+fn between_on_rook_line() {
+    let between = vec2!(0, 0)
+        .between(vec2!(3, 3))
+        .unwrap()
+        .collect::<Vec<_>>();
+    assert_eq!(between, [vec2!(1, 1), vec2!(2, 2)]);
+
+    let between = vec2!(5, 9)
+        .between(vec2!(5, 6))
+        .unwrap()
+        .collect::<Vec<_>>();
+    assert_eq!(between, [vec2!(5, 8), vec2!(5, 7)]);
+}
+
+#[test]
+fn between_on_bishop_line() {
+    let between = vec2!(0, 0)
+        .between(vec2!(6, 3))
+        .unwrap()
+        .collect::<Vec<_>>();
+    assert_eq!(between, [vec2!(2, 1), vec2!(4, 2)]);
+
+    let between = vec2!(4, 4)
+        .between(vec2!(5, 3))
+        .unwrap()
+        .collect::<Vec<_>>();
+    assert_eq!(between, []);
+}
+
+#[test]
+fn between_non_colinear_is_none() {
+    // Knight jump
+    assert!(vec2!(0, 0).between(vec2!(3, 1)).is_none());
+    assert!(vec2!(2, 3).between(vec2!(7, 5)).is_none());
+    assert!(vec2!(4, 4).between(vec2!(4, 4)).is_none());
+}
+
+#[test]
+fn neighbors_are_at_distance_one() {
+    for position in Vec2::iter() {
+        let neighbors = position.neighbors().collect::<HashSet<_>>();
+        let expected = Vec2::iter()
+            .filter(|&other| position.distance(other) == 1)
+            .collect::<HashSet<_>>();
+        assert_eq!(neighbors, expected, "{position}");
+    }
+
+    assert_eq!(Vec2::CENTER.neighbors().count(), 6);
+}
+
+#[test]
+fn axial_and_cube_coordinates_round_trip() {
+    for position in Vec2::iter() {
+        let (q, r) = position.to_axial();
+        assert_eq!(Vec2::from_axial(q, r), Some(position));
+
+        let (q, r, s) = position.to_cube();
+        assert_eq!(q + r + s, 0);
+        assert_eq!(Vec2::from_cube(q, r, s), Some(position));
+    }
+
+    assert_eq!(Vec2::CENTER.to_cube(), (0, 0, 0));
+    assert_eq!(Vec2::from_axial(6, 0), None);
+    assert_eq!(Vec2::from_axial(-3, -3), None);
+    assert_eq!(Vec2::from_cube(1, 1, 1), None);
+
+    // Extreme coordinates are off the board rather than overflowing.
+    assert_eq!(Vec2::from_axial(0, i32::MIN), None);
+    assert_eq!(Vec2::from_axial(i32::MIN, 0), None);
+    assert_eq!(Vec2::from_axial(i32::MAX, i32::MAX), None);
+    assert_eq!(Vec2::from_cube(i32::MAX, i32::MAX, i32::MAX), None);
+    assert_eq!(Vec2::from_cube(i32::MIN, i32::MAX, 1), None);
+}
+
+#[test]
+fn cube_distance_matches_distance() {
+    for a in Vec2::iter() {
+        for b in Vec2::iter() {
+            let (a_cube, b_cube) = (a.to_cube(), b.to_cube());
+            let cube_distance = (a_cube.0 - b_cube.0)
+                .abs()
+                .max((a_cube.1 - b_cube.1).abs())
+                .max((a_cube.2 - b_cube.2).abs());
+            assert_eq!(cube_distance, a.distance(b) as i32, "{a} to {b}");
+        }
+    }
+}
+
+#[test]
+fn axial_directions_follow_the_flat_top_convention() {
+    // Straight down is towards white's side.
+    assert_eq!(vec2!(4, 4).to_axial(), (0, 1));
+    // Towards the bottom right.
+    assert_eq!(vec2!(4, 5).to_axial(), (1, 0));
+}
+
+#[test]
+fn stepping_off_each_edge_is_none() {
+    for position in Vec2::iter() {
+        for stride in IVec2::strides() {
+            let expected = Vec2::iter().find(|&other| other - position == stride);
+            assert_eq!(
+                position.checked_add(stride),
+                expected,
+                "{position} + {stride}"
+            );
+        }
+    }
+
+    // One tile on each of the six edges.
+    for (position, stride) in [
+        (vec2!(0, 3), ivec2!(-1, 0)),
+        (vec2!(3, 0), ivec2!(0, -1)),
+        (vec2!(10, 7), ivec2!(1, 0)),
+        (vec2!(7, 10), ivec2!(0, 1)),
+        (vec2!(8, 3), ivec2!(1, 0)),
+        (vec2!(3, 8), ivec2!(0, 1)),
+    ] {
+        assert_eq!(position.checked_add(stride), None, "{position} + {stride}");
+    }
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn tiles_off_the_board_are_not_deserialized() {
+    let tile: Vec2 = serde_json::from_str(r#"{ "x": 10, "y": 5 }"#).unwrap();
+    assert_eq!(tile, vec2!(10, 5));
+
+    let error = serde_json::from_str::<Vec2>(r#"{ "x": 20, "y": 20 }"#).unwrap_err();
+    assert!(
+        error.to_string().contains("(20, 20) is not a tile"),
+        "{error}"
+    );
+    assert!(serde_json::from_str::<Vec2>(r#"{ "x": 10, "y": 4 }"#).is_err());
+}
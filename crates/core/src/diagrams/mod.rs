@@ -32,8 +32,21 @@ pub const MIN_VALID_RANK_COORDINATES: &str = include_str!("./min_valid_rank_coor
 pub const INDICES: &str = include_str!("./indices.txt");
 #[allow(missing_docs)]
 pub const INITIAL_BOARD: &str = include_str!("./initial_board.txt");
+#[allow(missing_docs)]
+pub const INITIAL_BOARD_ASCII: &str = include_str!("./initial_board_ascii.txt");
 
 #[allow(missing_docs)]
 pub const MOVEMENT_KNIGHT: &str = include_str!("./movement_knight.txt");
 #[allow(missing_docs)]
 pub const MOVEMENT_BISHOP: &str = include_str!("./movement_bishop.txt");
+#[allow(missing_docs)]
+pub const MOVEMENT_PAWN: &str = include_str!("./movement_pawn.txt");
+#[allow(missing_docs)]
+pub const MOVEMENT_ROOK: &str = include_str!("./movement_rook.txt");
+#[allow(missing_docs)]
+pub const MOVEMENT_QUEEN: &str = include_str!("./movement_queen.txt");
+#[allow(missing_docs)]
+pub const MOVEMENT_KING: &str = include_str!("./movement_king.txt");
+
+#[allow(missing_docs)]
+pub const DEBUG_MOVES_PINNED_ROOK: &str = include_str!("./debug_moves_pinned_rook.txt");
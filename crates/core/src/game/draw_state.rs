@@ -0,0 +1,163 @@
+//! Draw offers, as a small state machine. See [`DrawState`].
+
+use crate::Color;
+
+/// Whether a draw has been offered in a [`Game`](super::Game), and by whom.
+///
+/// The transitions are:
+///
+/// | State          | Action             | Result                        |
+/// |----------------|--------------------|-------------------------------|
+/// | `None`         | `c` offers         | `OfferedBy(c)`                |
+/// | `None`         | anyone retracts    | [`DrawError::NotOffered`]     |
+/// | `None`         | anyone accepts     | [`DrawError::NotOffered`]     |
+/// | `OfferedBy(c)` | `c` offers         | [`DrawError::AlreadyOffered`] |
+/// | `OfferedBy(c)` | the other offers   | `OfferedBy(other)`            |
+/// | `OfferedBy(c)` | `c` retracts       | `None`                        |
+/// | `OfferedBy(c)` | the other retracts | [`DrawError::NotYourOffer`]   |
+/// | `OfferedBy(c)` | `c` accepts        | [`DrawError::OwnOffer`]       |
+/// | `OfferedBy(c)` | the other accepts  | The game is drawn             |
+///
+/// Failed transitions leave the state as it was.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "Option<Color>", into = "Option<Color>")
+)]
+pub enum DrawState {
+    /// There is no draw offer on the table.
+    #[default]
+    None,
+
+    /// The player of the given color offered a draw, which their opponent can accept.
+    OfferedBy(Color),
+}
+
+impl DrawState {
+    /// The color of the player that offered a draw, if any.
+    pub fn offered_by(self) -> Option<Color> {
+        match self {
+            DrawState::None => None,
+            DrawState::OfferedBy(color) => Some(color),
+        }
+    }
+
+    /// The state after `color` offers a draw.
+    ///
+    /// Offering a draw when the opponent had already offered one replaces their offer.
+    pub fn offer(self, color: Color) -> Result<Self, DrawError> {
+        match self {
+            DrawState::OfferedBy(offered_by) if offered_by == color => {
+                Err(DrawError::AlreadyOffered)
+            }
+            DrawState::None | DrawState::OfferedBy(_) => Ok(DrawState::OfferedBy(color)),
+        }
+    }
+
+    /// The state after `color` retracts their draw offer.
+    pub fn retract(self, color: Color) -> Result<Self, DrawError> {
+        match self {
+            DrawState::None => Err(DrawError::NotOffered),
+            DrawState::OfferedBy(offered_by) if offered_by != color => Err(DrawError::NotYourOffer),
+            DrawState::OfferedBy(_) => Ok(DrawState::None),
+        }
+    }
+
+    /// Checks that `color` can accept the draw offer, returning who offered it.
+    pub fn accept(self, color: Color) -> Result<Color, DrawError> {
+        match self {
+            DrawState::None => Err(DrawError::NotOffered),
+            DrawState::OfferedBy(offered_by) if offered_by == color => Err(DrawError::OwnOffer),
+            DrawState::OfferedBy(offered_by) => Ok(offered_by),
+        }
+    }
+}
+
+impl From<Option<Color>> for DrawState {
+    fn from(offered_by: Option<Color>) -> Self {
+        offered_by.map_or(DrawState::None, DrawState::OfferedBy)
+    }
+}
+
+impl From<DrawState> for Option<Color> {
+    fn from(state: DrawState) -> Self {
+        state.offered_by()
+    }
+}
+
+#[allow(missing_docs)]
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum DrawError {
+    #[error("You have already offered a draw.")]
+    AlreadyOffered,
+
+    #[error("No draw has been offered.")]
+    NotOffered,
+
+    #[error("The draw was offered by your opponent, so only they can retract it.")]
+    NotYourOffer,
+
+    #[error("You can't accept your own draw offer.")]
+    OwnOffer,
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Color;
+
+    use super::{DrawError, DrawState};
+
+    const WHITE: Color = Color::White;
+    const BLACK: Color = Color::Black;
+
+    #[test]
+    fn offers() {
+        assert_eq!(
+            DrawState::None.offer(WHITE),
+            Ok(DrawState::OfferedBy(WHITE))
+        );
+        assert_eq!(
+            DrawState::OfferedBy(WHITE).offer(WHITE),
+            Err(DrawError::AlreadyOffered)
+        );
+        assert_eq!(
+            DrawState::OfferedBy(WHITE).offer(BLACK),
+            Ok(DrawState::OfferedBy(BLACK))
+        );
+    }
+
+    #[test]
+    fn retractions() {
+        assert_eq!(DrawState::None.retract(WHITE), Err(DrawError::NotOffered));
+        assert_eq!(
+            DrawState::OfferedBy(WHITE).retract(WHITE),
+            Ok(DrawState::None)
+        );
+        assert_eq!(
+            DrawState::OfferedBy(WHITE).retract(BLACK),
+            Err(DrawError::NotYourOffer)
+        );
+    }
+
+    #[test]
+    fn acceptances() {
+        assert_eq!(DrawState::None.accept(BLACK), Err(DrawError::NotOffered));
+        assert_eq!(
+            DrawState::OfferedBy(WHITE).accept(WHITE),
+            Err(DrawError::OwnOffer)
+        );
+        assert_eq!(DrawState::OfferedBy(WHITE).accept(BLACK), Ok(WHITE));
+    }
+
+    #[test]
+    fn converts_to_and_from_the_offering_color() {
+        for state in [
+            DrawState::None,
+            DrawState::OfferedBy(WHITE),
+            DrawState::OfferedBy(BLACK),
+        ] {
+            assert_eq!(DrawState::from(state.offered_by()), state);
+        }
+    }
+}
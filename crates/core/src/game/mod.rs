@@ -2,13 +2,23 @@
 //!
 //! See [`Game`].
 
+mod draw_state;
+mod opening;
 mod tests;
 mod time_control;
-
-use crate::{Color, board::Board, mov::Move, piece::MoveError};
+pub mod transcript;
+
+use crate::{
+    Color,
+    board::{Board, BoardStatus, InvalidBoardError},
+    mov::Move,
+    piece::{MoveError, Piece},
+};
+pub use draw_state::{DrawError, DrawState};
 use jiff::Timestamp;
 use std::{fmt, time::Duration};
 pub use time_control::TimeControl;
+pub use transcript::TranscriptError;
 
 /// A game of chessagon.
 ///
@@ -18,13 +28,25 @@ pub use time_control::TimeControl;
 /// - Time controls ([`TimeControl`])
 /// - Whether the game has finished ([`GameResult`])
 ///
-// TODO: Document when exactly the timing is timed with (i.e., the authority)
-#[derive(Debug, Clone)]
+/// # Timing
+///
+/// The clock is sampled by the game itself: each move is timestamped with the current time when
+/// it is [applied](Self::apply_action), so whoever holds the `Game` is the authority on time. A
+/// move takes from the timestamp of the opponent's previous move to its own timestamp, except
+/// for the first move of each player, which takes no time.
+///
+/// The [increment](TimeControl::increment) is granted once each move is played (including the
+/// first one), never for the move that is still being thought about.
+#[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Game {
     /// The current state of the board
     board: Board,
 
+    /// The state of the board before any move was played.
+    #[cfg_attr(feature = "serde", serde(default))]
+    initial_board: Board,
+
     /// The time control for this game. To see when moves where played, use [`Self::moves`]
     time_control: TimeControl,
 
@@ -35,11 +57,35 @@ pub struct Game {
     result: Option<GameResult>,
 
     /// Whether a draw has been offered, and by who.
-    draw_offer: Option<Color>,
+    #[cfg_attr(feature = "serde", serde(rename = "draw_offer"))]
+    draw_state: DrawState,
+
+    /// The number of moves (of either color) since the last capture or pawn move.
+    #[cfg_attr(feature = "serde", serde(default))]
+    halfmove_clock: u8,
+
+    /// Every action that has been applied, in order, with the color of who took it.
+    ///
+    /// Used for [transcripts](transcript).
+    #[cfg_attr(feature = "serde", serde(default))]
+    actions: Vec<(Color, Action)>,
+
+    /// The [Zobrist hashes](Board::zobrist_hash) of every position reached, including the
+    /// initial one.
+    ///
+    /// Used to detect repetitions.
+    #[cfg_attr(feature = "serde", serde(default))]
+    position_hashes: Vec<u64>,
+
+    /// Whether [claimable draws](Self::can_claim_draw) end the game as soon as they happen,
+    /// instead of waiting for a player to [claim them](Action::ClaimDraw).
+    #[cfg_attr(feature = "serde", serde(default))]
+    automatic_draws: bool,
 }
 
 /// A possible action a player can take in a game.
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Action {
     /// Make a move.
     Move(Move),
@@ -58,6 +104,13 @@ pub enum Action {
 
     /// Accept a draw offered by opponent with [`Self::OfferDraw`].
     AcceptDraw,
+
+    /// End the game in a draw because of the fifty-move rule or a threefold repetition, which is
+    /// only possible when [`Game::can_claim_draw`].
+    ClaimDraw,
+
+    /// Call off the game without a winner, which is only possible before any move is made.
+    Abort,
 }
 
 impl Game {
@@ -66,24 +119,43 @@ impl Game {
     /// See also [`Self::from_position`].
     pub fn new(time_control: TimeControl) -> Self {
         Self::from_position(Board::default(), time_control)
+            .expect("The default board should be valid")
     }
 
     /// Creates a new game from a specific [`Board`] state with [`TimeControl`].
     ///
+    /// The board is [validated](Board::validate) first. If white (who always moves first) is
+    /// already checkmated or stalemated, the game starts finished.
+    ///
     /// See also [`Self::new`]
-    pub fn from_position(board: Board, time_control: TimeControl) -> Self {
-        Self {
+    pub fn from_position(
+        board: Board,
+        time_control: TimeControl,
+    ) -> Result<Self, InvalidBoardError> {
+        board.validate(Color::White)?;
+
+        let mut game = Self {
+            position_hashes: vec![board.zobrist_hash()],
+            initial_board: board.clone(),
             board,
             time_control,
             moves: Vec::new(),
             result: None,
-            draw_offer: None,
-        }
+            draw_state: DrawState::None,
+            halfmove_clock: 0,
+            actions: Vec::new(),
+            automatic_draws: false,
+        };
+
+        let color = game.turn();
+        game.result = GameResult::from_status(game.board.status(color), color);
+
+        Ok(game)
     }
 
     /// The color of the player that has to make a move
     pub fn turn(&self) -> Color {
-        if self.moves.len() % 2 == 0 {
+        if self.moves.len().is_multiple_of(2) {
             Color::White
         } else {
             Color::Black
@@ -95,16 +167,90 @@ impl Game {
         &self.board
     }
 
+    /// The board the game started from, before any move was played.
+    pub fn initial_board(&self) -> &Board {
+        &self.initial_board
+    }
+
+    /// The move history of both players, in the order the moves were played, with the instant
+    /// each was played on.
+    pub fn moves(&self) -> &[(Move, Timestamp)] {
+        &self.moves
+    }
+
     /// The [`GameResult`] of the game, if it has concluded.
     pub fn result(&self) -> Option<GameResult> {
         self.result
     }
 
+    /// The number of moves, of either color, that have been played since the last capture
+    /// or pawn move.
+    ///
+    /// This is the counter used for the [fifty-move rule](DrawReason::FiftyMoves), which
+    /// applies when it reaches 100 (fifty moves of each player).
+    pub fn halfmove_clock(&self) -> u8 {
+        self.halfmove_clock
+    }
+
+    /// Whether playing `mov` would reach a position for the third time in this game, i.e., a
+    /// threefold repetition.
+    ///
+    /// Returns `false` if `mov` is not legal for the player whose turn it is.
+    pub fn would_be_repetition(&self, mov: Move) -> bool {
+        let mut board = self.board.clone();
+        if board.apply_move(mov, self.turn()).is_err() {
+            return false;
+        }
+
+        let hash = board.zobrist_hash();
+        self.position_hashes.iter().filter(|&&h| h == hash).count() >= 2
+    }
+
+    /// The draw that the player whose turn it is can [claim](Action::ClaimDraw), if any.
+    ///
+    /// Draws can be claimed when:
+    /// - The [halfmove clock](Self::halfmove_clock) reaches 100 ([`DrawReason::FiftyMoves`]).
+    /// - The current position has been reached three times ([`DrawReason::Repetition`]).
+    ///
+    /// If both hold, the fifty-move rule is returned.
+    pub fn can_claim_draw(&self) -> Option<DrawReason> {
+        if self.is_finished() {
+            return None;
+        }
+
+        if self.halfmove_clock >= 100 {
+            return Some(DrawReason::FiftyMoves);
+        }
+
+        let hash = self.board.zobrist_hash();
+        let repetitions = self.position_hashes.iter().filter(|&&h| h == hash).count();
+        (repetitions >= 3).then_some(DrawReason::Repetition)
+    }
+
+    /// Makes [claimable draws](Self::can_claim_draw) end the game as soon as they happen, as if
+    /// the player to move had claimed them. By default, they have to be claimed.
+    pub fn with_automatic_draws(mut self, automatic_draws: bool) -> Self {
+        self.automatic_draws = automatic_draws;
+        self
+    }
+
+    /// Whether [claimable draws](Self::can_claim_draw) end the game on their own. See
+    /// [`Self::with_automatic_draws`].
+    pub fn automatic_draws(&self) -> bool {
+        self.automatic_draws
+    }
+
     /// The move history from a player's side.
     pub fn moves_from(&self, color: Color) -> impl Iterator<Item = &(Move, Timestamp)> {
         self.moves.iter().skip(color as usize).step_by(2)
     }
 
+    /// How long ago the player of the given color made their last move, if they have moved.
+    pub fn time_since_last_move(&self, color: Color) -> Option<Duration> {
+        let (_, timestamp) = self.moves_from(color).last()?;
+        Some(Timestamp::now().duration_since(*timestamp).unsigned_abs())
+    }
+
     /// How long it took or is taking to play the `i`th move of the game.
     ///
     /// Note that this is the `i`th move in general, for both colors. In other words,
@@ -135,25 +281,72 @@ impl Game {
         Some(end.duration_since(*start).unsigned_abs())
     }
 
+    /// The total time the player of the given color has spent on their moves.
+    ///
+    /// If the game is in progress and it's their turn, this includes the time spent so far on the
+    /// current move. The first move of each player is free, so it doesn't count.
+    pub fn total_time(&self, color: Color) -> Duration {
+        (color as usize..)
+            .step_by(2)
+            .map_while(|i| self.move_duration(i))
+            .sum()
+    }
+
+    /// The average time the player of the given color has spent on each of their moves.
+    ///
+    /// Only moves that have been played count, except for the first one (which is free). Returns
+    /// [`None`] if there are no such moves.
+    pub fn average_move_time(&self, color: Color) -> Option<Duration> {
+        let durations = (color as usize + 2..self.moves.len())
+            .step_by(2)
+            .filter_map(|i| self.move_duration(i))
+            .collect::<Vec<_>>();
+
+        if durations.is_empty() {
+            return None;
+        }
+
+        Some(durations.iter().sum::<Duration>() / durations.len() as u32)
+    }
+
     /// The amount of time the player of the given color has to make a move when it's their turn.
     ///
+    /// This is the base time, minus the time spent on each move, plus the increment of each move
+    /// that has been played (see [timing](Self#timing)). If it's their turn, the time spent so
+    /// far on the current move is subtracted too.
+    ///
     /// Returns [`Duration::ZERO`] if the player has ran out of time.
     // TODO: This keeps the timer running after resignations.
     pub fn time_remaining(&self, color: Color) -> Duration {
-        // + 2 since first two moves always take 0 time.
-        let mut i = color as usize + 2;
+        self.time_remaining_at(color, self.moves.len())
+    }
+
+    /// The time the player of the given color had left after the first `ply` moves (of either
+    /// color) were played, e.g., to show the clocks when going through the history of the game.
+    ///
+    /// With `ply` equal to the number of moves played (or more), this is the same as
+    /// [`Self::time_remaining`], including the time spent so far on the current move. Otherwise,
+    /// only the moves played before that point count.
+    pub fn time_remaining_at(&self, color: Color, ply: usize) -> Duration {
+        let ply = ply.min(self.moves.len());
         let mut time_remaining = self.time_control.base_time[color];
-        while let Some(move_duration) = self.move_duration(i) {
-            // This is in two separate lines because otherwise you get an underflow error if `move_duration` is greater than increment.
-            if time_remaining < move_duration {
-                return Duration::ZERO;
+        for i in (color as usize..).step_by(2) {
+            if i >= ply && ply < self.moves.len() {
+                break;
             }
-            time_remaining -= move_duration;
-            // Only add increment if the move has been played.
+
+            let Some(move_duration) = self.move_duration(i) else {
+                break;
+            };
+
+            let Some(after_move) = time_remaining.checked_sub(move_duration) else {
+                return Duration::ZERO;
+            };
+
+            time_remaining = after_move;
             if i < self.moves.len() {
-                time_remaining += self.time_control.increment[color];
+                time_remaining = time_remaining.saturating_add(self.time_control.increment[color]);
             }
-            i += 2;
         }
 
         time_remaining
@@ -166,10 +359,80 @@ impl Game {
     pub fn winner(&self) -> Option<Option<Color>> {
         self.result.map(|result| match result {
             GameResult::Win { winner, .. } => Some(winner),
-            GameResult::Draw { .. } => None,
+            GameResult::Draw { .. } | GameResult::Aborted { .. } => None,
         })
     }
 
+    /// How the game ended, if it has: who won (if anyone) and why.
+    ///
+    /// Unlike [`Self::result`], this also notices when a player has ran out of time, which is
+    /// not recorded in the result until [`Self::check_timeout`] is called, and reports it as
+    /// described there.
+    pub fn outcome(&self) -> Option<Outcome> {
+        self.result
+            .or_else(|| self.timeout_result())
+            .map(Into::into)
+    }
+
+    /// Ends the game if a player has ran out of time, returning the result if it did.
+    ///
+    /// The player whose flag fell first loses by [`WinReason::Timeout`], unless their opponent
+    /// doesn't [have mating material](Board::has_mating_material), in which case it is a
+    /// [`DrawReason::TimeoutVsInsufficientMaterial`] (as in FIDE's rules).
+    ///
+    /// Only the clock of the player to move runs, so flags fall one at a time. Moves are not
+    /// rejected when the player's flag has fallen, so both clocks can end up exhausted if the
+    /// timeout is not checked in between, but it is still the first flag to fall that counts.
+    pub fn check_timeout(&mut self) -> Option<GameResult> {
+        if self.is_finished() {
+            return None;
+        }
+
+        self.result = self.timeout_result();
+        self.result
+    }
+
+    /// The result of the game if it ended by [timeout](Self::check_timeout).
+    fn timeout_result(&self) -> Option<GameResult> {
+        let (color, timestamp) = self.first_flag_fall()?;
+        Some(if self.board.has_mating_material(color.other()) {
+            GameResult::Win {
+                winner: color.other(),
+                reason: WinReason::Timeout { timestamp },
+            }
+        } else {
+            GameResult::Draw {
+                reason: DrawReason::TimeoutVsInsufficientMaterial { timestamp },
+            }
+        })
+    }
+
+    /// The player that ran out of time first and the moment they did, if any has.
+    fn first_flag_fall(&self) -> Option<(Color, Timestamp)> {
+        let mut remaining = self.time_control.base_time;
+        for i in 0..=self.moves.len() {
+            let color = if i % 2 == 0 {
+                Color::White
+            } else {
+                Color::Black
+            };
+            let duration = self.move_duration(i)?;
+
+            // The clock only starts after the first move of each player.
+            if i >= 2 && duration >= remaining[color] {
+                let (_, start) = self.moves[i - 1];
+                return Some((color, start.checked_add(remaining[color]).ok()?));
+            }
+
+            if i < self.moves.len() {
+                remaining[color] = (remaining[color] - duration)
+                    .saturating_add(self.time_control.increment[color]);
+            }
+        }
+
+        None
+    }
+
     /// Whether the game has finished.
     #[inline]
     pub fn is_finished(&self) -> bool {
@@ -189,29 +452,58 @@ impl Game {
         });
     }
 
-    /// Offer a draw from the player of the given color
-    #[inline]
-    // TODO: These should be specific errors that are `#[from]` in `ApplyActionError`
-    pub fn offer_draw(&mut self, color: Color) -> Result<(), ApplyActionError> {
-        // TODO: Add a "draw already offered" error
-        self.draw_offer = Some(color);
-        Ok(())
+    /// Makes the player of the opposite color win because the player of the given color broke
+    /// the rules (e.g., an engine that keeps trying to play illegal moves).
+    pub fn forfeit(&mut self, color: Color) {
+        self.result = Some(GameResult::Win {
+            winner: color.other(),
+            reason: WinReason::Forfeit {
+                timestamp: Timestamp::now(),
+            },
+        });
     }
 
-    #[inline]
-    // TODO: These should be specific errors that are `#[from]` in `ApplyActionError`
-    pub fn retract_draw(&mut self, color: Color) -> Result<(), ApplyActionError> {
-        // TODO: Add a "no offered draw to retract" error
-        let Some(offered_by) = self.draw_offer else {
-            return Ok(());
-        };
+    /// Ends the game in a draw that neither player asked for (e.g., an engine match that went on
+    /// for too long).
+    pub fn adjudicate_draw(&mut self) {
+        self.result = Some(GameResult::Draw {
+            reason: DrawReason::Adjudication {
+                timestamp: Timestamp::now(),
+            },
+        });
+    }
+
+    /// Calls off the game on behalf of the player of the given color, so that nobody wins.
+    ///
+    /// Games can only be aborted before any move has been made.
+    pub fn abort(&mut self, color: Color) -> Result<(), ApplyActionError> {
+        if self.is_finished() {
+            return Err(ApplyActionError::GameIsFinished);
+        }
 
-        if offered_by != color {
-            // TODO: Add a "not your draw offer" error
-            return Ok(());
+        if !self.moves.is_empty() {
+            return Err(ApplyActionError::CannotAbort);
         }
 
-        self.draw_offer = None;
+        self.result = Some(GameResult::Aborted {
+            by: color,
+            timestamp: Timestamp::now(),
+        });
+
+        Ok(())
+    }
+
+    /// Offer a draw from the player of the given color, see [`DrawState::offer`].
+    #[inline]
+    pub fn offer_draw(&mut self, color: Color) -> Result<(), DrawError> {
+        self.draw_state = self.draw_state.offer(color)?;
+        Ok(())
+    }
+
+    /// Retracts the draw offered by the player of the given color, see [`DrawState::retract`].
+    #[inline]
+    pub fn retract_draw(&mut self, color: Color) -> Result<(), DrawError> {
+        self.draw_state = self.draw_state.retract(color)?;
         Ok(())
     }
 
@@ -229,40 +521,44 @@ impl Game {
                     return Err(ApplyActionError::GameIsFinished);
                 }
 
+                let is_pawn_move = matches!(mov, Move::Promotion { .. })
+                    || self.board.get(mov.origin(), color) == Some(Piece::Pawn);
+
                 let now = Timestamp::now();
+                let (capture, status) = self.board.apply_move_with_status(mov, color)?;
                 self.moves.push((mov, now));
-                self.board.apply_move(mov, color)?;
-
-                if self.board.possible_moves(color.other()).next().is_none() {
-                    if self.board.in_check(color.other()).is_some() {
-                        self.result = Some(GameResult::Win {
-                            winner: color,
-                            reason: WinReason::Checkmate,
-                        })
-                    } else {
-                        self.result = Some(GameResult::Draw {
-                            reason: DrawReason::Stalemate,
-                        })
-                    }
+                self.position_hashes.push(self.board.zobrist_hash());
+
+                if is_pawn_move || capture.is_some() {
+                    self.halfmove_clock = 0;
+                } else {
+                    self.halfmove_clock = self.halfmove_clock.saturating_add(1);
+                }
+
+                self.result = GameResult::from_status(status, color.other());
+                if self.automatic_draws
+                    && let Some(reason) = self.can_claim_draw()
+                {
+                    self.result = Some(GameResult::Draw { reason });
                 }
             }
             Action::Resign => self.resign(color),
             Action::OfferDraw => self.offer_draw(color)?,
             Action::RetractDraw => self.retract_draw(color)?,
             Action::AcceptDraw => self.accept_draw(color)?,
+            Action::ClaimDraw => self.claim_draw(color)?,
+            Action::Abort => self.abort(color)?,
         }
 
+        self.actions.push((color, action));
         Ok(())
     }
 
-    pub fn accept_draw(&mut self, color: Color) -> Result<(), ApplyActionError> {
-        let Some(offered_by) = self.draw_offer.take() else {
-            return Err(ApplyActionError::DrawNotOffered);
-        };
-
-        if offered_by == color {
-            return Err(ApplyActionError::DrawNotOffered);
-        }
+    /// Accepts the draw offered by the opponent of the given color, ending the game, see
+    /// [`DrawState::accept`].
+    pub fn accept_draw(&mut self, color: Color) -> Result<(), DrawError> {
+        let offered_by = self.draw_state.accept(color)?;
+        self.draw_state = DrawState::None;
 
         self.result = Some(GameResult::Draw {
             reason: DrawReason::Agreement {
@@ -274,11 +570,76 @@ impl Game {
         Ok(())
     }
 
+    /// Ends the game in the draw that [can be claimed](Self::can_claim_draw), if the given color
+    /// is the one to move.
+    pub fn claim_draw(&mut self, color: Color) -> Result<(), ApplyActionError> {
+        if self.is_finished() {
+            return Err(ApplyActionError::GameIsFinished);
+        }
+
+        if color != self.turn() {
+            return Err(ApplyActionError::NotYourTurn);
+        }
+
+        let reason = self
+            .can_claim_draw()
+            .ok_or(ApplyActionError::NoDrawToClaim)?;
+        self.result = Some(GameResult::Draw { reason });
+
+        Ok(())
+    }
+
+    /// All the actions the player whose [turn](Self::turn) it is can take.
+    ///
+    /// This includes every legal [`Action::Move`], [`Action::Resign`], whichever draw actions
+    /// are valid given the current [draw offer](Self::draw_offer), [`Action::ClaimDraw`] if a
+    /// draw [can be claimed](Self::can_claim_draw) and [`Action::Abort`] if no move has been
+    /// made yet. Returns an empty list if the game has finished.
+    pub fn legal_actions(&self) -> Vec<Action> {
+        if self.is_finished() {
+            return Vec::new();
+        }
+
+        let color = self.turn();
+        let mut actions = self
+            .board
+            .possible_moves(color)
+            .map(Action::Move)
+            .collect::<Vec<_>>();
+
+        actions.push(Action::Resign);
+        match self.draw_state {
+            DrawState::None => actions.push(Action::OfferDraw),
+            DrawState::OfferedBy(offered_by) if offered_by == color => {
+                actions.push(Action::RetractDraw)
+            }
+            DrawState::OfferedBy(_) => actions.extend([Action::AcceptDraw, Action::OfferDraw]),
+        }
+
+        if self.can_claim_draw().is_some() {
+            actions.push(Action::ClaimDraw);
+        }
+
+        if self.moves.is_empty() {
+            actions.push(Action::Abort);
+        }
+
+        actions
+    }
+
+    /// The color of the player that offered a draw, if any.
     #[inline]
     pub fn draw_offer(&self) -> Option<Color> {
-        self.draw_offer
+        self.draw_state.offered_by()
+    }
+
+    /// Whether a draw has been offered, and by whom.
+    #[inline]
+    pub fn draw_state(&self) -> DrawState {
+        self.draw_state
     }
 
+    /// The time control the game is played with.
     pub fn time_control(&self) -> TimeControl {
         self.time_control
     }
@@ -286,14 +647,30 @@ impl Game {
 
 /// The result of a [`Game`]
 #[allow(missing_docs)]
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum GameResult {
     Win { winner: Color, reason: WinReason },
     Draw { reason: DrawReason },
+    Aborted { by: Color, timestamp: Timestamp },
 }
 
 impl GameResult {
+    /// The result of a board with the given [`BoardStatus`] for the player that has to move, or
+    /// `None` if the game goes on.
+    fn from_status(status: BoardStatus, to_move: Color) -> Option<Self> {
+        match status {
+            BoardStatus::Ongoing => None,
+            BoardStatus::Checkmate => Some(GameResult::Win {
+                winner: to_move.other(),
+                reason: WinReason::Checkmate,
+            }),
+            BoardStatus::Stalemate => Some(GameResult::Draw {
+                reason: DrawReason::Stalemate,
+            }),
+        }
+    }
+
     /// The moment the game ended.
     pub fn time_of_end(&self) -> Option<Timestamp> {
         match *self {
@@ -301,18 +678,85 @@ impl GameResult {
                 WinReason::Checkmate => None,
                 WinReason::Resignation { timestamp } => Some(timestamp),
                 WinReason::Timeout { timestamp } => Some(timestamp),
+                WinReason::Forfeit { timestamp } => Some(timestamp),
             },
             GameResult::Draw { reason } => match reason {
                 DrawReason::Stalemate => None,
                 DrawReason::FiftyMoves => None,
+                DrawReason::Repetition => None,
                 DrawReason::Agreement { timestamp, .. } => Some(timestamp),
+                DrawReason::TimeoutVsInsufficientMaterial { timestamp } => Some(timestamp),
+                DrawReason::Adjudication { timestamp } => Some(timestamp),
+            },
+            GameResult::Aborted { timestamp, .. } => Some(timestamp),
+        }
+    }
+}
+
+impl fmt::Display for GameResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Outcome::from(*self).fmt(f)
+    }
+}
+
+/// How a game ended, as given by [`Game::outcome`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Outcome {
+    /// The color of the player that won, or `None` if the game was a draw or was aborted.
+    pub winner: Option<Color>,
+
+    /// Why the game ended.
+    pub reason: EndReason,
+}
+
+/// Why a game ended, either in a win, in a draw or by being aborted.
+#[allow(missing_docs)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EndReason {
+    Win(WinReason),
+    Draw(DrawReason),
+    Aborted { by: Color },
+}
+
+impl From<GameResult> for Outcome {
+    fn from(result: GameResult) -> Self {
+        match result {
+            GameResult::Win { winner, reason } => Outcome {
+                winner: Some(winner),
+                reason: EndReason::Win(reason),
+            },
+            GameResult::Draw { reason } => Outcome {
+                winner: None,
+                reason: EndReason::Draw(reason),
+            },
+            GameResult::Aborted { by, .. } => Outcome {
+                winner: None,
+                reason: EndReason::Aborted { by },
             },
         }
     }
 }
 
+impl fmt::Display for Outcome {
+    /// Formats the outcome as, e.g., "White wins by checkmate" or "Draw by stalemate".
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.winner, self.reason) {
+            (Some(winner), EndReason::Win(reason)) => {
+                write!(f, "{} wins by {reason}", winner.choose("White", "Black"))
+            }
+            (_, EndReason::Win(reason)) => write!(f, "Win by {reason}"),
+            (_, EndReason::Draw(reason)) => write!(f, "Draw by {reason}"),
+            (_, EndReason::Aborted { by }) => {
+                write!(f, "Aborted by {}", by.choose("white", "black"))
+            }
+        }
+    }
+}
+
 /// The way the player won a game.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum WinReason {
     /// The opponent was in check and had no legal moves remaining.
@@ -329,10 +773,26 @@ pub enum WinReason {
         /// The moment the player ran out of time.
         timestamp: Timestamp,
     },
+    /// The opponent was disqualified for breaking the rules.
+    Forfeit {
+        /// The moment the player forfeited.
+        timestamp: Timestamp,
+    },
+}
+
+impl fmt::Display for WinReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            WinReason::Checkmate => "checkmate",
+            WinReason::Resignation { .. } => "resignation",
+            WinReason::Timeout { .. } => "timeout",
+            WinReason::Forfeit { .. } => "forfeit",
+        })
+    }
 }
 
 /// The way a game resulted in a draw.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum DrawReason {
     /// The opponent had no legal moves remaining, but it wasn't in check.
@@ -341,6 +801,8 @@ pub enum DrawReason {
     Stalemate,
     /// There were fifty moves played after the last pawn move.
     FiftyMoves,
+    /// The same position was reached three times.
+    Repetition,
     /// Both players agreed to a draw.
     Agreement {
         /// The color of the player that offerred a draw.
@@ -348,6 +810,32 @@ pub enum DrawReason {
         /// The moment both players agreed to a draw.
         timestamp: Timestamp,
     },
+    /// A player ran out of time, but their opponent doesn't have enough material to checkmate.
+    TimeoutVsInsufficientMaterial {
+        /// The moment the player ran out of time.
+        timestamp: Timestamp,
+    },
+    /// The game was [stopped as a draw](Game::adjudicate_draw) from outside, without either
+    /// player asking for it.
+    Adjudication {
+        /// The moment the game was stopped.
+        timestamp: Timestamp,
+    },
+}
+
+impl fmt::Display for DrawReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DrawReason::Stalemate => "stalemate",
+            DrawReason::FiftyMoves => "the fifty-move rule",
+            DrawReason::Repetition => "threefold repetition",
+            DrawReason::Agreement { .. } => "agreement",
+            DrawReason::TimeoutVsInsufficientMaterial { .. } => {
+                "timeout against insufficient material"
+            }
+            DrawReason::Adjudication { .. } => "adjudication",
+        })
+    }
 }
 
 #[allow(missing_docs)]
@@ -359,8 +847,14 @@ pub enum ApplyActionError {
     #[error("Game is finished, cannot do any more moves.")]
     GameIsFinished,
 
-    #[error("Opponent has not offered a draw.")]
-    DrawNotOffered,
+    #[error("{0}")]
+    Draw(#[from] DrawError),
+
+    #[error("There is no draw to claim.")]
+    NoDrawToClaim,
+
+    #[error("Games can only be aborted before any move is made.")]
+    CannotAbort,
 
     #[error("It is your opponent's turn")]
     NotYourTurn,
@@ -374,6 +868,8 @@ impl fmt::Display for Action {
             Action::OfferDraw => write!(f, "offers draw"),
             Action::RetractDraw => write!(f, "rectracts draw"),
             Action::AcceptDraw => write!(f, "accepts the draw"),
+            Action::ClaimDraw => write!(f, "claims a draw"),
+            Action::Abort => write!(f, "aborts the game"),
         }
     }
 }
@@ -0,0 +1,130 @@
+//! Names for the first few moves of a game, in the spirit of chess' ECO codes.
+//!
+//! Openings are looked up by the moves that were played, in order, so different move orders that
+//! reach the same position are not recognized as the same opening.
+
+use crate::{Board, Move, Vec2, vec2};
+
+use super::Game;
+
+/// A named opening, with the origin and destination of each of its moves (alternating between
+/// white and black, starting with white).
+struct Opening {
+    name: &'static str,
+    moves: &'static [(Vec2, Vec2)],
+}
+
+/// The known openings. When several match a game, the longest one is used.
+const OPENINGS: &[Opening] = &[
+    Opening {
+        name: "Central pawn opening",
+        moves: &[(vec2!(4, 4), vec2!(5, 5))],
+    },
+    Opening {
+        name: "Central pawn opening, knight defence",
+        moves: &[(vec2!(4, 4), vec2!(5, 5)), (vec2!(8, 10), vec2!(7, 7))],
+    },
+    Opening {
+        name: "Pawn double step",
+        moves: &[(vec2!(4, 3), vec2!(6, 5))],
+    },
+    Opening {
+        name: "Knight's opening",
+        moves: &[(vec2!(2, 0), vec2!(3, 3))],
+    },
+    Opening {
+        name: "Knight's opening, symmetrical",
+        moves: &[(vec2!(2, 0), vec2!(3, 3)), (vec2!(8, 10), vec2!(7, 7))],
+    },
+];
+
+impl Game {
+    /// The name of the [opening](self) that the game started with, if it is a known one.
+    ///
+    /// Games that don't start from the [default position](Board::default) have no opening.
+    pub fn opening_name(&self) -> Option<&str> {
+        if self.initial_board != Board::default() {
+            return None;
+        }
+
+        OPENINGS
+            .iter()
+            .filter(|opening| {
+                opening.moves.len() <= self.moves.len()
+                    && opening.moves.iter().zip(&self.moves).all(
+                        |(&(origin, destination), &(mov, _))| {
+                            mov == Move::Regular {
+                                origin,
+                                destination,
+                                captures: false,
+                            }
+                        },
+                    )
+            })
+            .max_by_key(|opening| opening.moves.len())
+            .map(|opening| opening.name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        Vec2,
+        game::{Action, Game, TimeControl},
+        vec2,
+    };
+
+    use super::OPENINGS;
+
+    /// Plays the moves given as origin and destination, alternating colors from white.
+    fn play(game: &mut Game, moves: &[(Vec2, Vec2)]) {
+        for &(origin, destination) in moves {
+            let color = game.turn();
+            let (mov, _) = game.board().get_move(origin, destination, color).unwrap();
+            game.apply_action(Action::Move(mov), color).unwrap();
+        }
+    }
+
+    #[test]
+    fn every_opening_is_legal() {
+        for opening in OPENINGS {
+            let mut game = Game::new(TimeControl::rapid());
+            play(&mut game, opening.moves);
+            assert_eq!(game.opening_name(), Some(opening.name));
+        }
+    }
+
+    #[test]
+    fn known_prefixes_are_classified() {
+        let mut game = Game::new(TimeControl::rapid());
+        assert_eq!(game.opening_name(), None);
+
+        play(&mut game, &[(vec2!(4, 4), vec2!(5, 5))]);
+        assert_eq!(game.opening_name(), Some("Central pawn opening"));
+
+        // The name is kept after the opening, and refined by longer openings.
+        play(
+            &mut game,
+            &[(vec2!(8, 10), vec2!(7, 7)), (vec2!(2, 0), vec2!(3, 3))],
+        );
+        assert_eq!(
+            game.opening_name(),
+            Some("Central pawn opening, knight defence")
+        );
+
+        // Unknown continuations keep the name of the shorter opening.
+        let mut game = Game::new(TimeControl::rapid());
+        play(
+            &mut game,
+            &[(vec2!(2, 0), vec2!(3, 3)), (vec2!(6, 7), vec2!(5, 6))],
+        );
+        assert_eq!(game.opening_name(), Some("Knight's opening"));
+    }
+
+    #[test]
+    fn unknown_prefixes_are_not_classified() {
+        let mut game = Game::new(TimeControl::rapid());
+        play(&mut game, &[(vec2!(4, 0), vec2!(5, 1))]);
+        assert_eq!(game.opening_name(), None);
+    }
+}
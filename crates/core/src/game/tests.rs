@@ -1,6 +1,7 @@
 #![cfg(test)]
 
 use super::*;
+use crate::{Side, piece::Piece, vec2};
 
 #[test]
 fn fn_move_duration_returns_move_duration_for_moves_0_to_3() {
@@ -24,3 +25,885 @@ fn fn_move_duration_returns_move_duration_for_moves_0_to_3() {
     assert_eq!(game.move_duration(1), Some(Duration::ZERO));
     assert!(game.move_duration(2).unwrap() - move_duration <= Duration::from_millis(5));
 }
+
+#[test]
+fn untimed_games_never_run_out_of_time() {
+    let mut game = Game::new(TimeControl::max());
+    for _ in 0..6 {
+        let color = game.turn();
+        let action = Action::Move(game.board().possible_moves(color).next().unwrap());
+        game.apply_action(action, color).unwrap();
+    }
+
+    for color in [Color::White, Color::Black] {
+        assert!(game.time_remaining(color) > Duration::from_secs(60 * 60 * 24 * 365));
+    }
+}
+
+#[test]
+fn increment_is_granted_once_per_completed_move() {
+    let increment = Duration::from_secs(2);
+    let mut game = Game::new(TimeControl::new(Duration::ZERO, increment));
+    assert_eq!(game.time_remaining(Color::White), Duration::ZERO);
+
+    for moves_played in 1..=6 {
+        let color = game.turn();
+        let action = Action::Move(game.board().possible_moves(color).next().unwrap());
+        game.apply_action(action, color).unwrap();
+
+        // Once the move is played, the clock has grown by exactly the increment, minus the time
+        // spent on the move.
+        let completed = (moves_played + 1 - color as u32) / 2;
+        assert_eq!(game.moves_from(color).count() as u32, completed);
+        assert_eq!(
+            game.time_remaining(color) + game.total_time(color),
+            increment * completed
+        );
+    }
+
+    // The move being thought about doesn't get the increment yet.
+    let color = game.turn();
+    assert!(game.time_remaining(color) <= increment * 3);
+    assert!(game.time_remaining(color) > increment * 2);
+}
+
+#[test]
+fn total_and_average_move_times() {
+    fn play(game: &mut Game, thinking_time: Duration) {
+        let_time_pass(game, thinking_time);
+        let color = game.turn();
+        let mov = game.board().possible_moves(color).next().unwrap();
+        game.apply_action(Action::Move(mov), color).unwrap();
+    }
+
+    // The test itself also takes some time, so durations are a bit longer than the simulated
+    // ones.
+    let is_about = |duration: Duration, secs| {
+        let expected = Duration::from_secs(secs);
+        duration >= expected && duration - expected < Duration::from_secs(1)
+    };
+
+    let mut game = Game::new(TimeControl::rapid());
+
+    // First moves are free, even if they take a while.
+    play(&mut game, Duration::from_secs(50));
+    play(&mut game, Duration::from_secs(50));
+    assert!(is_about(game.total_time(Color::White), 0));
+    assert_eq!(game.average_move_time(Color::White), None);
+
+    play(&mut game, Duration::from_secs(40));
+    play(&mut game, Duration::from_secs(20));
+    play(&mut game, Duration::from_secs(120));
+
+    assert!(is_about(game.total_time(Color::White), 160));
+    assert!(is_about(game.average_move_time(Color::White).unwrap(), 80));
+
+    // Black is thinking, which counts towards the total but not the average.
+    let_time_pass(&mut game, Duration::from_secs(40));
+    assert!(is_about(game.total_time(Color::Black), 60));
+    assert!(is_about(game.average_move_time(Color::Black).unwrap(), 20));
+}
+
+#[test]
+fn fn_legal_actions_on_a_fresh_game_includes_opening_moves_resign_and_draw_offer() {
+    let game = Game::new(TimeControl::rapid());
+    let actions = game.legal_actions();
+
+    for mov in game.board().possible_moves(Color::White) {
+        assert!(actions.contains(&Action::Move(mov)), "{mov} is missing");
+    }
+
+    assert!(actions.contains(&Action::Resign));
+    assert!(actions.contains(&Action::OfferDraw));
+    assert!(!actions.contains(&Action::AcceptDraw));
+    assert!(!actions.contains(&Action::RetractDraw));
+    assert!(actions.contains(&Action::Abort));
+    assert_eq!(
+        actions.len(),
+        game.board().possible_moves(Color::White).count() + 3
+    );
+}
+
+#[test]
+fn fn_legal_actions_is_empty_when_game_is_finished() {
+    let mut game = Game::new(TimeControl::rapid());
+    game.apply_action(Action::Resign, Color::White).unwrap();
+
+    assert!(game.legal_actions().is_empty());
+}
+
+#[test]
+fn games_can_only_be_aborted_before_the_first_move() {
+    let mut game = Game::new(TimeControl::rapid());
+    game.apply_action(Action::Abort, Color::Black).unwrap();
+
+    assert_eq!(game.winner(), Some(None));
+    assert!(matches!(
+        game.result(),
+        Some(GameResult::Aborted {
+            by: Color::Black,
+            ..
+        })
+    ));
+    assert_eq!(game.outcome().unwrap().to_string(), "Aborted by black");
+
+    assert_eq!(game.to_transcript(), "black abort\n");
+
+    let mut game = Game::new(TimeControl::rapid());
+    let mov = game.board().possible_moves(Color::White).next().unwrap();
+    game.apply_action(Action::Move(mov), Color::White).unwrap();
+
+    assert!(!game.legal_actions().contains(&Action::Abort));
+    assert!(matches!(
+        game.apply_action(Action::Abort, Color::White),
+        Err(ApplyActionError::CannotAbort)
+    ));
+    assert!(!game.is_finished());
+}
+
+#[test]
+fn fn_halfmove_clock_increments_on_piece_moves_and_resets_on_pawn_moves() {
+    let mut game = Game::new(TimeControl::rapid());
+    assert_eq!(game.halfmove_clock(), 0);
+
+    let mut play = |origin, destination, expected_clock| {
+        let color = game.turn();
+        let (mov, _) = game.board().get_move(origin, destination, color).unwrap();
+        game.apply_action(Action::Move(mov), color).unwrap();
+        assert_eq!(game.halfmove_clock(), expected_clock);
+    };
+
+    // Knights
+    play(vec2!(0, 2), vec2!(3, 3), 1);
+    play(vec2!(10, 8), vec2!(7, 7), 2);
+    play(vec2!(3, 3), vec2!(0, 2), 3);
+
+    // Pawn
+    play(vec2!(6, 9), vec2!(5, 8), 0);
+
+    // Knight again
+    play(vec2!(0, 2), vec2!(3, 3), 1);
+}
+
+#[test]
+fn completing_a_threefold_repetition_is_detected() {
+    let mut game = Game::new(TimeControl::rapid());
+
+    let get_move = |game: &Game, origin, destination| {
+        let (mov, _) = game
+            .board()
+            .get_move(origin, destination, game.turn())
+            .unwrap();
+        mov
+    };
+
+    let mut play = |origin, destination| {
+        let mov = get_move(&game, origin, destination);
+        assert!(!game.would_be_repetition(mov));
+        game.apply_action(Action::Move(mov), game.turn()).unwrap();
+    };
+
+    // Shuffle the knights back and forth, until the initial position has been reached twice.
+    play(vec2!(0, 2), vec2!(3, 3));
+    play(vec2!(10, 8), vec2!(7, 7));
+    play(vec2!(3, 3), vec2!(0, 2));
+    play(vec2!(7, 7), vec2!(10, 8));
+
+    play(vec2!(0, 2), vec2!(3, 3));
+    play(vec2!(10, 8), vec2!(7, 7));
+    play(vec2!(3, 3), vec2!(0, 2));
+
+    // Going back to the initial position a third time is a repetition.
+    let repeating = get_move(&game, vec2!(7, 7), vec2!(10, 8));
+    assert!(game.would_be_repetition(repeating));
+
+    // Any other knight move from the same tile isn't.
+    let other = game
+        .board()
+        .possible_moves(Color::Black)
+        .find(|mov| mov.origin() == vec2!(7, 7) && mov != &repeating)
+        .unwrap();
+    assert!(!game.would_be_repetition(other));
+}
+
+/// Shuffles both knights back and forth twice, which reaches the initial position for the third
+/// time with the last move.
+fn shuffle_knights(game: &mut Game) {
+    for _ in 0..2 {
+        for (origin, destination) in [
+            (vec2!(0, 2), vec2!(3, 3)),
+            (vec2!(10, 8), vec2!(7, 7)),
+            (vec2!(3, 3), vec2!(0, 2)),
+            (vec2!(7, 7), vec2!(10, 8)),
+        ] {
+            let color = game.turn();
+            let (mov, _) = game.board().get_move(origin, destination, color).unwrap();
+            game.apply_action(Action::Move(mov), color).unwrap();
+        }
+    }
+}
+
+#[test]
+fn repetitions_can_be_claimed_once_they_happen() {
+    let mut game = Game::new(TimeControl::rapid());
+    assert!(matches!(
+        game.apply_action(Action::ClaimDraw, Color::White),
+        Err(ApplyActionError::NoDrawToClaim)
+    ));
+
+    shuffle_knights(&mut game);
+    assert_eq!(game.result(), None);
+    assert_eq!(game.can_claim_draw(), Some(DrawReason::Repetition));
+    assert!(game.legal_actions().contains(&Action::ClaimDraw));
+
+    assert!(matches!(
+        game.apply_action(Action::ClaimDraw, Color::Black),
+        Err(ApplyActionError::NotYourTurn)
+    ));
+    game.apply_action(Action::ClaimDraw, Color::White).unwrap();
+    assert_eq!(
+        game.result(),
+        Some(GameResult::Draw {
+            reason: DrawReason::Repetition
+        })
+    );
+    assert_eq!(game.can_claim_draw(), None);
+}
+
+#[test]
+fn fifty_move_rule_can_be_claimed_once_it_happens() {
+    let mut game = Game::new(TimeControl::rapid());
+    game.halfmove_clock = 98;
+
+    let play = |game: &mut Game, origin, destination| {
+        let color = game.turn();
+        let (mov, _) = game.board().get_move(origin, destination, color).unwrap();
+        game.apply_action(Action::Move(mov), color).unwrap();
+    };
+    play(&mut game, vec2!(0, 2), vec2!(3, 3));
+    assert_eq!(game.can_claim_draw(), None);
+    play(&mut game, vec2!(10, 8), vec2!(7, 7));
+    assert_eq!(game.can_claim_draw(), Some(DrawReason::FiftyMoves));
+
+    game.apply_action(Action::ClaimDraw, Color::White).unwrap();
+    assert_eq!(
+        game.result(),
+        Some(GameResult::Draw {
+            reason: DrawReason::FiftyMoves
+        })
+    );
+}
+
+#[test]
+fn automatic_draws_end_the_game_without_a_claim() {
+    let mut game = Game::new(TimeControl::rapid()).with_automatic_draws(true);
+    shuffle_knights(&mut game);
+    assert_eq!(
+        game.result(),
+        Some(GameResult::Draw {
+            reason: DrawReason::Repetition
+        })
+    );
+    assert!(matches!(
+        game.apply_action(Action::ClaimDraw, Color::White),
+        Err(ApplyActionError::GameIsFinished)
+    ));
+}
+
+#[test]
+fn automatic_draws_round_trip_through_transcript() {
+    let mut game = Game::new(TimeControl::rapid()).with_automatic_draws(true);
+    shuffle_knights(&mut game);
+
+    let transcript = game.to_transcript();
+    assert!(
+        transcript.starts_with("automatic-draws\nwhite move "),
+        "{transcript}"
+    );
+
+    let reconstructed = Game::from_transcript(&transcript, TimeControl::rapid()).unwrap();
+    assert!(reconstructed.automatic_draws());
+    assert_eq!(reconstructed.result(), game.result());
+    assert_eq!(reconstructed.to_transcript(), transcript);
+
+    // The header goes after the position.
+    let board = Board::new_minimal(vec2!(0, 0), vec2!(10, 10)).unwrap();
+    let game = Game::from_position(board, TimeControl::rapid())
+        .unwrap()
+        .with_automatic_draws(true);
+    let transcript = game.to_transcript();
+    assert!(
+        transcript.ends_with(" -\nautomatic-draws\n"),
+        "{transcript}"
+    );
+    let reconstructed = Game::from_transcript(&transcript, TimeControl::rapid()).unwrap();
+    assert!(reconstructed.automatic_draws());
+    assert_eq!(reconstructed.initial_board(), game.initial_board());
+}
+
+#[test]
+fn invalid_draw_actions_are_rejected_without_changing_the_offer() {
+    let mut game = Game::new(TimeControl::rapid());
+    assert!(matches!(
+        game.apply_action(Action::RetractDraw, Color::White),
+        Err(ApplyActionError::Draw(DrawError::NotOffered))
+    ));
+
+    game.apply_action(Action::OfferDraw, Color::White).unwrap();
+    for (action, error) in [
+        (Action::OfferDraw, DrawError::AlreadyOffered),
+        (Action::AcceptDraw, DrawError::OwnOffer),
+    ] {
+        assert!(matches!(
+            game.apply_action(action, Color::White),
+            Err(ApplyActionError::Draw(err)) if err == error
+        ));
+    }
+    assert!(matches!(
+        game.apply_action(Action::RetractDraw, Color::Black),
+        Err(ApplyActionError::Draw(DrawError::NotYourOffer))
+    ));
+    assert_eq!(game.draw_state(), DrawState::OfferedBy(Color::White));
+    assert_eq!(game.result(), None);
+}
+
+#[test]
+fn draw_agreement_round_trips_through_transcript() {
+    let mut game = Game::new(TimeControl::rapid());
+    for (origin, destination, color) in [
+        (vec2!(0, 2), vec2!(3, 3), Color::White),
+        (vec2!(10, 8), vec2!(7, 7), Color::Black),
+    ] {
+        let (mov, _) = game.board().get_move(origin, destination, color).unwrap();
+        game.apply_action(Action::Move(mov), color).unwrap();
+    }
+
+    game.apply_action(Action::OfferDraw, Color::White).unwrap();
+    game.apply_action(Action::RetractDraw, Color::White)
+        .unwrap();
+    game.apply_action(Action::OfferDraw, Color::Black).unwrap();
+    game.apply_action(Action::AcceptDraw, Color::White).unwrap();
+
+    let transcript = game.to_transcript();
+    assert_eq!(
+        transcript,
+        "white move 0,2 3,3\n\
+         black move 10,8 7,7\n\
+         white offer-draw\n\
+         white retract-draw\n\
+         black offer-draw\n\
+         white accept-draw\n"
+    );
+
+    let reconstructed = Game::from_transcript(&transcript, TimeControl::rapid()).unwrap();
+    assert_eq!(reconstructed.board(), game.board());
+    assert_eq!(reconstructed.draw_offer(), None);
+    assert!(matches!(
+        reconstructed.result(),
+        Some(GameResult::Draw {
+            reason: DrawReason::Agreement {
+                offered_by: Color::Black,
+                ..
+            }
+        })
+    ));
+    assert_eq!(reconstructed.to_transcript(), transcript);
+}
+
+#[test]
+fn resignation_round_trips_through_transcript() {
+    let mut game = Game::new(TimeControl::rapid());
+    game.apply_action(Action::Resign, Color::White).unwrap();
+
+    let reconstructed = Game::from_transcript(&game.to_transcript(), TimeControl::rapid()).unwrap();
+    assert_eq!(reconstructed.winner(), Some(Some(Color::Black)));
+}
+
+#[test]
+fn custom_starting_position_round_trips_through_transcript() {
+    let board = Board::new_minimal(vec2!(0, 0), vec2!(10, 10)).unwrap();
+    let mut game = Game::from_position(board.clone(), TimeControl::rapid()).unwrap();
+    for (color, origin, destination) in [
+        (Color::White, vec2!(0, 0), vec2!(1, 1)),
+        (Color::Black, vec2!(10, 10), vec2!(9, 9)),
+    ] {
+        let (mov, _) = game.board().get_move(origin, destination, color).unwrap();
+        game.apply_action(Action::Move(mov), color).unwrap();
+    }
+
+    let transcript = game.to_transcript();
+    assert_eq!(
+        transcript,
+        "position K/2/3/4/5/6/5/6/5/6/5/6/5/6/5/6/5/4/3/2/k -\n\
+         white move 0,0 1,1\n\
+         black move 10,10 9,9\n"
+    );
+
+    let reconstructed = Game::from_transcript(&transcript, TimeControl::rapid()).unwrap();
+    assert_eq!(reconstructed.initial_board(), &board);
+    assert_eq!(reconstructed.board(), game.board());
+    assert_eq!(reconstructed.to_transcript(), transcript);
+}
+
+#[test]
+fn promotions_from_a_custom_position_round_trip_through_transcript_and_fen() {
+    let mut board = Board::new_minimal(vec2!(0, 0), vec2!(5, 10)).unwrap();
+    board
+        .get_mut(vec2!(9, 7), Color::White)
+        .replace(Piece::Pawn);
+    board
+        .get_mut(vec2!(10, 7), Color::Black)
+        .replace(Piece::Rook);
+    let mut game = Game::from_position(board, TimeControl::rapid()).unwrap();
+
+    let promotion = game
+        .board()
+        .moves_from(vec2!(9, 7), Color::White)
+        .find(|mov| {
+            matches!(
+                mov,
+                Move::Promotion {
+                    captures: Some(_),
+                    promoting_to: Piece::Knight,
+                    ..
+                }
+            )
+        })
+        .unwrap();
+    game.apply_action(Action::Move(promotion), Color::White)
+        .unwrap();
+
+    let transcript = game.to_transcript();
+    let reconstructed = Game::from_transcript(&transcript, TimeControl::rapid()).unwrap();
+    assert_eq!(reconstructed.board(), game.board());
+    assert_eq!(reconstructed.to_transcript(), transcript);
+    assert_eq!(
+        reconstructed.board().get(vec2!(10, 7), Color::White),
+        Some(Piece::Knight)
+    );
+
+    let fen = game.board().to_fen();
+    assert!(fen.ends_with(" 9,7*10,7=N"), "{fen}");
+    assert_eq!(&Board::from_fen(&fen).unwrap(), game.board());
+}
+
+#[test]
+fn moves_without_coordinates_are_unsupported_in_transcripts() {
+    // Deserialized games can have moves that can't be played.
+    let mut game = Game::new(TimeControl::rapid());
+    game.actions.push((
+        Color::White,
+        Action::Move(Move::EnPassant {
+            file: 3,
+            direction: Side::King,
+        }),
+    ));
+
+    let transcript = game.to_transcript();
+    assert_eq!(transcript, "white unsupported-move\n");
+    assert!(matches!(
+        Game::from_transcript(&transcript, TimeControl::rapid()),
+        Err(TranscriptError::InvalidLine { line_number: 1 })
+    ));
+}
+
+#[test]
+fn invalid_transcripts_are_rejected() {
+    assert!(matches!(
+        Game::from_transcript("white jump", TimeControl::rapid()),
+        Err(TranscriptError::InvalidLine { line_number: 1 })
+    ));
+
+    assert!(matches!(
+        Game::from_transcript(
+            "white move 0,2 3,3\nwhite move 3,3 0,2",
+            TimeControl::rapid()
+        ),
+        Err(TranscriptError::InvalidAction { line_number: 2, .. })
+    ));
+
+    assert!(matches!(
+        Game::from_transcript("\nposition K/2/3 -", TimeControl::rapid()),
+        Err(TranscriptError::InvalidPosition { line_number: 2, .. })
+    ));
+
+    // Black is in check with white to move.
+    let mut checked = Board::new_minimal(vec2!(0, 0), vec2!(10, 10)).unwrap();
+    checked
+        .get_mut(vec2!(10, 8), Color::White)
+        .replace(Piece::Rook);
+    assert!(matches!(
+        Game::from_transcript(
+            &format!("position {}", checked.to_fen()),
+            TimeControl::rapid()
+        ),
+        Err(TranscriptError::InvalidStartingPosition(
+            InvalidBoardError::WaitingPlayerInCheck {
+                color: Color::Black
+            }
+        ))
+    ));
+}
+
+/// Plays the first move of the player whose turn it is that leads to a position where the
+/// opponent has the given status.
+fn play_into(game: &mut Game, status: BoardStatus) {
+    let color = game.turn();
+    let mov = game
+        .board()
+        .possible_moves(color)
+        .find(|&mov| {
+            let mut board = game.board().clone();
+            board.apply_move(mov, color).unwrap();
+            board.status(color.other()) == status
+        })
+        .unwrap();
+
+    game.apply_action(Action::Move(mov), color).unwrap();
+}
+
+#[test]
+fn outcome_of_checkmate_and_stalemate() {
+    let mut board = Board::new_minimal(vec2!(0, 0), vec2!(10, 10)).unwrap();
+    board
+        .get_mut(vec2!(8, 4), Color::White)
+        .replace(Piece::Queen);
+    let mut game = Game::from_position(board, TimeControl::rapid()).unwrap();
+    assert_eq!(game.outcome(), None);
+
+    play_into(&mut game, BoardStatus::Checkmate);
+    let outcome = game.outcome().unwrap();
+    assert_eq!(
+        outcome,
+        Outcome {
+            winner: Some(Color::White),
+            reason: EndReason::Win(WinReason::Checkmate)
+        }
+    );
+    assert_eq!(outcome.to_string(), "White wins by checkmate");
+
+    let mut board = Board::new_minimal(vec2!(7, 7), vec2!(10, 10)).unwrap();
+    board
+        .get_mut(vec2!(9, 4), Color::White)
+        .replace(Piece::Queen);
+    let mut game = Game::from_position(board, TimeControl::rapid()).unwrap();
+    play_into(&mut game, BoardStatus::Stalemate);
+    let outcome = game.outcome().unwrap();
+    assert_eq!(
+        outcome,
+        Outcome {
+            winner: None,
+            reason: EndReason::Draw(DrawReason::Stalemate)
+        }
+    );
+    assert_eq!(outcome.to_string(), "Draw by stalemate");
+}
+
+#[test]
+fn outcome_of_resignation_forfeit_agreement_and_adjudication() {
+    let mut game = Game::new(TimeControl::rapid());
+    game.apply_action(Action::Resign, Color::Black).unwrap();
+    assert!(matches!(
+        game.outcome(),
+        Some(Outcome {
+            winner: Some(Color::White),
+            reason: EndReason::Win(WinReason::Resignation { .. })
+        })
+    ));
+    assert_eq!(
+        game.outcome().unwrap().to_string(),
+        "White wins by resignation"
+    );
+
+    let mut game = Game::new(TimeControl::rapid());
+    game.forfeit(Color::White);
+    assert!(matches!(
+        game.outcome(),
+        Some(Outcome {
+            winner: Some(Color::Black),
+            reason: EndReason::Win(WinReason::Forfeit { .. })
+        })
+    ));
+
+    let mut game = Game::new(TimeControl::rapid());
+    game.apply_action(Action::OfferDraw, Color::White).unwrap();
+    game.apply_action(Action::AcceptDraw, Color::Black).unwrap();
+    assert!(matches!(
+        game.outcome(),
+        Some(Outcome {
+            winner: None,
+            reason: EndReason::Draw(DrawReason::Agreement {
+                offered_by: Color::White,
+                ..
+            })
+        })
+    ));
+    assert_eq!(game.outcome().unwrap().to_string(), "Draw by agreement");
+
+    let mut game = Game::new(TimeControl::rapid());
+    game.adjudicate_draw();
+    assert!(matches!(
+        game.outcome(),
+        Some(Outcome {
+            winner: None,
+            reason: EndReason::Draw(DrawReason::Adjudication { .. })
+        })
+    ));
+}
+
+#[test]
+fn outcome_of_running_out_of_time() {
+    let base_time = Duration::from_secs(60);
+    let mut game = start_clocks(Board::default(), base_time);
+
+    assert_eq!(game.outcome(), None);
+    let_time_pass(&mut game, base_time * 2);
+
+    let Some(Outcome {
+        winner: Some(Color::Black),
+        reason: EndReason::Win(WinReason::Timeout { timestamp }),
+    }) = game.outcome()
+    else {
+        panic!("Expected white to lose on time, got {:?}", game.outcome());
+    };
+
+    let (_, start) = game.moves_from(Color::Black).last().unwrap();
+    assert_eq!(timestamp.duration_since(*start).unsigned_abs(), base_time);
+    assert_eq!(game.outcome().unwrap().to_string(), "Black wins by timeout");
+}
+
+/// Plays the first move of each color, which start the clocks, from `board`.
+fn start_clocks(board: Board, base_time: Duration) -> Game {
+    let mut game = Game::from_position(board, TimeControl::no_increment(base_time)).unwrap();
+    for color in [Color::White, Color::Black] {
+        let mov = game.board().possible_moves(color).next().unwrap();
+        game.apply_action(Action::Move(mov), color).unwrap();
+    }
+
+    game
+}
+
+/// Moves every timestamp of `game` back by `elapsed`, as if that much time had passed since the
+/// last move, without having to wait for it.
+fn let_time_pass(game: &mut Game, elapsed: Duration) {
+    for (_, timestamp) in &mut game.moves {
+        *timestamp = timestamp.checked_sub(elapsed).unwrap();
+    }
+}
+
+#[test]
+fn check_timeout_ends_the_game_for_the_player_to_move() {
+    let base_time = Duration::from_secs(60);
+    let mut game = start_clocks(Board::default(), base_time);
+    assert_eq!(game.check_timeout(), None);
+    assert!(!game.is_finished());
+
+    let_time_pass(&mut game, base_time * 2);
+    assert!(matches!(
+        game.check_timeout(),
+        Some(GameResult::Win {
+            winner: Color::Black,
+            reason: WinReason::Timeout { .. }
+        })
+    ));
+    assert!(game.is_finished());
+    assert_eq!(game.outcome(), game.result().map(Outcome::from));
+
+    // Once it is recorded, the timeout is not reported again.
+    assert_eq!(game.check_timeout(), None);
+}
+
+#[test]
+fn timeout_against_insufficient_material_is_a_draw() {
+    let mut board = Board::new_minimal(vec2!(0, 0), vec2!(10, 10)).unwrap();
+    board
+        .get_mut(vec2!(2, 4), Color::White)
+        .replace(Piece::Queen);
+    board
+        .get_mut(vec2!(8, 6), Color::Black)
+        .replace(Piece::Knight);
+
+    let base_time = Duration::from_secs(60);
+    let mut game = start_clocks(board, base_time);
+    let_time_pass(&mut game, base_time * 2);
+
+    // White ran out of time, but black only has a knight, which can't checkmate.
+    assert!(matches!(
+        game.check_timeout(),
+        Some(GameResult::Draw {
+            reason: DrawReason::TimeoutVsInsufficientMaterial { .. }
+        })
+    ));
+    assert_eq!(
+        game.outcome().unwrap().to_string(),
+        "Draw by timeout against insufficient material"
+    );
+}
+
+#[test]
+fn when_both_clocks_are_exhausted_the_first_flag_to_fall_loses() {
+    let base_time = Duration::from_secs(60);
+    let mut game = start_clocks(Board::default(), base_time);
+
+    // White moves after running out of time, and then black runs out of time too.
+    let_time_pass(&mut game, base_time * 2);
+    let mov = game.board().possible_moves(Color::White).next().unwrap();
+    game.apply_action(Action::Move(mov), Color::White).unwrap();
+    let_time_pass(&mut game, base_time * 2);
+    assert!(game.time_remaining(Color::White).is_zero());
+    assert!(game.time_remaining(Color::Black).is_zero());
+
+    let Some(GameResult::Win {
+        winner: Color::Black,
+        reason: WinReason::Timeout { timestamp },
+    }) = game.check_timeout()
+    else {
+        panic!("Expected white to lose on time, got {:?}", game.result());
+    };
+
+    let (_, start) = game.moves_from(Color::Black).next().unwrap();
+    assert_eq!(timestamp.duration_since(*start).unsigned_abs(), base_time);
+}
+
+#[test]
+fn checkmated_starting_position_is_finished() {
+    let mut board = Board::new_minimal(vec2!(10, 10), vec2!(0, 0)).unwrap();
+    board
+        .get_mut(vec2!(8, 8), Color::Black)
+        .replace(Piece::Queen);
+
+    let mut game = Game::from_position(board, TimeControl::rapid()).unwrap();
+    assert!(game.is_finished());
+    assert!(matches!(
+        game.result(),
+        Some(GameResult::Win {
+            winner: Color::Black,
+            reason: WinReason::Checkmate
+        })
+    ));
+    assert!(game.legal_actions().is_empty());
+
+    let mov = Move::Regular {
+        origin: vec2!(10, 10),
+        destination: vec2!(9, 9),
+        captures: false,
+    };
+    assert!(matches!(
+        game.apply_action(Action::Move(mov), Color::White),
+        Err(ApplyActionError::GameIsFinished)
+    ));
+}
+
+#[test]
+fn invalid_starting_position_is_rejected() {
+    let mut board = Board::new_minimal(vec2!(0, 0), vec2!(10, 10)).unwrap();
+    board.get_mut(vec2!(10, 10), Color::Black).take();
+
+    assert!(matches!(
+        Game::from_position(board, TimeControl::rapid()),
+        Err(InvalidBoardError::WrongNumberOfKings {
+            color: Color::Black,
+            count: 0
+        })
+    ));
+}
+
+#[test]
+fn starting_positions_are_validated_with_white_to_move() {
+    // After a white move, the board itself thinks that it's black's turn, but games always start
+    // with white to move, so black can't be in check.
+    let mut board = Board::new_minimal(vec2!(0, 0), vec2!(10, 10)).unwrap();
+    board
+        .get_mut(vec2!(10, 5), Color::White)
+        .replace(Piece::Rook);
+    let (check, _) = board
+        .get_move(vec2!(10, 5), vec2!(10, 8), Color::White)
+        .unwrap();
+    board.apply_move(check, Color::White).unwrap();
+    assert_eq!(board.turn(), Color::Black);
+
+    assert!(matches!(
+        Game::from_position(board, TimeControl::rapid()),
+        Err(InvalidBoardError::WaitingPlayerInCheck {
+            color: Color::Black
+        })
+    ));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn game_in_progress_round_trips_through_serde() {
+    let time_control = TimeControl::new_asymetric(
+        [Duration::from_secs(300), Duration::from_secs(180)],
+        [Duration::from_secs(2), Duration::ZERO],
+    );
+    let mut game = Game::new(time_control);
+    for _ in 0..3 {
+        for color in [Color::White, Color::Black] {
+            let mov = game.board().possible_moves(color).next().unwrap();
+            game.apply_action(Action::Move(mov), color).unwrap();
+            std::thread::sleep(Duration::from_millis(2));
+        }
+    }
+    game.apply_action(Action::OfferDraw, Color::White).unwrap();
+
+    let json = serde_json::to_string(&game).unwrap();
+    let deserialized: Game = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized, game);
+    assert_eq!(deserialized.time_control(), time_control);
+    assert_eq!(deserialized.draw_offer(), Some(Color::White));
+    assert_eq!(deserialized.moves_from(Color::Black).count(), 3);
+    assert_eq!(deserialized.move_duration(2), game.move_duration(2));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn finished_game_round_trips_through_serde() {
+    let mut game = Game::new(TimeControl::rapid());
+    let mov = game.board().possible_moves(Color::White).next().unwrap();
+    game.apply_action(Action::Move(mov), Color::White).unwrap();
+    game.apply_action(Action::Resign, Color::Black).unwrap();
+
+    let json = serde_json::to_string(&game).unwrap();
+    let deserialized: Game = serde_json::from_str(&json).unwrap();
+    assert_eq!(deserialized, game);
+    assert_eq!(deserialized.outcome(), game.outcome());
+}
+
+#[test]
+fn time_remaining_at_reconstructs_the_clocks_of_each_ply() {
+    let base_time = Duration::from_secs(60);
+    let increment = Duration::from_secs(1);
+    let mut game = Game::new(TimeControl::new(base_time, increment));
+    for _ in 0..6 {
+        std::thread::sleep(Duration::from_millis(2));
+        let color = game.turn();
+        let mov = game.board().possible_moves(color).next().unwrap();
+        game.apply_action(Action::Move(mov), color).unwrap();
+    }
+    // Finishing the game stops the clocks, so that the snapshots don't change while comparing.
+    std::thread::sleep(Duration::from_millis(2));
+    game.resign(Color::White);
+
+    for color in [Color::White, Color::Black] {
+        assert_eq!(game.time_remaining_at(color, 0), base_time);
+        assert_eq!(
+            game.time_remaining_at(color, game.moves().len()),
+            game.time_remaining(color)
+        );
+    }
+
+    // Each clock only changes on the plies of its own color.
+    assert_eq!(
+        game.time_remaining_at(Color::White, 3),
+        game.time_remaining_at(Color::White, 4)
+    );
+    assert_ne!(
+        game.time_remaining_at(Color::White, 4),
+        game.time_remaining_at(Color::White, 5)
+    );
+    assert_eq!(
+        game.time_remaining_at(Color::White, 3),
+        base_time + 2 * increment - game.move_duration(2).unwrap()
+    );
+}
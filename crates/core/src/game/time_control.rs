@@ -78,7 +78,11 @@ impl TimeControl {
 
     /// Some measure of an "average" duration of a game (but not actually statistically average).
     ///
-    /// It is defined as the maximum time for a game that took 40 moves.
+    /// It is defined as the maximum time each player can spend in a game that took 40 moves.
+    /// For asymmetric time controls, it's the average of both players.
+    ///
+    /// This is the same as lichess' "estimated game duration", which is also per player (e.g.,
+    /// 3+2 is 180s + 40 × 2s = 260s).
     pub fn canonical_duration(&self) -> Duration {
         let white = self.base_time[Color::White] + self.increment[Color::White] * 40;
         let black = self.base_time[Color::Black] + self.increment[Color::Black] * 40;
@@ -86,12 +90,21 @@ impl TimeControl {
         (white + black) / 2
     }
 
+    /// Formats the time control as "minutes+seconds" (e.g., `3+2`).
+    ///
+    /// If the players have different times, both are shown, white's first (e.g., `5+0 / 2+0`).
     pub fn formatted(&self) -> String {
+        let format = |color: Color| {
+            format!(
+                "{}+{}",
+                self.base_time[color].as_secs() / 60,
+                self.increment[color].as_secs()
+            )
+        };
+
         match (self.base_time(), self.increment()) {
-            (Some(base_time), Some(increment)) => {
-                format!("{}+{}", base_time.as_secs() / 60, increment.as_secs())
-            }
-            _ => todo!(),
+            (Some(_), Some(_)) => format(Color::White),
+            _ => format!("{} / {}", format(Color::White), format(Color::Black)),
         }
     }
 
@@ -101,7 +114,8 @@ impl TimeControl {
 }
 
 /// Broad categories you can put [`TimeControl`]s in.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Category {
     UltraBullet,
     Bullet,
@@ -113,7 +127,8 @@ pub enum Category {
 impl Category {
     /// Classify a [`TimeControl::canonical_duration`].
     ///
-    /// Follows [lichess' rules](https://lichess.org/faq#time-controls).
+    /// Follows [lichess' rules](https://lichess.org/faq#time-controls), where the boundaries are
+    /// the first second of the next category (e.g., 179s is bullet and 180s is blitz).
     pub fn classify(duration: Duration) -> Self {
         match duration.as_secs() {
             0..30 => Self::UltraBullet,
@@ -136,3 +151,74 @@ impl fmt::Display for Category {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Category, TimeControl};
+
+    #[test]
+    fn common_time_controls() {
+        assert_eq!(TimeControl::mps(0, 0).category(), Category::UltraBullet);
+        assert_eq!(TimeControl::bullet().category(), Category::Bullet);
+        assert_eq!(TimeControl::mps(2, 1).category(), Category::Bullet);
+        assert_eq!(TimeControl::mps(3, 0).category(), Category::Blitz);
+        assert_eq!(TimeControl::blitz().category(), Category::Blitz);
+        assert_eq!(TimeControl::mps(5, 3).category(), Category::Blitz);
+        assert_eq!(TimeControl::mps(10, 0).category(), Category::Rapid);
+        assert_eq!(TimeControl::rapid().category(), Category::Rapid);
+        assert_eq!(TimeControl::mps(15, 10).category(), Category::Rapid);
+        assert_eq!(TimeControl::mps(30, 0).category(), Category::Classical);
+    }
+
+    #[test]
+    fn category_boundaries() {
+        let boundaries = [
+            (30, Category::UltraBullet, Category::Bullet),
+            (180, Category::Bullet, Category::Blitz),
+            (480, Category::Blitz, Category::Rapid),
+            (1500, Category::Rapid, Category::Classical),
+        ];
+
+        for (boundary, below, above) in boundaries {
+            let just_below = Duration::from_millis(boundary * 1000 - 1);
+            assert_eq!(Category::classify(just_below), below, "{boundary}s");
+            assert_eq!(Category::classify(Duration::from_secs(boundary)), above);
+        }
+    }
+
+    #[test]
+    fn asymmetric_time_controls_show_both_times() {
+        assert_eq!(TimeControl::blitz().formatted(), "3+2");
+
+        let handicap = TimeControl::new_asymetric(
+            [Duration::from_secs(5 * 60), Duration::from_secs(2 * 60)],
+            [Duration::ZERO; 2],
+        );
+        assert_eq!(handicap.formatted(), "5+0 / 2+0");
+
+        let increment_only = TimeControl::new_asymetric(
+            [Duration::from_secs(60); 2],
+            [Duration::from_secs(1), Duration::ZERO],
+        );
+        assert_eq!(increment_only.formatted(), "1+1 / 1+0");
+    }
+
+    #[test]
+    fn canonical_duration_is_per_player() {
+        assert_eq!(
+            TimeControl::blitz().canonical_duration(),
+            Duration::from_secs(180 + 40 * 2)
+        );
+
+        let asymmetric = TimeControl::new_asymetric(
+            [Duration::from_secs(60), Duration::from_secs(180)],
+            [Duration::ZERO, Duration::from_secs(1)],
+        );
+        assert_eq!(
+            asymmetric.canonical_duration(),
+            Duration::from_secs((60 + 180 + 40) / 2)
+        );
+    }
+}
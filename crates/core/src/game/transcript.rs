@@ -0,0 +1,208 @@
+//! A plain-text record of every [`Action`] taken in a [`Game`].
+//!
+//! Each line is one action, prefixed by the color of the player that took it, in the order they
+//! were taken. Moves are written as the origin and destination coordinates, followed by the
+//! piece that the pawn promotes to for promotions:
+//!
+//! ```text
+//! white move 0,2 3,3
+//! black move 10,8 7,7
+//! white move 9,9 10,10 queen
+//! white offer-draw
+//! black accept-draw
+//! ```
+//!
+//! Games that don't start from the [default position](crate::Board::default) begin with a
+//! header with the [FEN](crate::Board::to_fen) of the starting position, which games always start
+//! with white to move from:
+//!
+//! ```text
+//! position K10/11/9/8/7/6/5/6/7/8/9/10/11/10/9/8/7/6/5/6/7/8/9/10/k -
+//! white move 0,0 1,1
+//! ```
+//!
+//! Games with [automatic draws](Game::with_automatic_draws) have an `automatic-draws` line in
+//! the header too (after the position, if there is one), since the same actions can end the
+//! game or not depending on it.
+//!
+//! Moves that can't be written as coordinates (en passant, which can't be played yet, and
+//! promotions on files where pawns can't promote, which can only come from deserialized games)
+//! are written as `unsupported-move`, and transcripts with them can't be read back.
+//!
+//! Transcripts don't include timing information.
+
+use std::fmt::Write as _;
+
+use crate::{Board, Color, FenError, InvalidBoardError, Move, Vec2, piece::pawn};
+
+use super::{Action, ApplyActionError, Game, TimeControl};
+
+impl Game {
+    /// Writes the [transcript](self) of the game.
+    pub fn to_transcript(&self) -> String {
+        let mut output = String::new();
+        if self.initial_board != Board::default() {
+            writeln!(output, "position {}", self.initial_board.to_fen()).unwrap();
+        }
+
+        if self.automatic_draws {
+            output.push_str("automatic-draws\n");
+        }
+
+        for &(color, action) in &self.actions {
+            write!(output, "{color} ").unwrap();
+            match action {
+                Action::Move(mov) if has_coordinates(mov, color) => {
+                    write!(output, "move {}", mov.notation(color, Color::White))
+                }
+                Action::Move(_) => write!(output, "unsupported-move"),
+                Action::Resign => write!(output, "resign"),
+                Action::OfferDraw => write!(output, "offer-draw"),
+                Action::RetractDraw => write!(output, "retract-draw"),
+                Action::AcceptDraw => write!(output, "accept-draw"),
+                Action::ClaimDraw => write!(output, "claim-draw"),
+                Action::Abort => write!(output, "abort"),
+            }
+            .unwrap();
+
+            output.push('\n');
+        }
+
+        output
+    }
+
+    /// Reconstructs a game from a [transcript](self), by applying each action in order.
+    ///
+    /// The game starts from the position in the header of the transcript, if it has one, or from
+    /// the default position otherwise, and with [automatic draws](Self::with_automatic_draws) if
+    /// the header says so. The result and the draw offer of the game are the ones
+    /// that the actions lead to.
+    pub fn from_transcript(
+        transcript: &str,
+        time_control: TimeControl,
+    ) -> Result<Self, TranscriptError> {
+        let mut lines = transcript
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line))
+            .filter(|(_, line)| !line.trim().is_empty())
+            .peekable();
+
+        let header = lines.next_if(|(_, line)| line.trim_start().starts_with("position "));
+        let mut game = match header {
+            Some((line_number, line)) => {
+                let fen = line.trim_start().trim_start_matches("position ");
+                let board =
+                    Board::from_fen(fen).map_err(|source| TranscriptError::InvalidPosition {
+                        line_number,
+                        source,
+                    })?;
+
+                Game::from_position(board, time_control)?
+            }
+            None => Game::new(time_control),
+        };
+
+        if lines
+            .next_if(|(_, line)| line.trim() == "automatic-draws")
+            .is_some()
+        {
+            game = game.with_automatic_draws(true);
+        }
+
+        for (line_number, line) in lines {
+            let (color, action) =
+                parse_line(line, &game).ok_or(TranscriptError::InvalidLine { line_number })?;
+
+            game.apply_action(action, color)
+                .map_err(|source| TranscriptError::InvalidAction {
+                    line_number,
+                    source,
+                })?;
+        }
+
+        Ok(game)
+    }
+}
+
+/// Whether `mov`, made by `color`, has an origin and a destination to write in a transcript.
+fn has_coordinates(mov: Move, color: Color) -> bool {
+    match mov {
+        Move::Regular { .. } => true,
+        Move::EnPassant { .. } => false,
+        Move::Promotion { file, captures, .. } => {
+            pawn::promotion_origin(file, captures, color).is_some()
+        }
+    }
+}
+
+/// Parses a line of a transcript. `game` is used to know whether moves are captures.
+fn parse_line(line: &str, game: &Game) -> Option<(Color, Action)> {
+    let mut words = line.split_whitespace();
+    let color = match words.next()? {
+        "white" => Color::White,
+        "black" => Color::Black,
+        _ => return None,
+    };
+
+    let action = match words.next()? {
+        "move" => {
+            let origin = parse_vec2(words.next()?)?;
+            let destination = parse_vec2(words.next()?)?;
+            let captures = game.board().get(destination, color.other()).is_some();
+            match words.next() {
+                Some(name) => Action::Move(Move::Promotion {
+                    file: origin.file(),
+                    captures: captures
+                        .then(|| pawn::capture_side(color, destination - origin))
+                        .flatten(),
+                    promoting_to: pawn::PROMOTION_PIECES
+                        .into_iter()
+                        .find(|piece| piece.name() == name)?,
+                }),
+                None => Action::Move(Move::Regular {
+                    origin,
+                    destination,
+                    captures,
+                }),
+            }
+        }
+        "resign" => Action::Resign,
+        "offer-draw" => Action::OfferDraw,
+        "retract-draw" => Action::RetractDraw,
+        "accept-draw" => Action::AcceptDraw,
+        "claim-draw" => Action::ClaimDraw,
+        "abort" => Action::Abort,
+        _ => return None,
+    };
+
+    words.next().is_none().then_some((color, action))
+}
+
+/// Parses coordinates of the form `x,y`.
+fn parse_vec2(word: &str) -> Option<Vec2> {
+    let (x, y) = word.split_once(',')?;
+    Vec2::new(x.parse().ok()?, y.parse().ok()?)
+}
+
+#[allow(missing_docs)]
+#[derive(Debug, thiserror::Error)]
+pub enum TranscriptError {
+    #[error("Line {line_number} is not a valid action")]
+    InvalidLine { line_number: usize },
+
+    #[error("Action in line {line_number} can't be applied: {source}")]
+    InvalidAction {
+        line_number: usize,
+        source: ApplyActionError,
+    },
+
+    #[error("The position in line {line_number} is not valid FEN: {source}")]
+    InvalidPosition {
+        line_number: usize,
+        source: FenError,
+    },
+
+    #[error("The starting position can't be played from: {0}")]
+    InvalidStartingPosition(#[from] InvalidBoardError),
+}
@@ -1,4 +1,11 @@
 //! Core functionality of chessagon (hexagonal chess).
+//!
+//! Without the `std` feature (enabled by default), the crate is `no_std` (but needs `alloc`), and
+//! only has the rules of movement: everything but [`game`].
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
 
 mod board;
 pub(crate) mod diagrams;
@@ -6,11 +13,18 @@ mod mov;
 mod sides;
 
 pub mod coordinate;
+#[cfg(feature = "std")]
 pub mod game;
 pub mod piece;
+#[cfg(any(test, feature = "svg"))]
+pub mod svg;
 
-pub use board::Board;
+pub use board::{
+    Board, BoardStatus, BoardView, DiagramError, InvalidBoardError, TranspositionKey,
+    fen::FenError, peek::PeekBoard,
+};
 pub use coordinate::{IVec2, Vec2};
+#[cfg(feature = "std")]
 pub use game::Game;
-pub use mov::Move;
+pub use mov::{Move, MoveNotation};
 pub use sides::{Color, Side};
@@ -1,6 +1,10 @@
-use std::fmt;
+use core::fmt;
 
-use crate::{Color, Side, coordinate::Vec2, piece::Piece};
+use crate::{
+    Board, Color, IVec2, Side,
+    coordinate::Vec2,
+    piece::{Piece, pawn},
+};
 
 /// Translations of pieces with optional captures.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
@@ -56,7 +60,12 @@ pub enum Move {
 
 impl fmt::Display for Move {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{} -> {}", self.origin(), self.destination())
+        match self {
+            Move::Promotion {
+                file, promoting_to, ..
+            } => write!(f, "promotion to {promoting_to} on file {file}"),
+            mov => write!(f, "{} -> {}", mov.origin(), mov.destination()),
+        }
     }
 }
 
@@ -82,7 +91,366 @@ impl Move {
     }
 }
 
+impl Move {
+    /// The notation of the move made by `color`, as seen from the side of `perspective`.
+    ///
+    /// The canonical notation (the one used in transcripts) is the one
+    /// from white's perspective, and is the same regardless of who plays the move. Seen from
+    /// black's perspective, every coordinate is [flipped](Vec2::flipped), which is how black
+    /// players expect to read it.
+    pub fn notation(self, color: Color, perspective: Color) -> MoveNotation {
+        MoveNotation {
+            mov: self,
+            color,
+            perspective,
+        }
+    }
+}
+
+/// The notation of a move, as given by [`Move::notation`].
+///
+/// It is formatted as `x,y x,y` (the origin and the destination), followed by the piece for
+/// promotions.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct MoveNotation {
+    mov: Move,
+    color: Color,
+    perspective: Color,
+}
+
+impl fmt::Display for MoveNotation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let origin = self.mov.origin_for(self.color).oriented(self.perspective);
+        let destination = self
+            .mov
+            .destination_for(self.color)
+            .oriented(self.perspective);
+        write!(
+            f,
+            "{},{} {},{}",
+            origin.x(),
+            origin.y(),
+            destination.x(),
+            destination.y()
+        )?;
+
+        match self.mov {
+            Move::Promotion { promoting_to, .. } => write!(f, " {promoting_to}"),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl Move {
+    /// A [`Move::Regular`] of `color` from `origin` to `destination`, where whether it
+    /// [captures](Move::Regular::captures) is inferred from whether there is a piece of the other
+    /// color at `destination`.
+    ///
+    /// The move is not checked for legality (see [`Board::get_move`] for that).
+    pub fn regular(origin: Vec2, destination: Vec2, board: &Board, color: Color) -> Self {
+        Move::Regular {
+            origin,
+            destination,
+            captures: board.get(destination, color.other()).is_some(),
+        }
+    }
+
+    /// Whether the move, made by `color` on `board` (before applying it), can never be undone:
+    /// captures and pawn moves, which reset the [halfmove clock](crate::Game::halfmove_clock).
+    pub fn is_irreversible(self, board: &Board, color: Color) -> bool {
+        match self {
+            Move::Regular {
+                origin, captures, ..
+            } => captures || board.get(origin, color) == Some(Piece::Pawn),
+            Move::EnPassant { .. } | Move::Promotion { .. } => true,
+        }
+    }
+}
+
+impl Move {
+    /// The tile where the piece was at before the move, if it's made by `color`.
+    ///
+    /// Unlike [`Self::origin`], this can resolve promotions, since their tiles depend on the color.
+    pub fn origin_for(self, color: Color) -> Vec2 {
+        match self {
+            Move::Promotion { file, captures, .. } => pawn::promotion_origin(file, captures, color)
+                .expect("Move::Promotion should be on a file that can promote"),
+            mov => mov.origin(),
+        }
+    }
+
+    /// The tile where the piece went to after the move, if it's made by `color`.
+    ///
+    /// Unlike [`Self::destination`], this can resolve promotions, since their tiles depend on the
+    /// color.
+    pub fn destination_for(self, color: Color) -> Vec2 {
+        match self {
+            Move::Promotion { captures, .. } => {
+                let delta = match captures {
+                    None => IVec2::new_unchecked(color.direction(), color.direction()),
+                    Some(side) => pawn::capture_delta(color, side),
+                };
+
+                self.origin_for(color) + delta
+            }
+            mov => mov.destination(),
+        }
+    }
+}
+
+// The kind of move, in the two highest bits of `Move::to_u16`.
+const QUIET_TAG: u16 = 0b00;
+const CAPTURE_TAG: u16 = 0b01;
+const EN_PASSANT_TAG: u16 = 0b10;
+const PROMOTION_TAG: u16 = 0b11;
+
+impl Move {
+    /// Encodes the move in 16 bits, which is much more compact than serde, e.g., for a wire
+    /// protocol. See [`Self::from_u16`] for the inverse.
+    ///
+    /// The two highest bits are the kind of move, and the meaning of the rest depends on it:
+    /// - `00` and `01`: a [`Move::Regular`] that doesn't capture and one that does, respectively,
+    ///   with the [index](Board::index) of the origin in the lowest 7 bits and the index of the
+    ///   destination in the next 7.
+    /// - `10`: a [`Move::EnPassant`], with the file in the lowest 4 bits and the direction in the
+    ///   next one (`0` for the king side and `1` for the queen side).
+    /// - `11`: a [`Move::Promotion`], with the file in the lowest 4 bits, the side it captures
+    ///   towards in the next 2 (`0` if it doesn't capture, `1` for the king side and `2` for the
+    ///   queen side) and the index of the piece in [`pawn::PROMOTION_PIECES`] in the next 2.
+    ///
+    /// Unused bits are zero. There is no castling in hexagonal chess, so it needs no encoding.
+    ///
+    /// Returns [`None`] if the move breaks the invariants of its variant, so that it doesn't fit
+    /// in its bits (i.e., an invalid file or a promotion to a piece that is not one of the
+    /// [`pawn::PROMOTION_PIECES`]). Such moves can still be built from the public fields or
+    /// deserialized, but they can't be played.
+    pub fn to_u16(self) -> Option<u16> {
+        let side_bits = |side: Side| side.choose(0, 1);
+        let file_bits = |file: u8| (file < Board::NUMBER_OF_FILES).then_some(file as u16);
+        Some(match self {
+            Move::Regular {
+                origin,
+                destination,
+                captures,
+            } => {
+                let tag = if captures { CAPTURE_TAG } else { QUIET_TAG };
+                tag << 14 | (Board::index(destination) as u16) << 7 | Board::index(origin) as u16
+            }
+            Move::EnPassant { file, direction } => {
+                EN_PASSANT_TAG << 14 | side_bits(direction) << 4 | file_bits(file)?
+            }
+            Move::Promotion {
+                file,
+                captures,
+                promoting_to,
+            } => {
+                let piece = pawn::PROMOTION_PIECES
+                    .iter()
+                    .position(|&piece| piece == promoting_to)? as u16;
+                let captures = captures.map_or(0, |side| side_bits(side) + 1);
+                PROMOTION_TAG << 14 | piece << 6 | captures << 4 | file_bits(file)?
+            }
+        })
+    }
+
+    /// Decodes a move encoded with [`Self::to_u16`].
+    ///
+    /// Returns [`None`] if `bits` doesn't encode any move, e.g., because a tile index is out of the
+    /// board or an unused bit is set. The move is not checked for legality.
+    pub fn from_u16(bits: u16) -> Option<Self> {
+        let tile = |index: u16| {
+            (index < Board::NUMBER_OF_TILES as u16).then(|| Board::index_to_vec(index as usize))
+        };
+        let file = (bits & 0b1111) as u8;
+        let side = |bits: u16| match bits {
+            0 => Some(Side::King),
+            1 => Some(Side::Queen),
+            _ => None,
+        };
+
+        match bits >> 14 {
+            tag @ (QUIET_TAG | CAPTURE_TAG) => Some(Move::Regular {
+                origin: tile(bits & 0b111_1111)?,
+                destination: tile(bits >> 7 & 0b111_1111)?,
+                captures: tag == CAPTURE_TAG,
+            }),
+            EN_PASSANT_TAG if bits >> 5 & 0b1_1111_1111 == 0 && file < Board::NUMBER_OF_FILES => {
+                Some(Move::EnPassant {
+                    file,
+                    direction: side(bits >> 4 & 1)?,
+                })
+            }
+            PROMOTION_TAG if bits >> 8 & 0b11_1111 == 0 && file < Board::NUMBER_OF_FILES => {
+                Some(Move::Promotion {
+                    file,
+                    captures: match bits >> 4 & 0b11 {
+                        0 => None,
+                        side_bits => Some(side(side_bits - 1)?),
+                    },
+                    promoting_to: pawn::PROMOTION_PIECES[(bits >> 6 & 0b11) as usize],
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct MoveMeta {
     pub color: Color,
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{Board, Color, Side, Vec2, piece::Piece, vec2};
+
+    use super::Move;
+
+    #[test]
+    fn regular_infers_captures_from_the_board() {
+        let mut board = Board::default();
+        // Trade a few pieces so that there are pieces of both colors in the middle.
+        for _ in 0..6 {
+            let color = board.turn();
+            let mov = board.possible_moves(color).last().unwrap();
+            board.apply_move(mov, color).unwrap();
+        }
+
+        for color in [Color::White, Color::Black] {
+            for (origin, _) in board.piece_positions(color) {
+                for destination in Vec2::iter().filter(|&d| board.get(d, color).is_none()) {
+                    let mov = Move::regular(origin, destination, &board, color);
+                    let Move::Regular { captures, .. } = mov else {
+                        panic!("Expected a regular move, got {mov:?}");
+                    };
+
+                    let occupant = board.get(destination, color.other());
+                    assert_eq!(captures, occupant.is_some());
+                    assert_eq!(board.clone().apply_move_unchecked(mov, color), occupant);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn notation_from_blacks_perspective_is_flipped() {
+        let mut board = Board::default();
+        for _ in 0..4 {
+            let color = board.turn();
+            for mov in board.possible_moves(color) {
+                let canonical = mov.notation(color, Color::White).to_string();
+                let flipped = Move::Regular {
+                    origin: mov.origin().flipped(),
+                    destination: mov.destination().flipped(),
+                    captures: false,
+                };
+
+                assert_eq!(
+                    mov.notation(color, Color::Black).to_string(),
+                    flipped.notation(color, Color::White).to_string(),
+                );
+                assert_eq!(
+                    canonical,
+                    format!(
+                        "{},{} {},{}",
+                        mov.origin().x(),
+                        mov.origin().y(),
+                        mov.destination().x(),
+                        mov.destination().y()
+                    )
+                );
+            }
+
+            let mov = board.possible_moves(color).next().unwrap();
+            board.apply_move(mov, color).unwrap();
+        }
+    }
+
+    #[test]
+    fn regular_matches_legal_moves() {
+        let board = Board::default();
+        for mov in board.possible_moves(Color::White) {
+            let inferred = Move::regular(mov.origin(), mov.destination(), &board, Color::White);
+            assert_eq!(inferred, mov);
+        }
+    }
+
+    #[test]
+    fn u16_encoding_round_trips_possible_moves() {
+        // A few positions of a game, where captures happen soon...
+        let mut positions = Vec::new();
+        let mut board = Board::default();
+        for _ in 0..30 {
+            let color = board.turn();
+            positions.push((board.clone(), color));
+            let mov = board.possible_moves(color).last().unwrap();
+            board.apply_move(mov, color).unwrap();
+        }
+
+        // ...and one with promotions, both capturing and not.
+        let mut board = Board::new_minimal(vec2!(10, 10), vec2!(5, 5)).unwrap();
+        board
+            .get_mut(vec2!(1, 3), Color::Black)
+            .replace(Piece::Pawn);
+        board
+            .get_mut(vec2!(0, 3), Color::White)
+            .replace(Piece::Rook);
+        positions.push((board, Color::Black));
+
+        let mut kinds = [false; 3];
+        for (board, color) in positions {
+            for mov in board.possible_moves(color) {
+                assert_eq!(Move::from_u16(mov.to_u16().unwrap()), Some(mov), "{mov:?}");
+                match mov {
+                    Move::Regular { captures, .. } => kinds[captures as usize] = true,
+                    Move::Promotion { .. } => kinds[2] = true,
+                    Move::EnPassant { .. } => {}
+                }
+            }
+        }
+        assert_eq!(kinds, [true; 3], "Every kind of move should be tested");
+
+        // En passant is not generated yet, so it is checked on its own.
+        for file in 0..Board::NUMBER_OF_FILES {
+            for direction in [Side::King, Side::Queen] {
+                let mov = Move::EnPassant { file, direction };
+                assert_eq!(Move::from_u16(mov.to_u16().unwrap()), Some(mov));
+            }
+        }
+    }
+
+    #[test]
+    fn moves_that_break_their_invariants_are_not_encoded() {
+        for promoting_to in [Piece::Pawn, Piece::King] {
+            let mov = Move::Promotion {
+                file: 3,
+                captures: None,
+                promoting_to,
+            };
+            assert_eq!(mov.to_u16(), None, "{mov:?}");
+        }
+
+        let file = Board::NUMBER_OF_FILES;
+        let promotion = Move::Promotion {
+            file,
+            captures: None,
+            promoting_to: Piece::Queen,
+        };
+        let en_passant = Move::EnPassant {
+            file,
+            direction: Side::King,
+        };
+        assert_eq!(promotion.to_u16(), None);
+        assert_eq!(en_passant.to_u16(), None);
+    }
+
+    #[test]
+    fn every_u16_encodes_at_most_one_move() {
+        for bits in 0..=u16::MAX {
+            if let Some(mov) = Move::from_u16(bits) {
+                assert_eq!(mov.to_u16(), Some(bits), "{mov:?}");
+            }
+        }
+    }
+}
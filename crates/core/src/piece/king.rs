@@ -87,3 +87,107 @@ pub const VALID_DELTAS: [IVec2; 12] = [
     ivec2!(-1, -2),
     ivec2!(1, -1),
 ];
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        Board, Color, Vec2,
+        board::InvalidBoardError,
+        diagrams,
+        piece::{MoveError, Piece, king},
+        vec2,
+    };
+
+    use super::VALID_DELTAS;
+
+    /// Whether the king at `king` could move to `position` on an empty board.
+    fn is_king_step(king: Vec2, position: Vec2) -> bool {
+        VALID_DELTAS.contains(&(position - king))
+    }
+
+    #[test]
+    fn fn_moves_from_center_match_diagram() {
+        let board = Board::new_minimal(Vec2::ZERO, vec2!(0, 1)).unwrap();
+        let diagram = diagrams::visualize_tile_property(
+            |dest| {
+                if dest == Vec2::CENTER {
+                    return Piece::King.emoji(Color::White);
+                }
+
+                match king::get_move(Vec2::CENTER, dest, &board, Color::White) {
+                    Ok(_) => 'x',
+                    Err(_) => ' ',
+                }
+            },
+            |x| *x,
+        );
+
+        assert_eq!(diagrams::MOVEMENT_KING.trim_end(), diagram.trim_end())
+    }
+
+    #[test]
+    fn enemy_king_attacks_the_tiles_it_can_step_to() {
+        let black_king = Vec2::CENTER;
+        let board = Board::new_minimal(Vec2::ZERO, black_king).unwrap();
+
+        for position in Vec2::iter().filter(|&position| position != black_king) {
+            assert_eq!(
+                board.is_attacked(position, Color::Black),
+                is_king_step(black_king, position),
+                "{position}"
+            );
+        }
+    }
+
+    #[test]
+    fn king_cannot_step_next_to_the_enemy_king() {
+        let white_king = vec2!(5, 3);
+        let black_king = Vec2::CENTER;
+        let board = Board::new_minimal(white_king, black_king).unwrap();
+
+        let destinations = VALID_DELTAS
+            .iter()
+            .filter_map(|&delta| white_king.checked_add(delta));
+
+        let (mut rejected, mut accepted) = (0, 0);
+        for destination in destinations {
+            let result = Piece::King.get_move(white_king, destination, &board, Color::White);
+            if is_king_step(black_king, destination) {
+                assert!(
+                    matches!(result, Err(MoveError::KingIsUnprotected { .. })),
+                    "{destination}: {result:?}"
+                );
+                rejected += 1;
+            } else {
+                assert!(result.is_ok(), "{destination}: {result:?}");
+                accepted += 1;
+            }
+        }
+
+        assert!(rejected > 0 && accepted > 0);
+
+        for destination in board.king_zone(Color::Black) {
+            assert!(
+                board
+                    .moves_from(white_king, Color::White)
+                    .all(|mov| mov.destination() != destination),
+                "{destination} is next to the black king"
+            );
+        }
+    }
+
+    #[test]
+    fn kings_cannot_be_placed_next_to_each_other() {
+        for delta in VALID_DELTAS {
+            let black_king = Vec2::CENTER + delta;
+            let board = Board::new_minimal(Vec2::CENTER, black_king).unwrap();
+            assert_eq!(
+                board.validate(Color::White),
+                Err(InvalidBoardError::WaitingPlayerInCheck {
+                    color: Color::Black
+                }),
+                "{black_king}"
+            );
+        }
+    }
+}
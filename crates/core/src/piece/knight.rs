@@ -13,7 +13,9 @@
 #![doc = include_str!("../diagrams/movement_knight.txt")]
 //! ```
 
-use crate::{Color, IVec2, board::Board, coordinate::Vec2, mov::Move, piece::movement, vec2};
+use crate::{
+    Color, IVec2, board::Board, coordinate::Vec2, ivec2, mov::Move, piece::movement, vec2,
+};
 
 use super::rook;
 
@@ -42,6 +44,22 @@ pub fn valid_delta(delta: IVec2) -> Result<(), MoveError> {
     Ok(())
 }
 
+/// Every delta for which [`valid_delta`] is `Ok`.
+pub const DELTAS: [IVec2; 12] = [
+    ivec2!(1, 3),
+    ivec2!(2, 3),
+    ivec2!(3, 2),
+    ivec2!(3, 1),
+    ivec2!(2, -1),
+    ivec2!(1, -2),
+    ivec2!(-1, -3),
+    ivec2!(-2, -3),
+    ivec2!(-3, -2),
+    ivec2!(-3, -1),
+    ivec2!(-2, 1),
+    ivec2!(-1, 2),
+];
+
 /// Gets a move from `origin` to `destination` if the movement is knight-like.
 ///
 /// See the [module-level docs](self) for more info about how a knight moves.
@@ -122,4 +140,12 @@ mod tests {
 
         assert_eq!(diagrams::MOVEMENT_KNIGHT.trim_end(), diagram.trim_end())
     }
+
+    #[test]
+    fn deltas_are_exactly_the_valid_deltas() {
+        let valid = IVec2::iter()
+            .filter(|&delta| delta != IVec2::ZERO && knight::valid_delta(delta).is_ok())
+            .collect::<std::collections::HashSet<_>>();
+        assert_eq!(valid, knight::DELTAS.into_iter().collect());
+    }
 }
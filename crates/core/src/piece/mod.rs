@@ -11,13 +11,13 @@ pub mod queen;
 pub mod rook;
 
 use core::fmt;
-use std::ops::Index;
+use core::ops::Index;
 
 use strum::EnumString;
 
 use crate::{
-    Color,
-    board::Board,
+    Color, IVec2,
+    board::{Board, BoardView},
     coordinate::Vec2,
     mov::{Move, MoveMeta},
 };
@@ -40,6 +40,22 @@ pub enum Piece {
     King = 5,
 }
 
+/// A piece on the board, along with its color and where it is.
+///
+/// See [`Board::placed_pieces`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlacedPiece {
+    /// The kind of piece.
+    pub piece: Piece,
+
+    /// The color of the player that owns the piece.
+    pub color: Color,
+
+    /// The tile the piece is on.
+    pub position: Vec2,
+}
+
 impl Piece {
     /// Gets the move from `origin` to `destination`, if it is legal, assuming the given piece is at `origin`.
     ///
@@ -70,21 +86,18 @@ impl Piece {
         let (mov, meta) = self.get_move_no_checks(origin, destination, board, color)?;
 
         assert!(
-            board.get(mov.destination(), color).is_none(),
+            board.get(mov.destination_for(color), color).is_none(),
             "pieces should not capture pieces of their own color",
         );
 
         assert_ne!(
-            board.get(mov.destination(), color.other()),
+            board.get(mov.destination_for(color), color.other()),
             Some(Piece::King),
             "Should not be able to capture the king. ({mov}) {origin} -> {destination}. {:?} {board}",
             board.get_either(origin),
         );
 
-        let mut test_board = board.clone();
-        test_board.apply_move_unchecked(mov, color);
-
-        if let Some(capturing_move) = test_board.in_check(color) {
+        if let Some(capturing_move) = board.peek(mov, color).in_check(color) {
             return Err(MoveError::KingIsUnprotected { capturing_move });
         }
 
@@ -123,6 +136,39 @@ impl Piece {
         Ok((mov, meta))
     }
 
+    /// Whether a piece of this kind at `origin` attacks `target`, i.e., whether it could capture
+    /// an enemy piece if there was one at `target`.
+    ///
+    /// This doesn't take into account what is at `target` (it could be empty, or have a piece of
+    /// either color), nor whether capturing would leave the king in check. It does take into
+    /// account blockers between `origin` and `target`.
+    ///
+    /// Note that for pawns this is different from the tiles it can move to, since pawns only
+    /// attack in their capture directions.
+    pub fn attacks(self, origin: Vec2, target: Vec2, board: &impl BoardView, color: Color) -> bool {
+        if origin == target {
+            return false;
+        }
+
+        let delta = target - origin;
+        let (stride, distance) = movement::get_stride(delta);
+        let slides_to_target = |valid_stride: fn(IVec2) -> bool| {
+            valid_stride(stride)
+                && movement::check_blockers(origin, stride, distance, board).is_ok()
+        };
+
+        match self {
+            Self::Pawn => distance == 1 && pawn::is_capture_stride(stride, color),
+            Self::Knight => knight::valid_delta(delta).is_ok(),
+            Self::Bishop => slides_to_target(bishop::valid_stride),
+            Self::Rook => slides_to_target(rook::valid_stride),
+            Self::Queen => {
+                slides_to_target(bishop::valid_stride) || slides_to_target(rook::valid_stride)
+            }
+            Self::King => king::VALID_DELTAS.contains(&delta),
+        }
+    }
+
     pub fn initial_configuration() -> impl Iterator<Item = (Piece, Vec2, Color)> {
         pawn::initial_configuration()
             .map(|(p, c)| (Piece::Pawn, p, c))
@@ -165,6 +211,53 @@ pub enum MoveError {
 
     #[error("Move leaves king unprotected (could by captured by {capturing_move})")]
     KingIsUnprotected { capturing_move: Move },
+
+    #[error(
+        "The pawn promotes when moving from {origin} to {destination}, so the move has to say which piece it promotes to"
+    )]
+    PromotionRequired { origin: Vec2, destination: Vec2 },
+
+    #[error("{mov} is not a valid promotion")]
+    InvalidPromotion { mov: Move },
+
+    #[error("{mov} says whether it captures, but it doesn't match the piece in the destination")]
+    WrongCaptureFlag { mov: Move },
+}
+
+impl MoveError {
+    /// A single character that summarizes the reason of the error, used in
+    /// [`Board::debug_moves`].
+    ///
+    /// - `p`: there is no piece in the origin.
+    /// - `c`: the piece in the origin is of the other color.
+    /// - `o`: the destination is the origin.
+    /// - `b`: there is a piece in the way or in the destination.
+    /// - `f`: the destination is too far away.
+    /// - `n`: a pawn is trying to capture, but there is nothing to capture.
+    /// - `k`: the move would leave the king in check.
+    /// - `.`: the piece doesn't move like that.
+    #[cfg(any(test, feature = "debug-diagrams"))]
+    pub fn code(&self) -> char {
+        match self {
+            Self::PieceNotPresent { .. } => 'p',
+            Self::NotYourPiece { .. } => 'c',
+            Self::NullMovement => 'o',
+            Self::KingIsUnprotected { .. } => 'k',
+            Self::Pawn(pawn::MoveError::Blocked(_))
+            | Self::Bishop(bishop::MoveError::Blocked(_))
+            | Self::Knight(knight::MoveError::Blocked(_))
+            | Self::Rook(rook::MoveError::Blocked(_))
+            | Self::King(king::MoveError::Blocked(_)) => 'b',
+            Self::Queen(err) if err.is_blocked() => 'b',
+            Self::Pawn(
+                pawn::MoveError::TooFarAway { .. } | pawn::MoveError::CaptureTooFarAway { .. },
+            )
+            | Self::Knight(knight::MoveError::TooFarAway { .. })
+            | Self::King(king::MoveError::TooFarAway { .. }) => 'f',
+            Self::Pawn(pawn::MoveError::NoPieceToCapture { .. }) => 'n',
+            _ => '.',
+        }
+    }
 }
 
 impl Piece {
@@ -212,6 +305,45 @@ impl Piece {
         })
     }
 
+    /// The value of the piece in centipawns (hundredths of a pawn), for engines.
+    ///
+    /// Unlike [`Self::value`], which is meant to be shown to players, this is finer grained and
+    /// the king has a finite value, larger than all the other pieces together, so that it can be
+    /// used in computations such as exchange evaluation.
+    #[must_use]
+    pub const fn centipawns(self) -> i32 {
+        match self {
+            Piece::Pawn => 100,
+            Piece::Knight | Piece::Bishop => 300,
+            Piece::Rook => 500,
+            Piece::Queen => 900,
+            Piece::King => 20_000,
+        }
+    }
+
+    /// The inverse of [`Self::emoji`], but it also accepts the letter of each piece (uppercase
+    /// for white and lowercase for black).
+    #[must_use]
+    pub fn from_char(c: char) -> Option<(Self, Color)> {
+        let color = if c.is_lowercase() || "♙♘♗♖♕♔".contains(c) {
+            Color::Black
+        } else {
+            Color::White
+        };
+
+        let piece = match c.to_ascii_uppercase() {
+            'P' | '♟' | '♙' => Piece::Pawn,
+            'N' | '♞' | '♘' => Piece::Knight,
+            'B' | '♝' | '♗' => Piece::Bishop,
+            'R' | '♜' | '♖' => Piece::Rook,
+            'Q' | '♛' | '♕' => Piece::Queen,
+            'K' | '♚' | '♔' => Piece::King,
+            _ => return None,
+        };
+
+        Some((piece, color))
+    }
+
     #[must_use]
     pub fn emoji(self, color: Color) -> char {
         color.choose(
@@ -248,3 +380,43 @@ impl<T> Index<Piece> for [T; 6] {
         &self[index as usize]
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Piece;
+    use crate::{Board, Color};
+
+    const PIECES: [Piece; 6] = [
+        Piece::Pawn,
+        Piece::Knight,
+        Piece::Bishop,
+        Piece::Rook,
+        Piece::Queen,
+        Piece::King,
+    ];
+
+    #[test]
+    fn centipawns_are_ordered_like_values() {
+        for a in PIECES {
+            for b in PIECES {
+                // The king is invaluable, so it goes above every other piece (unlike how `Option`
+                // orders `None`).
+                let by_value = match (a.value(), b.value()) {
+                    (None, None) => core::cmp::Ordering::Equal,
+                    (None, Some(_)) => core::cmp::Ordering::Greater,
+                    (Some(_), None) => core::cmp::Ordering::Less,
+                    (Some(a), Some(b)) => a.cmp(&b),
+                };
+
+                assert_eq!(a.centipawns().cmp(&b.centipawns()), by_value, "{a} vs {b}");
+            }
+        }
+
+        let material = Board::default()
+            .pieces(Color::White)
+            .filter(|&piece| piece != Piece::King)
+            .map(Piece::centipawns)
+            .sum::<i32>();
+        assert!(Piece::King.centipawns() > material);
+    }
+}
@@ -1,6 +1,10 @@
 //! General piece movement logic.
 
-use crate::{Color, IVec2, board::Board, coordinate::Vec2};
+use crate::{
+    Color, IVec2,
+    board::{Board, BoardView},
+    coordinate::Vec2,
+};
 use gcd::Gcd;
 
 use super::Piece;
@@ -9,15 +13,21 @@ use super::Piece;
 ///
 /// In other words, `stride * stride_length(stride, delta) == delta`
 pub fn stride_length(stride: IVec2, delta: IVec2) -> Result<i8, StrideLengthError> {
-    let (sx, sy) = (stride.x(), stride.y());
-    let (dx, dy) = (delta.x(), delta.y());
+    // The length along one axis, or `Some(None)` if any length works because both components
+    // are zero (as in rook-like strides).
+    let axis_length = |s: i8, d: i8| match s {
+        0 => (d == 0).then_some(None),
+        _ => (d % s == 0).then_some(Some(d / s)),
+    };
 
-    let divides_evenly = (dx % sx == 0) && (dy % sy == 0) && (dx / sx == dy / sy);
-    if !divides_evenly {
-        return Err(StrideLengthError { stride, delta });
+    match (
+        axis_length(stride.x(), delta.x()),
+        axis_length(stride.y(), delta.y()),
+    ) {
+        (Some(Some(x)), Some(Some(y))) if x == y => Ok(x),
+        (Some(Some(length)), Some(None)) | (Some(None), Some(Some(length))) => Ok(length),
+        _ => Err(StrideLengthError { stride, delta }),
     }
-
-    Ok(dx / sx)
 }
 
 #[derive(Debug, thiserror::Error)]
@@ -95,7 +105,7 @@ pub fn check_color_blocker(
 /// through [`check_blockers`].
 ///
 /// See also [`check_color_blocker`].
-pub fn check_any_blocker(position: Vec2, board: &Board) -> Result<(), BlockerError> {
+pub fn check_any_blocker(position: Vec2, board: &impl BoardView) -> Result<(), BlockerError> {
     if let Some((piece, color)) = board.get_either(position) {
         return Err(BlockerError {
             position,
@@ -116,9 +126,11 @@ pub fn check_blockers(
     origin: Vec2,
     stride: IVec2,
     distance: u8,
-    board: &Board,
+    board: &impl BoardView,
 ) -> Result<(), BlockerError> {
     for i in 1..distance {
+        // This is hot in move generation, and the tiles are between `origin` and the destination,
+        // so they are on the board as long as the destination is (see [`Vec2::between`]).
         let position = origin + stride * i as i8;
         check_any_blocker(position, board)?;
     }
@@ -133,3 +145,45 @@ pub struct BlockerError {
     pub piece: Piece,
     pub color: Color,
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{IVec2, ivec2};
+
+    use super::{get_stride, stride_length};
+
+    #[test]
+    fn strides_with_zero_components() {
+        for n in 1..=5 {
+            for (delta, stride) in [
+                (ivec2!(0, 1) * n, ivec2!(0, 1)),
+                (ivec2!(0, -1) * n, ivec2!(0, -1)),
+                (ivec2!(1, 0) * n, ivec2!(1, 0)),
+                (ivec2!(-1, 0) * n, ivec2!(-1, 0)),
+                (ivec2!(1, 1) * n, ivec2!(1, 1)),
+                (ivec2!(-1, -1) * n, ivec2!(-1, -1)),
+            ] {
+                assert_eq!(get_stride(delta), (stride, n as u8), "{delta}");
+                assert_eq!(stride_length(stride, delta).unwrap(), n, "{delta}");
+            }
+        }
+    }
+
+    #[test]
+    fn every_delta_is_a_whole_number_of_strides() {
+        for delta in IVec2::iter().filter(|&delta| delta != IVec2::ZERO) {
+            let (stride, length) = get_stride(delta);
+            assert_eq!(stride * length as i8, delta);
+            assert_eq!(get_stride(stride), (stride, 1), "{delta}");
+            assert_eq!(stride_length(stride, delta).unwrap(), length as i8);
+        }
+    }
+
+    #[test]
+    fn stride_length_rejects_deltas_off_the_line() {
+        assert!(stride_length(ivec2!(0, 1), ivec2!(1, 3)).is_err());
+        assert!(stride_length(ivec2!(1, 0), ivec2!(2, -1)).is_err());
+        assert!(stride_length(ivec2!(1, 1), ivec2!(2, 3)).is_err());
+        assert!(stride_length(IVec2::ZERO, IVec2::ZERO).is_err());
+    }
+}
@@ -1,4 +1,11 @@
-use crate::{Color, IVec2, board::Board, coordinate::Vec2, mov::Move, piece::movement, vec2};
+use crate::{
+    Color, IVec2, Side,
+    board::Board,
+    coordinate::Vec2,
+    mov::Move,
+    piece::{Piece, movement},
+    vec2,
+};
 
 // /// Gets the stride of a pawn given the color and the optional direction of the capture.
 // pub const fn stride(color: Color, capture_direction: Option<Side>) -> IVec2 {
@@ -17,10 +24,79 @@ pub const fn is_capture_stride(stride: IVec2, color: Color) -> bool {
         || (stride.x() == color.direction() && stride.y() == 0)
 }
 
+/// The side towards which a pawn of the given color captures when moving by `delta`.
+///
+/// The queen's side is the one where the queen starts, which is `+x` for white and `-y` for
+/// black, and the king's side is the other one.
+///
+/// Returns [`None`] if `delta` is not a capture of a single tile. See also [`capture_delta`],
+/// which is the inverse.
+pub const fn capture_side(color: Color, delta: IVec2) -> Option<Side> {
+    let d = color.direction();
+    match (color, delta.x(), delta.y()) {
+        (Color::White, x, 0) | (Color::Black, 0, x) if x == d => Some(Side::Queen),
+        (Color::White, 0, y) | (Color::Black, y, 0) if y == d => Some(Side::King),
+        _ => None,
+    }
+}
+
+/// The delta of a capture of a pawn of the given color towards `side`.
+///
+/// This is the inverse of [`capture_side`].
+pub const fn capture_delta(color: Color, side: Side) -> IVec2 {
+    let d = color.direction();
+    match (color, side) {
+        (Color::White, Side::Queen) | (Color::Black, Side::King) => IVec2::new_unchecked(d, 0),
+        (Color::White, Side::King) | (Color::Black, Side::Queen) => IVec2::new_unchecked(0, d),
+    }
+}
+
+/// Whether a pawn of the given color promotes when reaching `position`.
+///
+/// These are the tiles on the two edges of the board that are furthest away from the color.
+pub const fn is_promotion_tile(position: Vec2, color: Color) -> bool {
+    let edge = color.choose(Vec2::MAX, 0);
+    position.x() == edge || position.y() == edge
+}
+
+/// The pieces that a pawn can promote to, from most to least valuable.
+pub const PROMOTION_PIECES: [Piece; 4] = [Piece::Queen, Piece::Rook, Piece::Bishop, Piece::Knight];
+
+/// Whether a pawn of the given color moving from `origin` to `destination` promotes.
+///
+/// Pawns that are already on a promotion tile (which can only happen in set up positions) don't
+/// promote again, since [`Move::Promotion`] can't describe their moves.
+pub const fn is_promotion(origin: Vec2, destination: Vec2, color: Color) -> bool {
+    is_promotion_tile(destination, color) && !is_promotion_tile(origin, color)
+}
+
+/// The tile from which a pawn of the given color on `file` promotes, either by moving forward or
+/// by capturing towards the given side.
+///
+/// Returns [`None`] if there is no such tile (e.g., queen-side captures on the king's side of the
+/// board don't reach the promotion tiles).
+pub fn promotion_origin(file: u8, captures: Option<Side>, color: Color) -> Option<Vec2> {
+    let delta = match captures {
+        None => IVec2::new_unchecked(color.direction(), color.direction()),
+        Some(side) => capture_delta(color, side),
+    };
+
+    Vec2::iter()
+        .filter(|position| position.file() == file && !is_promotion_tile(*position, color))
+        .find(|&position| {
+            position
+                .checked_add(delta)
+                .is_some_and(|destination| is_promotion_tile(destination, color))
+        })
+}
+
 /// Gets a move from `origin` to `destination` if the movement is pawn-like.
 ///
 /// See the [module-level docs](self) for more info about how a pawn moves.
 ///
+/// Moves onto a [promotion tile](is_promotion_tile) are returned as a [`Move::Promotion`] to a
+/// queen, the other [promotion pieces](PROMOTION_PIECES) are just as legal.
+///
 /// See [`Piece::get_move`](super::Piece::get_move) for more details about pre and postconditions.
 pub fn get_move(
     origin: Vec2,
@@ -29,6 +105,16 @@ pub fn get_move(
     color: Color,
 ) -> Result<Move, MoveError> {
     debug_assert_ne!(origin, destination);
+
+    // Destinations computed by stepping from a tile (e.g., `origin + capture_delta(..)` on the
+    // edge of the board) wrap around instead of failing, and indexing the board with them would
+    // read the wrong tile.
+    if !Vec2::is_valid(destination.x(), destination.y()) {
+        return Err(MoveError::OffBoard {
+            position: destination,
+        });
+    }
+
     let delta = destination - origin;
     let (stride, distance) = movement::get_stride(delta);
 
@@ -62,6 +148,18 @@ pub fn get_move(
         return Err(MoveError::InvalidMovementDirection { delta });
     };
 
+    if is_promotion(origin, destination, color) {
+        return Ok(Move::Promotion {
+            file: origin.file(),
+            captures: if captures {
+                capture_side(color, delta)
+            } else {
+                None
+            },
+            promoting_to: Piece::Queen,
+        });
+    }
+
     Ok(Move::Regular {
         origin,
         destination,
@@ -72,6 +170,9 @@ pub fn get_move(
 #[allow(missing_docs)]
 #[derive(Debug, thiserror::Error)]
 pub enum MoveError {
+    #[error("Target {position} is outside of the board")]
+    OffBoard { position: Vec2 },
+
     #[error(
         "Target is {distance} tiles away, but can only move {max_distance} tiles from this position"
     )]
@@ -200,6 +301,171 @@ mod tests {
         }
     }
 
+    #[test]
+    fn double_step_cannot_jump_over_the_middle_tile() {
+        use crate::piece::{MoveError as PieceMoveError, Piece};
+
+        for (color, origin, middle, destination) in [
+            (Color::White, vec2!(4, 2), vec2!(5, 3), vec2!(6, 4)),
+            (Color::Black, vec2!(6, 8), vec2!(5, 7), vec2!(4, 6)),
+        ] {
+            for blocker_color in [color, color.other()] {
+                let mut board = Board::default();
+                board.get_mut(middle, blocker_color).replace(Piece::Knight);
+
+                assert!(matches!(
+                    board.get_move(origin, destination, color),
+                    Err(PieceMoveError::Pawn(MoveError::Blocked(_)))
+                ));
+                assert!(
+                    board
+                        .possible_moves(color)
+                        .all(|mov| mov.origin() != origin || mov.destination() != destination)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn capture_side_and_delta_are_inverses() {
+        for color in [Color::White, Color::Black] {
+            for side in [Side::King, Side::Queen] {
+                let delta = capture_delta(color, side);
+                assert!(is_capture_stride(delta, color));
+                assert_eq!(capture_side(color, delta), Some(side));
+            }
+
+            let forward = IVec2::new_unchecked(color.direction(), color.direction());
+            assert_eq!(capture_side(color, forward), None);
+        }
+    }
+
+    #[test]
+    fn promotion_captures_map_to_sides_and_tiles() {
+        for (color, file, side, origin, destination) in [
+            (Color::White, 7, Side::King, vec2!(7, 9), vec2!(7, 10)),
+            (Color::White, 3, Side::Queen, vec2!(9, 7), vec2!(10, 7)),
+            (Color::Black, 7, Side::King, vec2!(1, 3), vec2!(0, 3)),
+            (Color::Black, 3, Side::Queen, vec2!(3, 1), vec2!(3, 0)),
+        ] {
+            assert_eq!(promotion_origin(file, Some(side), color), Some(origin));
+            assert_eq!(capture_side(color, destination - origin), Some(side));
+            assert!(is_promotion_tile(destination, color));
+
+            let mov = Move::Promotion {
+                file,
+                captures: Some(side),
+                promoting_to: crate::piece::Piece::Queen,
+            };
+            assert_eq!(mov.origin_for(color), origin);
+            assert_eq!(mov.destination_for(color), destination);
+        }
+    }
+
+    #[test]
+    fn edge_pawn_cannot_capture_off_the_board() {
+        use crate::piece::{MoveError as PieceMoveError, Piece};
+
+        for (color, origin, side) in [
+            (Color::White, vec2!(9, 4), Side::Queen),
+            (Color::Black, vec2!(1, 6), Side::King),
+        ] {
+            let mut board = Board::new_minimal(vec2!(0, 0), vec2!(10, 10)).unwrap();
+            board.get_mut(origin, color).replace(Piece::Pawn);
+
+            let destination = origin + capture_delta(color, side);
+            assert!(!Vec2::is_valid(destination.x(), destination.y()));
+            assert!(matches!(
+                board.get_move(origin, destination, color),
+                Err(PieceMoveError::Pawn(MoveError::OffBoard { position })) if position == destination
+            ));
+        }
+    }
+
+    #[test]
+    fn queen_side_captures_cannot_promote_past_the_center_file() {
+        assert_eq!(promotion_origin(7, Some(Side::Queen), Color::White), None);
+        assert_eq!(promotion_origin(7, Some(Side::Queen), Color::Black), None);
+    }
+
+    #[test]
+    fn fn_moves_from_several_tiles_match_diagram() {
+        use crate::{diagrams, piece::Piece};
+
+        // A pawn on its initial tile, one that is blocked and one on the edge of the board, with
+        // enemies to capture next to each of them.
+        let pawns = [vec2!(4, 2), vec2!(7, 5), vec2!(9, 4)];
+        let enemies = [vec2!(5, 2), vec2!(8, 6), vec2!(8, 5), vec2!(9, 5)];
+
+        let mut board = Board::empty();
+        for pawn in pawns {
+            board.get_mut(pawn, Color::White).replace(Piece::Pawn);
+        }
+        for enemy in enemies {
+            board.get_mut(enemy, Color::Black).replace(Piece::Knight);
+        }
+
+        let diagram = diagrams::visualize_tile_property(
+            |dest| {
+                let reachable = pawns.iter().any(|&origin| {
+                    origin != dest && get_move(origin, dest, &board, Color::White).is_ok()
+                });
+
+                match board.get_either(dest) {
+                    _ if reachable => 'x',
+                    Some((piece, color)) => piece.emoji(color),
+                    None => ' ',
+                }
+            },
+            |x| *x,
+        );
+
+        assert_eq!(diagrams::MOVEMENT_PAWN.trim_end(), diagram.trim_end())
+    }
+
+    /// Checks every origin and destination against the definition of pawn movement: a step
+    /// forward (or two, from the initial tiles) to an empty tile, or a capture on either side.
+    #[test]
+    fn moves_from_every_tile_match_the_deltas() {
+        use crate::piece::Piece;
+
+        for color in [Color::White, Color::Black] {
+            let forward = IVec2::new_unchecked(color.direction(), color.direction());
+            let captures = [Side::Queen, Side::King].map(|side| capture_delta(color, side));
+
+            for origin in Vec2::iter() {
+                let empty = Board::empty();
+                let mut full = Board::empty();
+                for position in Vec2::iter().filter(|&position| position != origin) {
+                    full.get_mut(position, color.other()).replace(Piece::Knight);
+                }
+
+                let mut steps = vec![origin.checked_add(forward)];
+                if is_intial_tile(origin, color) {
+                    steps.push(steps[0].and_then(|middle| middle.checked_add(forward)));
+                }
+
+                for destination in Vec2::iter().filter(|&destination| destination != origin) {
+                    let expected_step = steps.contains(&Some(destination));
+                    let expected_capture = captures
+                        .iter()
+                        .any(|&delta| origin.checked_add(delta) == Some(destination));
+
+                    assert_eq!(
+                        get_move(origin, destination, &empty, color).is_ok(),
+                        expected_step,
+                        "{color} {origin} -> {destination} on an empty board"
+                    );
+                    assert_eq!(
+                        get_move(origin, destination, &full, color).is_ok(),
+                        expected_capture,
+                        "{color} {origin} -> {destination} on a full board"
+                    );
+                }
+            }
+        }
+    }
+
     // #[test]
     // fn fn_stride_returns_correct_result_for_each_possible_value() {
     //     for (color, captures, [x, y]) in [
@@ -36,6 +36,44 @@ pub struct MoveError {
     bishop_err: bishop::MoveError,
 }
 
+impl MoveError {
+    /// Whether the queen could have moved there if there wasn't a piece in the way.
+    pub fn is_blocked(&self) -> bool {
+        matches!(self.rook_err, rook::MoveError::Blocked(_))
+            || matches!(self.bishop_err, bishop::MoveError::Blocked(_))
+    }
+}
+
 pub fn initial_configuration() -> impl Iterator<Item = (Vec2, Color)> {
     [(vec2!(1, 0), Color::White), (vec2!(10, 9), Color::Black)].into_iter()
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{diagrams, piece::queen};
+
+    use super::*;
+
+    #[test]
+    fn fn_moves_from_center_match_diagram() {
+        let mut board = Board::new_minimal(Vec2::ZERO, vec2!(0, 1)).unwrap();
+        board
+            .get_mut(Vec2::CENTER, Color::White)
+            .replace(Piece::Queen);
+        let diagram = diagrams::visualize_tile_property(
+            |dest| {
+                if dest == Vec2::CENTER {
+                    return Piece::Queen.emoji(Color::White);
+                }
+
+                match queen::get_move(Vec2::CENTER, dest, &board, Color::White) {
+                    Ok(_) => 'x',
+                    Err(_) => ' ',
+                }
+            },
+            |x| *x,
+        );
+
+        assert_eq!(diagrams::MOVEMENT_QUEEN.trim_end(), diagram.trim_end())
+    }
+}
@@ -73,3 +73,34 @@ pub fn initial_configuration() -> impl Iterator<Item = (Vec2, Color)> {
     ]
     .into_iter()
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        diagrams,
+        piece::{Piece, rook},
+        vec2,
+    };
+
+    use super::*;
+
+    #[test]
+    fn fn_moves_from_center_match_diagram() {
+        let board = Board::new_minimal(Vec2::ZERO, vec2!(0, 1)).unwrap();
+        let diagram = diagrams::visualize_tile_property(
+            |dest| {
+                if dest == Vec2::CENTER {
+                    return Piece::Rook.emoji(Color::White);
+                }
+
+                match rook::get_move(Vec2::CENTER, dest, &board, Color::White) {
+                    Ok(_) => 'x',
+                    Err(_) => ' ',
+                }
+            },
+            |x| *x,
+        );
+
+        assert_eq!(diagrams::MOVEMENT_ROOK.trim_end(), diagram.trim_end())
+    }
+}
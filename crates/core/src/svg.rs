@@ -0,0 +1,184 @@
+//! Rendering of boards to standalone SVGs, for sharing positions.
+//!
+//! The geometry is the same as the one the UI uses: the board fits in a square, with white's
+//! first rank at the bottom (unless the board is [flipped](SvgOptions::orientation)) and flat-top
+//! hexagons.
+
+use std::fmt::Write as _;
+
+use crate::{Board, Color, Vec2};
+
+/// Options for [`Board::to_svg`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SvgOptions {
+    /// The color whose side of the board is drawn at the bottom.
+    pub orientation: Color,
+
+    /// Tiles that are drawn with [`SvgOptions::highlight_color`].
+    pub highlighted: Vec<Vec2>,
+
+    /// Whether to write the coordinates of each tile on it.
+    pub coordinates: bool,
+
+    /// The width and height of the SVG, in pixels.
+    pub size: u32,
+
+    /// The colors of the tiles, chosen as in the UI by `(x + y) % 3`.
+    pub tile_colors: [&'static str; 3],
+
+    /// The color of the highlighted tiles.
+    pub highlight_color: &'static str,
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        Self {
+            orientation: Color::White,
+            highlighted: Vec::new(),
+            coordinates: false,
+            size: 600,
+            tile_colors: ["#5a3d8c", "#7450a8", "#8f66c7"],
+            highlight_color: "#a3306b",
+        }
+    }
+}
+
+/// The apothem of a hexagon, where the board is a unit square.
+const APOTHEM: f64 = 1.0 / (2.0 * Board::NUMBER_OF_RANKS as f64);
+
+/// The distance from the center of a hexagon to its vertices.
+const CIRCUMRADIUS: f64 = APOTHEM * 2.0 / 1.732_050_807_568_877_2;
+
+/// The center of a hexagon, where the board is a unit square.
+///
+/// Mirrors `hex_to_uv` in the UI.
+fn center(position: Vec2, orientation: Color) -> (f64, f64) {
+    let sqrt_3 = 3f64.sqrt();
+    let (x, y) = (position.x() as f64, position.y() as f64);
+
+    let u = 0.5 + (y - x) * sqrt_3 * APOTHEM;
+    let v = 1.0 - APOTHEM - (x + y) * APOTHEM;
+
+    match orientation {
+        Color::White => (u, v),
+        Color::Black => (1.0 - u, 1.0 - v),
+    }
+}
+
+impl Board {
+    /// Renders the board as a standalone SVG.
+    pub fn to_svg(&self, options: SvgOptions) -> String {
+        let size = options.size as f64;
+        let scale = |(u, v): (f64, f64)| (u * size, v * size);
+
+        let mut svg = String::new();
+        writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{size}" height="{size}" viewBox="0 0 {size} {size}">"#
+        )
+        .unwrap();
+
+        for position in Vec2::iter() {
+            let (cx, cy) = scale(center(position, options.orientation));
+            let fill = if options.highlighted.contains(&position) {
+                options.highlight_color
+            } else {
+                options.tile_colors[(position.x() as usize + position.y() as usize) % 3]
+            };
+
+            let points = (0..6)
+                .map(|i| {
+                    let angle = std::f64::consts::FRAC_PI_3 * i as f64;
+                    let x = cx + CIRCUMRADIUS * size * angle.cos();
+                    let y = cy + CIRCUMRADIUS * size * angle.sin();
+                    format!("{x:.2},{y:.2}")
+                })
+                .collect::<Vec<_>>()
+                .join(" ");
+
+            writeln!(svg, r#"<polygon points="{points}" fill="{fill}"/>"#).unwrap();
+
+            if options.coordinates {
+                writeln!(
+                    svg,
+                    r#"<text class="coordinate" x="{cx:.2}" y="{:.2}" font-size="{:.2}" text-anchor="middle" fill="white" fill-opacity="0.6">{},{}</text>"#,
+                    cy + APOTHEM * size * 0.8,
+                    APOTHEM * size * 0.35,
+                    position.x(),
+                    position.y(),
+                )
+                .unwrap();
+            }
+        }
+
+        for (position, piece, color) in self.all_piece_positions() {
+            let (cx, cy) = scale(center(position, options.orientation));
+            writeln!(
+                svg,
+                r#"<text class="piece" x="{cx:.2}" y="{cy:.2}" font-size="{:.2}" text-anchor="middle" dominant-baseline="central">{}</text>"#,
+                APOTHEM * size * 1.5,
+                piece.emoji(color),
+            )
+            .unwrap();
+        }
+
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Board, Color, Vec2};
+
+    use super::{SvgOptions, center};
+
+    #[test]
+    fn svg_has_every_hexagon_and_piece() {
+        let board = Board::default();
+        let svg = board.to_svg(SvgOptions::default());
+
+        assert!(svg.starts_with("<svg"));
+        assert_eq!(
+            svg.matches("<polygon").count(),
+            Board::NUMBER_OF_TILES as usize
+        );
+        assert_eq!(
+            svg.matches(r#"class="piece""#).count(),
+            board.all_piece_positions().count()
+        );
+
+        for (_, piece, color) in board.all_piece_positions() {
+            assert!(svg.contains(piece.emoji(color)));
+        }
+    }
+
+    #[test]
+    fn svg_options_are_rendered() {
+        let highlighted = Vec2::new(3, 4).unwrap();
+        let options = SvgOptions {
+            highlighted: vec![highlighted],
+            coordinates: true,
+            ..Default::default()
+        };
+
+        let svg = Board::default().to_svg(options.clone());
+        assert_eq!(svg.matches(options.highlight_color).count(), 1);
+        assert_eq!(
+            svg.matches(r#"class="coordinate""#).count(),
+            Board::NUMBER_OF_TILES as usize
+        );
+        assert!(svg.contains(">3,4</text>"));
+    }
+
+    #[test]
+    fn black_orientation_flips_the_board() {
+        let origin = Vec2::ZERO;
+        let (u, v) = center(origin, Color::White);
+        assert!(v > 0.9, "White's corner should be at the bottom");
+
+        let (flipped_u, flipped_v) = center(origin, Color::Black);
+        assert!((u + flipped_u - 1.0).abs() < 1e-9);
+        assert!((v + flipped_v - 1.0).abs() < 1e-9);
+    }
+}
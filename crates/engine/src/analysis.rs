@@ -0,0 +1,158 @@
+use chessagon_core::{Board, BoardStatus, Color, Game, Move};
+
+use crate::Engine;
+
+/// What an engine thinks of a position, see [`Engine::analyze`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Analysis {
+    /// The move the engine would play, if it searched for one.
+    pub best_move: Option<Move>,
+
+    /// The evaluation of the position, from white's perspective.
+    pub score: f64,
+}
+
+/// How far the search of an engine has gone while it is thinking, see
+/// [`Engine::get_action_with_progress`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SearchProgress {
+    /// The depth of the search that was just finished.
+    pub depth: usize,
+
+    /// The best move and score found at that depth.
+    pub analysis: Analysis,
+}
+
+/// Evaluates every legal move of `color` in the given position, from the point of view of `color`.
+///
+/// The result is sorted from best to worst move. Moves with equal evaluations keep the order
+/// of [`Board::possible_moves`].
+pub fn analyze_all_moves(
+    board: &Board,
+    color: Color,
+    engine: &mut impl Engine,
+) -> Vec<(Move, f64)> {
+    let mut analysis = board
+        .possible_moves(color)
+        .map(|mov| {
+            let mut board = board.clone();
+            board.apply_move_unchecked(mov, color);
+            (mov, engine.eval_for(&board, color))
+        })
+        .collect::<Vec<_>>();
+
+    analysis.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+    analysis
+}
+
+/// Evaluates every position of `game`, from white's perspective, to draw an evaluation graph.
+///
+/// The first evaluation is of the [initial board](Game::initial_board), and there is one more
+/// after each move, so there are as many as moves plus one. Checkmates are evaluated as infinitely
+/// good for the winner, and stalemates as `0.0`, regardless of what the engine thinks of them.
+pub fn evaluate_game(game: &Game, engine: &mut impl Engine) -> Vec<f64> {
+    let mut board = game.initial_board().clone();
+    let mut color = Color::White;
+    let mut evaluations = Vec::with_capacity(game.moves().len() + 1);
+    evaluations.push(evaluate_position(&board, color, engine));
+
+    for &(mov, _) in game.moves() {
+        board.apply_move_unchecked(mov, color);
+        color = color.other();
+        evaluations.push(evaluate_position(&board, color, engine));
+    }
+
+    evaluations
+}
+
+/// Evaluates `board` from white's perspective, with `color` to move.
+fn evaluate_position(board: &Board, color: Color, engine: &mut impl Engine) -> f64 {
+    match board.status(color) {
+        BoardStatus::Ongoing => engine.eval(board),
+        BoardStatus::Checkmate => color.choose(f64::NEG_INFINITY, f64::INFINITY),
+        BoardStatus::Stalemate => 0.0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chessagon_core::{
+        Vec2,
+        game::{Action, TimeControl},
+        piece::Piece,
+    };
+
+    use super::*;
+    use crate::models::Anthony;
+
+    #[test]
+    fn analyzes_every_legal_move_sorted_from_best_to_worst() {
+        let board = Board::default();
+        let mut engine = Anthony::new(Color::White, TimeControl::rapid());
+        let analysis = analyze_all_moves(&board, Color::White, &mut engine);
+
+        assert_eq!(analysis.len(), board.possible_moves(Color::White).count());
+        assert!(analysis.windows(2).all(|w| w[0].1 >= w[1].1));
+    }
+
+    #[test]
+    fn pawns_cannot_double_step_over_a_blocker() {
+        let origin = Vec2::new(4, 2).unwrap();
+        let middle = Vec2::new(5, 3).unwrap();
+        let destination = Vec2::new(6, 4).unwrap();
+
+        let mut board = Board::default();
+        board.get_mut(middle, Color::Black).replace(Piece::Knight);
+
+        let mut engine = Anthony::new(Color::White, TimeControl::rapid());
+        let analysis = analyze_all_moves(&board, Color::White, &mut engine);
+        assert!(
+            analysis
+                .iter()
+                .all(|(mov, _)| (mov.origin(), mov.destination()) != (origin, destination))
+        );
+    }
+
+    #[test]
+    fn evaluates_every_ply_of_a_game() {
+        let mut game = Game::new(TimeControl::max());
+        let mut engine = Anthony::new(Color::White, TimeControl::max());
+        assert_eq!(evaluate_game(&game, &mut engine), vec![0.0]);
+
+        for _ in 0..4 {
+            let mov = game.board().possible_moves(game.turn()).next().unwrap();
+            game.apply_action(Action::Move(mov), game.turn()).unwrap();
+        }
+
+        let evaluations = evaluate_game(&game, &mut engine);
+        assert_eq!(evaluations.len(), game.moves().len() + 1);
+        assert_eq!(evaluations[0], engine.eval(&Board::default()));
+        assert_eq!(evaluations.last(), Some(&engine.eval(game.board())));
+    }
+
+    #[test]
+    fn checkmate_is_infinitely_good_for_the_winner() {
+        let mut board = Board::new_minimal(Vec2::ZERO, Vec2::new(10, 10).unwrap()).unwrap();
+        board
+            .get_mut(Vec2::new(8, 4).unwrap(), Color::White)
+            .replace(Piece::Queen);
+        let mut game = Game::from_position(board, TimeControl::max()).unwrap();
+
+        let mov = game
+            .board()
+            .possible_moves(Color::White)
+            .find(|&mov| {
+                let mut board = game.board().clone();
+                board.apply_move_unchecked(mov, Color::White);
+                board.status(Color::Black) == BoardStatus::Checkmate
+            })
+            .unwrap();
+        game.apply_action(Action::Move(mov), Color::White).unwrap();
+
+        let mut engine = Anthony::new(Color::White, TimeControl::max());
+        let evaluations = evaluate_game(&game, &mut engine);
+        assert_eq!(evaluations.len(), 2);
+        assert!(evaluations[0].is_finite());
+        assert_eq!(evaluations[1], f64::INFINITY);
+    }
+}
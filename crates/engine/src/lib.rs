@@ -1,12 +1,15 @@
 #![allow(missing_docs)]
 
+use analysis::{Analysis, SearchProgress};
 use chessagon_core::{
     Board, Color, Game,
     game::{Action, ApplyActionError, TimeControl},
 };
 
+pub mod analysis;
 pub mod matcher;
 pub mod models;
+pub mod time_management;
 
 pub trait Engine {
     fn new(color: Color, time_control: TimeControl) -> Self
@@ -14,15 +17,55 @@ pub trait Engine {
         Self: Sized;
 
     fn get_action(&mut self, game: &Game) -> Action;
+
+    /// Same as [`Self::get_action`], but calls `progress` every time the search gets further
+    /// (e.g., after each depth), so that it can be shown while the engine is thinking.
+    ///
+    /// By default, no progress is reported.
+    fn get_action_with_progress(
+        &mut self,
+        game: &Game,
+        _progress: &mut dyn FnMut(SearchProgress),
+    ) -> Action {
+        self.get_action(game)
+    }
     fn accept_draw_offer(&mut self, game: &Game) -> bool;
 
-    fn eval(&mut self, board: &Board) -> f64;
+    /// Thinks on the opponent's time, while waiting for their action in `game`.
+    ///
+    /// This should return in a reasonable time, since the opponent's action can't be applied
+    /// until it does. By default, it does nothing.
+    fn ponder(&mut self, _game: &Game) {}
+
+    /// Evaluates the board from the perspective of `color`, without needing mutable access to
+    /// the engine.
+    ///
+    /// Positive values are good for `color`.
+    fn evaluate(&self, board: &Board, color: Color) -> f64;
+
+    /// Evaluates the board from white's perspective.
+    ///
+    /// Engines that cache evaluations can override this to use the cache, by default it is the
+    /// same as [`Self::evaluate`].
+    fn eval(&mut self, board: &Board) -> f64 {
+        self.evaluate(board, Color::White)
+    }
 
     fn eval_for(&mut self, board: &Board, color: Color) -> f64 {
         let eval = self.eval(board);
         color.choose(eval, -eval)
     }
 
+    /// Analyzes the current position of `game`, without playing a move.
+    ///
+    /// By default, this only [evaluates](Self::eval) the board, without suggesting a move.
+    fn analyze(&mut self, game: &Game) -> Analysis {
+        Analysis {
+            best_move: None,
+            score: self.eval(game.board()),
+        }
+    }
+
     fn play(&mut self, game: &mut Game) -> Result<(), ApplyActionError> {
         let action = self.get_action(game);
         game.apply_action(action, game.turn())
@@ -1,33 +1,597 @@
+use std::ops::ControlFlow;
+
 use crate::Engine;
-use chessagon_core::{Board, Color, Game, game::TimeControl};
+use chessagon_core::{
+    Board, Color, Game, InvalidBoardError, Move,
+    game::{Action, TimeControl},
+};
+
+/// How many illegal actions in a row an engine can return before forfeiting, by default.
+pub const MAX_CONSECUTIVE_ILLEGAL_ACTIONS: u32 = 10;
+
+/// How many plies (moves of either player) a match can last before it is a draw, by default.
+pub const MAX_PLIES: u32 = 1000;
+
+/// Limits that make sure that a match always ends, even if the engines misbehave or the position
+/// can't make progress.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MatchLimits {
+    /// The engine that returns this many illegal actions in a row [forfeits](Game::forfeit) the
+    /// game.
+    pub max_illegal_actions: u32,
+
+    /// After this many plies, the game is [adjudicated as a draw](Game::adjudicate_draw).
+    pub max_plies: u32,
+
+    /// After this many plies in a row without an [irreversible](Move::is_irreversible) move
+    /// (a capture or a pawn move), the game is [adjudicated as a draw](Game::adjudicate_draw).
+    ///
+    /// Unlike the [fifty-move rule](chessagon_core::game::DrawReason::FiftyMoves), this only
+    /// applies to engine matches, so it can be much shorter to make benchmarks finish quickly.
+    /// `None` disables it.
+    pub max_plies_without_progress: Option<u32>,
+}
+
+impl Default for MatchLimits {
+    fn default() -> Self {
+        Self {
+            max_illegal_actions: MAX_CONSECUTIVE_ILLEGAL_ACTIONS,
+            max_plies: MAX_PLIES,
+            max_plies_without_progress: None,
+        }
+    }
+}
 
+/// Plays a whole match between two engines, starting from `board`.
+///
+/// Returns an error if the board is not [valid](Board::validate).
 pub fn match_engines_from_position<White: Engine, Black: Engine>(
     board: Board,
     time_control: TimeControl,
-) -> Game {
-    let mut game = Game::from_position(board, time_control);
+) -> Result<Game, InvalidBoardError> {
+    match_engines_from_position_with_limits::<White, Black>(
+        board,
+        time_control,
+        MatchLimits::default(),
+    )
+}
 
-    let mut white = White::new(Color::White, time_control);
-    let mut black = Black::new(Color::Black, time_control);
+/// Same as [`match_engines_from_position`], but with custom [`MatchLimits`].
+pub fn match_engines_from_position_with_limits<White: Engine, Black: Engine>(
+    board: Board,
+    time_control: TimeControl,
+    limits: MatchLimits,
+) -> Result<Game, InvalidBoardError> {
+    let game = Game::from_position(board, time_control)?;
+    let mut engine_match = EngineMatch::<White, Black>::from_game(game, limits);
+    while engine_match.step().is_some() {}
 
-    let mut players: [&mut dyn Engine; 2] = [&mut white, &mut black];
+    Ok(engine_match.into_game())
+}
 
-    loop {
+/// A match between two engines that is played one action at a time, e.g., to watch it live.
+///
+/// See [`match_engines`] to play a whole match at once.
+pub struct EngineMatch<White, Black> {
+    game: Game,
+    white: White,
+    black: Black,
+    referee: Referee,
+}
+
+impl<White: Engine, Black: Engine> EngineMatch<White, Black> {
+    /// Creates a match that continues `game`, with new engines.
+    pub fn from_game(game: Game, limits: MatchLimits) -> Self {
+        let time_control = game.time_control();
+        Self {
+            white: White::new(Color::White, time_control),
+            black: Black::new(Color::Black, time_control),
+            referee: Referee::new(&game, limits),
+            game,
+        }
+    }
+
+    /// The game being played.
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    /// Stops the match, returning the game.
+    pub fn into_game(self) -> Game {
+        self.game
+    }
+
+    /// Asks the engine whose turn it is for an action and applies it.
+    ///
+    /// Returns the action and who took it, or `None` if the game is over (either before this
+    /// step, or because the match reached [`MatchLimits::max_plies`] or
+    /// [`MatchLimits::max_plies_without_progress`]). Illegal actions are
+    /// returned too, even though they don't change the game (unless the engine
+    /// [forfeits](MatchLimits::max_illegal_actions) because of them).
+    pub fn step(&mut self) -> Option<(Color, Action)> {
+        self.referee
+            .step(&mut self.game, &mut self.white, &mut self.black)
+    }
+}
+
+/// Keeps track of a match between two engines, to end it within its [`MatchLimits`].
+struct Referee {
+    limits: MatchLimits,
+    illegal_actions: u32,
+    plies: u32,
+    plies_without_progress: u32,
+}
+
+impl Referee {
+    /// Creates a referee for a match that continues `game`.
+    fn new(game: &Game, limits: MatchLimits) -> Self {
+        Self {
+            limits,
+            illegal_actions: 0,
+            plies: game.moves().len() as u32,
+            plies_without_progress: game.halfmove_clock() as u32,
+        }
+    }
+
+    /// Asks the engine whose turn it is for an action and applies it, as described in
+    /// [`EngineMatch::step`].
+    fn step(
+        &mut self,
+        game: &mut Game,
+        white: &mut dyn Engine,
+        black: &mut dyn Engine,
+    ) -> Option<(Color, Action)> {
         tracing::debug!("Board state: \n{}", game.board());
         if game.result().is_some() {
-            break;
-        };
+            return None;
+        }
 
-        let action = players[game.turn()].get_action(&game);
-        tracing::debug!("{}: {action}", game.turn());
+        if let Some(result) = game.check_timeout() {
+            tracing::info!("{result}");
+            return None;
+        }
 
-        if let Err(apply_action_err) = game.apply_action(action, game.turn()) {
+        if self.plies >= self.limits.max_plies {
+            tracing::info!("Adjudicating a draw after {} plies", self.plies);
+            game.adjudicate_draw();
+            return None;
+        }
+
+        if let Some(max_plies_without_progress) = self.limits.max_plies_without_progress
+            && self.plies_without_progress >= max_plies_without_progress
+        {
+            tracing::info!(
+                "Adjudicating a draw after {} plies without progress",
+                self.plies_without_progress
+            );
+            game.adjudicate_draw();
+            return None;
+        }
+
+        let color = game.turn();
+        let action = match color {
+            Color::White => white.get_action(game),
+            Color::Black => black.get_action(game),
+        };
+        tracing::debug!("{color}: {action}");
+
+        let is_irreversible =
+            matches!(action, Action::Move(mov) if mov.is_irreversible(game.board(), color));
+        if let Err(apply_action_err) = game.apply_action(action, color) {
             tracing::debug!("Action was invalid: {apply_action_err}");
+            self.illegal_actions += 1;
+            if self.illegal_actions >= self.limits.max_illegal_actions {
+                tracing::warn!(
+                    "{color} forfeits after {} illegal actions in a row",
+                    self.illegal_actions
+                );
+                game.forfeit(color);
+            }
+        } else {
+            self.illegal_actions = 0;
+            if matches!(action, Action::Move(_)) {
+                self.plies += 1;
+                if is_irreversible {
+                    self.plies_without_progress = 0;
+                } else {
+                    self.plies_without_progress += 1;
+                }
+            }
+        }
+
+        Some((color, action))
+    }
+}
+
+/// Callbacks to observe a game played by [`run_game`].
+///
+/// Closures taking the game, the color that moved and the move implement this, and `()` ignores
+/// every move.
+pub trait GameHooks {
+    /// Called after `color` plays `mov`, which is already applied to `game`.
+    ///
+    /// Returning [`ControlFlow::Break`] stops the game right away, leaving it unfinished.
+    fn on_move(&mut self, game: &Game, color: Color, mov: Move) -> ControlFlow<()>;
+}
+
+impl<F: FnMut(&Game, Color, Move) -> ControlFlow<()>> GameHooks for F {
+    fn on_move(&mut self, game: &Game, color: Color, mov: Move) -> ControlFlow<()> {
+        self(game, color, mov)
+    }
+}
+
+impl GameHooks for () {
+    fn on_move(&mut self, _: &Game, _: Color, _: Move) -> ControlFlow<()> {
+        ControlFlow::Continue(())
+    }
+}
+
+/// Plays a game between two engines from the default position, calling `hooks` after every
+/// move, e.g., to log the game or to stop it early.
+///
+/// Unlike [`match_engines`], the engines are borrowed, so they can be set up beforehand and
+/// inspected afterwards. The game ends within the default [`MatchLimits`], unless the hooks stop
+/// it before.
+pub fn run_game(
+    white: &mut dyn Engine,
+    black: &mut dyn Engine,
+    time_control: TimeControl,
+    hooks: &mut dyn GameHooks,
+) -> Game {
+    let mut game = Game::new(time_control);
+    let mut referee = Referee::new(&game, MatchLimits::default());
+    loop {
+        let plies = game.moves().len();
+        let Some((color, action)) = referee.step(&mut game, white, black) else {
+            break;
+        };
+
+        if let Action::Move(mov) = action
+            && game.moves().len() > plies
+            && hooks.on_move(&game, color, mov).is_break()
+        {
+            break;
         }
     }
 
     game
 }
+
 pub fn match_engines<White: Engine, Black: Engine>(time_control: TimeControl) -> Game {
     match_engines_from_position::<White, Black>(Board::default(), time_control)
+        .expect("The default board should be valid")
+}
+
+/// How a candidate engine did against a baseline in [`compare_engines`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Comparison {
+    pub wins: u32,
+    pub draws: u32,
+    pub losses: u32,
+}
+
+impl Comparison {
+    /// The points of the candidate per game, counting a win as 1 and a draw as 0.5.
+    ///
+    /// An engine that is as strong as the baseline scores around 0.5. Returns [`None`] if no
+    /// games were played (e.g., when comparing from no positions).
+    pub fn score(&self) -> Option<f64> {
+        let games = self.wins + self.draws + self.losses;
+        (games > 0).then(|| (self.wins as f64 + self.draws as f64 / 2.0) / games as f64)
+    }
+}
+
+/// Plays `Candidate` against `Baseline` from each of the given positions, once with each color,
+/// to catch regressions in the strength of an engine.
+///
+/// Games are untimed (see [`TimeControl::max`]), so that they are reproducible for
+/// deterministic engines, and end within the given `limits`.
+pub fn compare_engines<Candidate: Engine, Baseline: Engine>(
+    positions: &[Board],
+    limits: MatchLimits,
+) -> Result<Comparison, InvalidBoardError> {
+    let mut comparison = Comparison::default();
+    for board in positions {
+        let as_white = match_engines_from_position_with_limits::<Candidate, Baseline>(
+            board.clone(),
+            TimeControl::max(),
+            limits,
+        )?;
+        let as_black = match_engines_from_position_with_limits::<Baseline, Candidate>(
+            board.clone(),
+            TimeControl::max(),
+            limits,
+        )?;
+
+        for (game, color) in [(as_white, Color::White), (as_black, Color::Black)] {
+            match game.winner() {
+                Some(Some(winner)) if winner == color => comparison.wins += 1,
+                Some(Some(_)) => comparison.losses += 1,
+                Some(None) => comparison.draws += 1,
+                None => unreachable!("Matches always finish"),
+            }
+        }
+    }
+
+    Ok(comparison)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::ControlFlow;
+
+    use chessagon_core::{
+        Board, Color, Game, Move, Vec2,
+        game::{Action, DrawReason, GameResult, TimeControl, WinReason},
+        piece::Piece,
+    };
+
+    use super::{
+        Comparison, EngineMatch, MatchLimits, compare_engines,
+        match_engines_from_position_with_limits, run_game,
+    };
+    use crate::{Engine, models::Anthony};
+
+    /// An engine that always tries to move a piece that doesn't exist.
+    struct Broken;
+
+    impl Engine for Broken {
+        fn new(_: Color, _: TimeControl) -> Self {
+            Self
+        }
+
+        fn get_action(&mut self, _: &Game) -> Action {
+            Action::Move(Move::Regular {
+                origin: Vec2::new(5, 5).unwrap(),
+                destination: Vec2::new(6, 6).unwrap(),
+                captures: false,
+            })
+        }
+
+        fn accept_draw_offer(&mut self, _: &Game) -> bool {
+            false
+        }
+
+        fn evaluate(&self, _: &Board, _: Color) -> f64 {
+            0.0
+        }
+    }
+
+    /// An engine that always plays its first legal move, so that lone kings shuffle forever.
+    struct Shuffler;
+
+    impl Engine for Shuffler {
+        fn new(_: Color, _: TimeControl) -> Self {
+            Self
+        }
+
+        fn get_action(&mut self, game: &Game) -> Action {
+            let mov = game.board().possible_moves(game.turn()).next().unwrap();
+            Action::Move(mov)
+        }
+
+        fn accept_draw_offer(&mut self, _: &Game) -> bool {
+            false
+        }
+
+        fn evaluate(&self, _: &Board, _: Color) -> f64 {
+            0.0
+        }
+    }
+
+    /// An engine that pushes pawns in the first few plies of the game and then shuffles like
+    /// [`Shuffler`].
+    struct PawnPusher;
+
+    impl Engine for PawnPusher {
+        fn new(_: Color, _: TimeControl) -> Self {
+            Self
+        }
+
+        fn get_action(&mut self, game: &Game) -> Action {
+            let (board, color) = (game.board(), game.turn());
+            let progress = (game.moves().len() < 4)
+                .then(|| {
+                    board
+                        .possible_moves(color)
+                        .find(|mov| mov.is_irreversible(board, color))
+                })
+                .flatten();
+
+            Action::Move(progress.unwrap_or_else(|| board.possible_moves(color).next().unwrap()))
+        }
+
+        fn accept_draw_offer(&mut self, _: &Game) -> bool {
+            false
+        }
+
+        fn evaluate(&self, _: &Board, _: Color) -> f64 {
+            0.0
+        }
+    }
+
+    #[test]
+    fn match_without_progress_is_a_draw_after_max_plies() {
+        let board =
+            Board::new_minimal(Vec2::new(0, 0).unwrap(), Vec2::new(10, 10).unwrap()).unwrap();
+        let limits = MatchLimits {
+            max_plies: 20,
+            ..Default::default()
+        };
+        let game = match_engines_from_position_with_limits::<Shuffler, Shuffler>(
+            board,
+            TimeControl::max(),
+            limits,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            game.result(),
+            Some(GameResult::Draw {
+                reason: DrawReason::Adjudication { .. }
+            })
+        ));
+        assert_eq!(
+            game.moves_from(Color::White).count() + game.moves_from(Color::Black).count(),
+            20
+        );
+    }
+
+    #[test]
+    fn match_without_progress_is_a_draw_at_the_configured_threshold() {
+        let mut board =
+            Board::new_minimal(Vec2::new(0, 0).unwrap(), Vec2::new(10, 10).unwrap()).unwrap();
+        board
+            .get_mut(Vec2::new(4, 1).unwrap(), Color::White)
+            .replace(Piece::Pawn);
+        let limits = MatchLimits {
+            max_plies_without_progress: Some(12),
+            ..Default::default()
+        };
+        let game = match_engines_from_position_with_limits::<PawnPusher, PawnPusher>(
+            board.clone(),
+            TimeControl::max(),
+            limits,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            game.result(),
+            Some(GameResult::Draw {
+                reason: DrawReason::Adjudication { .. }
+            })
+        ));
+
+        // Counting starts over after every pawn move, and the game ends right at the threshold.
+        let mut plies_without_progress = 0;
+        for &(mov, _) in game.moves() {
+            let color = board.turn();
+            if mov.is_irreversible(&board, color) {
+                plies_without_progress = 0;
+            } else {
+                plies_without_progress += 1;
+            }
+            board.apply_move(mov, color).unwrap();
+        }
+        assert_eq!(plies_without_progress, 12);
+        assert_eq!(
+            game.moves().len(),
+            3 + 12,
+            "The pawn moved in plies 0 and 2"
+        );
+    }
+
+    #[test]
+    fn engine_playing_illegal_moves_forfeits() {
+        let limits = MatchLimits {
+            max_illegal_actions: 3,
+            ..Default::default()
+        };
+        let game = match_engines_from_position_with_limits::<Broken, Anthony>(
+            Board::default(),
+            TimeControl::max(),
+            limits,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            game.result(),
+            Some(GameResult::Win {
+                winner: Color::Black,
+                reason: WinReason::Forfeit { .. }
+            })
+        ));
+        assert_eq!(game.moves_from(Color::White).count(), 0);
+    }
+
+    #[test]
+    fn engine_match_steps_one_move_at_a_time() {
+        let game = Game::new(TimeControl::max());
+        let mut engine_match = EngineMatch::<Anthony, Anthony>::from_game(game, Default::default());
+
+        let (color, action) = engine_match.step().unwrap();
+        assert_eq!(color, Color::White);
+        assert!(matches!(action, Action::Move(_)));
+        assert_eq!(engine_match.game().moves_from(Color::White).count(), 1);
+        assert_eq!(engine_match.game().turn(), Color::Black);
+
+        let (color, _) = engine_match.step().unwrap();
+        assert_eq!(color, Color::Black);
+        assert_eq!(engine_match.into_game().moves_from(Color::Black).count(), 1);
+    }
+
+    #[test]
+    fn run_game_reports_every_move_to_the_hooks() {
+        let mut white = Anthony::new(Color::White, TimeControl::max());
+        let mut black = Anthony::new(Color::Black, TimeControl::max());
+
+        let mut moves = Vec::new();
+        let mut hooks = |game: &Game, color: Color, mov: Move| {
+            assert_eq!(game.turn(), color.other());
+            moves.push(mov);
+            if moves.len() < 6 {
+                ControlFlow::Continue(())
+            } else {
+                ControlFlow::Break(())
+            }
+        };
+
+        let game = run_game(&mut white, &mut black, TimeControl::max(), &mut hooks);
+        assert_eq!(game.result(), None, "The hooks stopped the game early");
+        assert_eq!(
+            moves,
+            game.moves().iter().map(|&(mov, _)| mov).collect::<Vec<_>>()
+        );
+        assert_eq!(moves.len(), 6);
+    }
+
+    /// A few positions after one move of each player, to play engines from.
+    fn opening_positions() -> Vec<Board> {
+        [(0, 0), (3, 7), (8, 2), (12, 11)]
+            .into_iter()
+            .map(|(white_move, black_move)| {
+                let mut board = Board::default();
+                for (color, n) in [(Color::White, white_move), (Color::Black, black_move)] {
+                    let mov = board.possible_moves(color).nth(n).unwrap();
+                    board.apply_move(mov, color).unwrap();
+                }
+
+                board
+            })
+            .collect()
+    }
+
+    #[test]
+    fn comparison_counts_both_colors() {
+        let positions = opening_positions();
+        let comparison = compare_engines::<Shuffler, Broken>(&positions, Default::default());
+        assert_eq!(
+            comparison,
+            Ok(Comparison {
+                wins: 2 * positions.len() as u32,
+                draws: 0,
+                losses: 0,
+            })
+        );
+        assert_eq!(comparison.unwrap().score(), Some(1.0));
+
+        let empty = compare_engines::<Shuffler, Broken>(&[], Default::default()).unwrap();
+        assert_eq!(empty, Comparison::default());
+        assert_eq!(empty.score(), None);
+    }
+
+    /// Slow, run with `cargo test -p chessagon-engine -- --ignored`.
+    ///
+    /// Replace the baseline with a copy of the previous version of the engine to check that
+    /// changes don't make it weaker.
+    #[test]
+    #[ignore]
+    fn anthony_scores_at_least_as_well_as_the_baseline() {
+        let limits = MatchLimits {
+            max_plies: 120,
+            ..Default::default()
+        };
+        let comparison = compare_engines::<Anthony, Anthony>(&opening_positions(), limits).unwrap();
+        assert!(comparison.score().unwrap() >= 0.5, "{comparison:?}");
+    }
 }
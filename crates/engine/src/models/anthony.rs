@@ -1,21 +1,128 @@
-use std::collections::HashSet;
+use std::{cmp::Reverse, collections::HashMap, time::Instant};
 
 use chessagon_core::{
-    Board, Color, Move,
+    Board, Color, Move, Vec2,
     game::{Action, Game, TimeControl},
+    piece::Piece,
 };
 
-use crate::Engine;
+use super::{EvalWeights, Personality};
+use crate::{
+    Engine,
+    analysis::{Analysis, SearchProgress},
+    time_management::TimeManagement,
+};
 
 /// Very simple chessagon engine, used as an example.
 pub struct Anthony {
     color: Color,
-    played_moves: HashSet<Move>,
+
+    /// Reusable buffers for the moves at each depth of the search.
+    move_buffers: Vec<Vec<Move>>,
+
+    /// Results of previous searches, keyed by the [hash](Board::zobrist_hash) of the position
+    /// and the color to move.
+    transpositions: HashMap<(u64, Color), Transposition>,
+
+    /// How many positions were searched (i.e., not found in the transposition table) since the
+    /// start of the last search, see [`Self::start_search`].
+    nodes: u64,
+
+    /// The most positions to search on each [`Engine::get_action`], see
+    /// [`AnthonyConfig::max_nodes`].
+    max_nodes: Option<u64>,
+
+    /// Whether a search was cut short by [`Self::max_nodes`] since the start of the last search,
+    /// see [`Self::start_search`].
+    out_of_nodes: bool,
+
+    /// The weights of the terms of [`Engine::evaluate`], see [`AnthonyConfig::personality`].
+    weights: EvalWeights,
+
+    /// How long to think on each move.
+    pub time_management: TimeManagement,
+
+    /// Whether to think on the opponent's time, see [`Engine::ponder`].
+    pub ponder: bool,
+}
+
+/// Settings of [`Anthony`] that can't be changed after it is created, see
+/// [`Anthony::with_config`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AnthonyConfig {
+    /// The most positions to search on each [`Engine::get_action`], counted as in
+    /// [`Anthony::nodes`]. `None` means there is no limit.
+    ///
+    /// Once the limit is reached, the remaining positions are evaluated without searching them,
+    /// and the best move of the deepest search that finished before is played. The search at
+    /// depth 1 always finishes, so a legal move is always found.
+    ///
+    /// Unlike the time budget, this makes the search the same regardless of how fast the machine
+    /// is, which is useful for tests.
+    pub max_nodes: Option<u64>,
+
+    /// How the engine weighs the terms of its evaluation.
+    pub personality: Personality,
+}
+
+/// A search result stored in the transposition table of [`Anthony`].
+#[derive(Debug, Clone, Copy)]
+struct Transposition {
+    depth: usize,
+    best_move: Option<Move>,
+    score: f64,
 }
 
 impl Anthony {
     pub const SEARCH_DEPTH: usize = 2;
 
+    /// The number of positions after which the transposition table is cleared, to bound its
+    /// memory usage.
+    pub const MAX_TRANSPOSITIONS: usize = 1 << 20;
+
+    /// How many positions were searched since the start of the last search (i.e.,
+    /// [`Engine::get_action`], [`Engine::analyze`], [`Engine::ponder`] or
+    /// [`Self::analyze_multipv`]).
+    ///
+    /// Positions found in the transposition table are not counted.
+    pub fn nodes(&self) -> u64 {
+        self.nodes
+    }
+
+    /// Resets the node count, so that every search gets the whole [`Self::max_nodes`].
+    fn start_search(&mut self) {
+        self.nodes = 0;
+        self.out_of_nodes = false;
+    }
+
+    /// Creates the engine with the given [`AnthonyConfig`].
+    ///
+    /// [`Engine::new`] is the same as this with the default config.
+    pub fn with_config(color: Color, config: AnthonyConfig) -> Self {
+        Self {
+            color,
+            move_buffers: Vec::new(),
+            transpositions: HashMap::new(),
+            nodes: 0,
+            max_nodes: config.max_nodes,
+            out_of_nodes: false,
+            weights: config.personality.weights(),
+            time_management: TimeManagement::default(),
+            ponder: false,
+        }
+    }
+
+    /// Creates the engine with the given [`Personality`] and otherwise default settings.
+    pub fn with_personality(color: Color, personality: Personality) -> Self {
+        Self::with_config(
+            color,
+            AnthonyConfig {
+                personality,
+                ..Default::default()
+            },
+        )
+    }
+
     pub fn search_move(
         &mut self,
         board: &Board,
@@ -26,26 +133,47 @@ impl Anthony {
             return (None, self.eval_for(board, color));
         }
 
+        let key = (board.zobrist_hash(), color);
+        if let Some(transposition) = self.transpositions.get(&key)
+            && transposition.depth >= depth
+        {
+            return (transposition.best_move, transposition.score);
+        }
+
+        if self
+            .max_nodes
+            .is_some_and(|max_nodes| self.nodes >= max_nodes)
+        {
+            self.out_of_nodes = true;
+            return (None, self.eval_for(board, color));
+        }
+
+        self.nodes += 1;
+        if self.move_buffers.len() < depth {
+            self.move_buffers.resize_with(depth, Vec::new);
+        }
+
+        let mut moves = std::mem::take(&mut self.move_buffers[depth - 1]);
+        board.collect_moves_into(color, &mut moves);
+
         let mut best_move = None;
         let mut best_move_score = f64::NEG_INFINITY;
-        for mov in board.possible_moves(color) {
+        for &mov in &moves {
             let mut board = board.clone();
             board.apply_move_unchecked(mov, color);
 
             let (_best_response, opponent_score) =
                 self.search_move(&board, color.other(), depth - 1);
 
-            let mut score = -opponent_score;
-            if self.played_moves.contains(&mov) {
-                score -= 50.0;
-            }
-
-            if score > best_move_score {
+            let score = -opponent_score;
+            if is_better(mov, score, best_move, best_move_score, color) {
                 best_move_score = score;
                 best_move = Some(mov);
             }
         }
 
+        self.move_buffers[depth - 1] = moves;
+
         if best_move.is_none() {
             for mov in board.possible_moves(color) {
                 tracing::debug!("There is {mov}");
@@ -57,26 +185,173 @@ impl Anthony {
             }
         }
 
+        // Positions below this one might not have been searched, so the score is not as deep as
+        // the transposition would claim.
+        if self.out_of_nodes {
+            return (best_move, best_move_score);
+        }
+
+        if self.transpositions.len() >= Self::MAX_TRANSPOSITIONS {
+            self.transpositions.clear();
+        }
+
+        self.transpositions.insert(
+            key,
+            Transposition {
+                depth,
+                best_move,
+                score: best_move_score,
+            },
+        );
+
+        (best_move, best_move_score)
+    }
+
+    /// Searches the current position of `game`, like [`Self::search_move`], but taking into
+    /// account the history of the game.
+    ///
+    /// Moves that would repeat a position for the third time are scored as a draw if the engine
+    /// would otherwise be ahead after them, so that it doesn't throw away a winning position.
+    /// Moves that don't repeat the position are scored as usual, even if they have been played
+    /// before.
+    pub fn search_game(&mut self, game: &Game, depth: usize) -> (Option<Move>, f64) {
+        let color = game.turn();
+        let mut best_move = None;
+        let mut best_move_score = f64::NEG_INFINITY;
+        for (mov, score) in self.score_root_moves(game, depth) {
+            if is_better(mov, score, best_move, best_move_score, color) {
+                best_move_score = score;
+                best_move = Some(mov);
+            }
+        }
+
         (best_move, best_move_score)
     }
+
+    /// The `n` best moves in the current position of `game`, with their scores, from best to
+    /// worst (i.e., [MultiPV](https://www.chessprogramming.org/Multiple_PV)).
+    ///
+    /// Every move is searched to [`Self::SEARCH_DEPTH`] and scored as in [`Self::search_game`],
+    /// so the first move is the one that [`Engine::get_action`] would play given enough time.
+    pub fn analyze_multipv(&mut self, game: &Game, n: usize) -> Vec<(Move, f64)> {
+        self.start_search();
+        let color = game.turn();
+        let mut scores = self.score_root_moves(game, Self::SEARCH_DEPTH);
+        scores.sort_by(|&(a, a_score), &(b, b_score)| {
+            b_score
+                .total_cmp(&a_score)
+                .then_with(|| tie_break_key(b, color).cmp(&tie_break_key(a, color)))
+        });
+
+        scores.truncate(n);
+        scores
+    }
+
+    /// Scores every legal move in the current position of `game`, as described in
+    /// [`Self::search_game`]. The moves are in the order of [`Board::possible_moves`].
+    fn score_root_moves(&mut self, game: &Game, depth: usize) -> Vec<(Move, f64)> {
+        let board = game.board();
+        let color = game.turn();
+
+        board
+            .possible_moves(color)
+            .map(|mov| {
+                let mut child = board.clone();
+                child.apply_move_unchecked(mov, color);
+
+                let (_best_response, opponent_score) =
+                    self.search_move(&child, color.other(), depth.saturating_sub(1));
+
+                let mut score = -opponent_score;
+                if score > 0.0 && game.would_be_repetition(mov) {
+                    score = 0.0;
+                }
+
+                (mov, score)
+            })
+            .collect()
+    }
+}
+
+/// Whether `mov` with `score` should replace the best move found so far, both made by `color`.
+fn is_better(
+    mov: Move,
+    score: f64,
+    best_move: Option<Move>,
+    best_move_score: f64,
+    color: Color,
+) -> bool {
+    let breaks_tie = score == best_move_score
+        && best_move.is_some_and(|best| tie_break_key(mov, color) > tie_break_key(best, color));
+
+    score > best_move_score || breaks_tie
+}
+
+/// Key used to choose between moves with the same score, where the greatest key is preferred.
+///
+/// Moves are compared by:
+/// 1. Captures over non-captures.
+/// 2. Destinations closer to the [center](Vec2::CENTER) of the board.
+///
+/// If both are equal, the move that comes first in [`Board::collect_moves_into`] is kept.
+fn tie_break_key(mov: Move, color: Color) -> (bool, Reverse<u8>) {
+    let captures = match mov {
+        Move::Regular { captures, .. } => captures,
+        Move::EnPassant { .. } => true,
+        Move::Promotion { captures, .. } => captures.is_some(),
+    };
+
+    let destination = mov.destination_for(color);
+    (captures, Reverse(destination.distance(Vec2::CENTER)))
 }
 
 impl Engine for Anthony {
     fn new(color: Color, _: TimeControl) -> Self {
-        Self {
-            color,
-            played_moves: HashSet::new(),
-        }
+        Self::with_config(color, AnthonyConfig::default())
     }
 
     fn get_action(&mut self, game: &Game) -> Action {
-        let (Some(mov), _score) = self.search_move(game.board(), self.color, Self::SEARCH_DEPTH)
-        else {
+        self.get_action_with_progress(game, &mut |_| ())
+    }
+
+    /// Reports the best move and score after each depth of the iterative deepening.
+    fn get_action_with_progress(
+        &mut self,
+        game: &Game,
+        progress: &mut dyn FnMut(SearchProgress),
+    ) -> Action {
+        let budget = self.time_management.budget_for(game, self.color);
+        let start = Instant::now();
+        self.start_search();
+
+        // Search deeper only while there is time left in the budget.
+        let mut best_move = None;
+        for depth in 1..=Self::SEARCH_DEPTH {
+            if depth > 1 && start.elapsed() >= budget {
+                tracing::debug!("Stopping search at depth {depth} (budget is {budget:?})");
+                break;
+            }
+
+            let (mov, score) = self.search_game(game, depth);
+            if depth > 1 && self.out_of_nodes {
+                tracing::debug!("Discarding search at depth {depth} (ran out of nodes)");
+                break;
+            }
+
+            best_move = mov.or(best_move);
+            progress(SearchProgress {
+                depth,
+                analysis: Analysis {
+                    best_move,
+                    score: game.turn().choose(score, -score),
+                },
+            });
+        }
+
+        let Some(mov) = best_move else {
             unreachable!("If no moves are left, game should have been considered finished before.");
         };
 
-        self.played_moves.insert(mov);
-
         Action::Move(mov)
     }
 
@@ -84,10 +359,320 @@ impl Engine for Anthony {
         false
     }
 
-    fn eval(&mut self, board: &Board) -> f64 {
-        (board.total_piece_value(Color::White) as i16
+    /// Searches the position to [`Self::SEARCH_DEPTH`], as in [`Self::search_game`].
+    fn analyze(&mut self, game: &Game) -> Analysis {
+        self.start_search();
+        let (best_move, score) = self.search_game(game, Self::SEARCH_DEPTH);
+        Analysis {
+            best_move,
+            score: game.turn().choose(score, -score),
+        }
+    }
+
+    /// Predicts the reply of the opponent and searches the position after it, so that the
+    /// search of the next move is mostly found in the transposition table if the opponent plays
+    /// the predicted move.
+    ///
+    /// Does nothing unless [`Anthony::ponder`] is enabled.
+    fn ponder(&mut self, game: &Game) {
+        if !self.ponder || game.is_finished() || game.turn() == self.color {
+            return;
+        }
+
+        self.start_search();
+        let (Some(predicted), _) =
+            self.search_move(game.board(), self.color.other(), Self::SEARCH_DEPTH)
+        else {
+            return;
+        };
+
+        tracing::debug!("Pondering on {predicted}");
+        let mut board = game.board().clone();
+        board.apply_move_unchecked(predicted, self.color.other());
+        self.search_move(&board, self.color, Self::SEARCH_DEPTH);
+    }
+
+    fn evaluate(&self, board: &Board, color: Color) -> f64 {
+        // A bishop on the same index as another one covers no new tiles, so it is worth less.
+        let redundant_bishops = |color| {
+            board
+                .bishops_by_index(color)
+                .iter()
+                .map(|&count| count.saturating_sub(1) as i16)
+                .sum::<i16>()
+        };
+
+        // The pieces of `color` next to its own king.
+        let defenders = |color| {
+            board
+                .king_zone(color)
+                .filter(|&tile| board.get(tile, color).is_some_and(|p| p != Piece::King))
+                .count() as i16
+        };
+
+        // The tiles around the king of the opponent of `color` that `color` attacks.
+        let attackers = |color: Color| {
+            board
+                .king_zone(color.other())
+                .filter(|&tile| board.is_attacked(tile, color))
+                .count() as i16
+        };
+
+        let material = board.total_piece_value(Color::White) as i16
             - board.total_piece_value(Color::Black) as i16
-            - board.in_check(Color::White).is_some() as i16 * 100
-            + board.in_check(Color::Black).is_some() as i16 * 200) as f64
+            - redundant_bishops(Color::White)
+            + redundant_bishops(Color::Black);
+        let checks = -(board.in_check(Color::White).is_some() as i16) * 100
+            + board.in_check(Color::Black).is_some() as i16 * 200;
+
+        let weights = self.weights;
+        let mut eval = weights.material * material as f64 + weights.checks * checks as f64;
+
+        // These terms are not used by most personalities, so they are only computed if needed.
+        if weights.king_safety != 0.0 {
+            eval +=
+                weights.king_safety * (defenders(Color::White) - defenders(Color::Black)) as f64;
+        }
+
+        if weights.king_attack != 0.0 {
+            eval +=
+                weights.king_attack * (attackers(Color::White) - attackers(Color::Black)) as f64;
+        }
+
+        color.choose(eval, -eval)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use chessagon_core::{
+        Board, Color, Move, Vec2,
+        game::{Action, Game, TimeControl},
+        piece::Piece,
+    };
+
+    use super::{Anthony, AnthonyConfig};
+    use crate::{Engine, models::Personality};
+
+    #[test]
+    fn evaluate_through_shared_reference() {
+        let engine = Arc::new(Anthony::new(Color::White, TimeControl::blitz()));
+        let board = Board::default();
+
+        let shared = Arc::clone(&engine);
+        let eval = std::thread::spawn(move || shared.evaluate(&Board::default(), Color::White))
+            .join()
+            .unwrap();
+
+        assert_eq!(eval, engine.evaluate(&board, Color::White));
+        assert_eq!(
+            engine.evaluate(&board, Color::White),
+            -engine.evaluate(&board, Color::Black)
+        );
+    }
+
+    #[test]
+    fn ties_prefer_central_destinations() {
+        let mut board =
+            Board::new_minimal(Vec2::new(0, 0).unwrap(), Vec2::new(10, 10).unwrap()).unwrap();
+        let knight = Vec2::new(2, 4).unwrap();
+        board.get_mut(knight, Color::White).replace(Piece::Knight);
+
+        // Every move keeps the material balance, so they all have the same score.
+        let mut engine = Anthony::new(Color::White, TimeControl::blitz());
+        for _ in 0..3 {
+            let (mov, _) = engine.search_move(&board, Color::White, 1);
+            assert_eq!(
+                mov,
+                Some(Move::Regular {
+                    origin: knight,
+                    destination: Vec2::CENTER,
+                    captures: false,
+                })
+            );
+        }
+    }
+
+    #[test]
+    fn replays_moves_unless_they_repeat_the_position() {
+        let mut board =
+            Board::new_minimal(Vec2::new(0, 0).unwrap(), Vec2::new(10, 10).unwrap()).unwrap();
+        let knight = Vec2::new(2, 4).unwrap();
+        board.get_mut(knight, Color::White).replace(Piece::Knight);
+
+        let mut game = Game::from_position(board, TimeControl::blitz()).unwrap();
+        let mut engine = Anthony::new(Color::White, TimeControl::blitz());
+        let to_center = Move::Regular {
+            origin: knight,
+            destination: Vec2::CENTER,
+            captures: false,
+        };
+
+        fn play(game: &mut Game, color: Color, origin: Vec2, destination: Vec2) {
+            let (mov, _) = game.board().get_move(origin, destination, color).unwrap();
+            game.apply_action(Action::Move(mov), color).unwrap();
+        }
+
+        let black_king = Vec2::new(10, 10).unwrap();
+        let black_king_moved = Vec2::new(9, 9).unwrap();
+        for repetition in 0..2 {
+            // The knight goes to the center, both times.
+            let action = engine.get_action(&game);
+            assert_eq!(action, Action::Move(to_center), "repetition {repetition}");
+            game.apply_action(action, Color::White).unwrap();
+
+            play(&mut game, Color::Black, black_king, black_king_moved);
+            play(&mut game, Color::White, Vec2::CENTER, knight);
+            play(&mut game, Color::Black, black_king_moved, black_king);
+        }
+
+        // The third time would repeat the position after the knight went to the center.
+        assert!(game.would_be_repetition(to_center));
+        assert_ne!(engine.get_action(&game), Action::Move(to_center));
+    }
+
+    #[test]
+    fn pondered_and_confirmed_move_searches_fewer_nodes() {
+        let game = Game::new(TimeControl::rapid());
+
+        // The engine plays black, so it ponders while white is to move.
+        let mut pondering = Anthony::new(Color::Black, TimeControl::rapid());
+        pondering.ponder = true;
+        pondering.ponder(&game);
+
+        // The opponent plays the move the engine predicted.
+        let (predicted, _) =
+            pondering.search_move(game.board(), Color::White, Anthony::SEARCH_DEPTH);
+        let mut game = game;
+        game.apply_action(Action::Move(predicted.unwrap()), Color::White)
+            .unwrap();
+
+        let mut cold = Anthony::new(Color::Black, TimeControl::rapid());
+        let cold_action = cold.get_action(&game);
+        let pondered_action = pondering.get_action(&game);
+
+        assert_eq!(pondered_action, cold_action);
+        assert!(
+            pondering.nodes() < cold.nodes(),
+            "{} vs {}",
+            pondering.nodes(),
+            cold.nodes()
+        );
+    }
+
+    #[test]
+    fn multipv_ranks_the_best_captures_in_order() {
+        let mut board =
+            Board::new_minimal(Vec2::new(0, 0).unwrap(), Vec2::new(10, 8).unwrap()).unwrap();
+        let queen = Vec2::CENTER;
+        let rook = Vec2::new(7, 6).unwrap();
+        let bishop = Vec2::new(5, 3).unwrap();
+        board.get_mut(queen, Color::White).replace(Piece::Queen);
+        board.get_mut(rook, Color::Black).replace(Piece::Rook);
+        board.get_mut(bishop, Color::Black).replace(Piece::Bishop);
+
+        let game = Game::from_position(board, TimeControl::rapid()).unwrap();
+        let mut engine = Anthony::new(Color::White, TimeControl::rapid());
+        let analysis = engine.analyze_multipv(&game, 3);
+
+        let destinations = analysis
+            .iter()
+            .map(|(mov, _)| mov.destination())
+            .collect::<Vec<_>>();
+        assert_eq!(analysis.len(), 3);
+        assert_eq!(destinations[..2], [rook, bishop]);
+        assert!(analysis.windows(2).all(|w| w[0].1 >= w[1].1));
+
+        let (best, _) = engine.search_game(&game, Anthony::SEARCH_DEPTH);
+        assert_eq!(best, Some(analysis[0].0));
+    }
+
+    #[test]
+    fn node_limit_bounds_the_search() {
+        let game = Game::new(TimeControl::rapid());
+        let config = AnthonyConfig {
+            max_nodes: Some(5),
+            ..Default::default()
+        };
+
+        let mut engine = Anthony::with_config(Color::White, config);
+        let action = engine.get_action(&game);
+        assert!(engine.nodes() <= 5, "{}", engine.nodes());
+        assert!(game.clone().apply_action(action, Color::White).is_ok());
+
+        // The search doesn't depend on how long it takes.
+        let mut other = Anthony::with_config(Color::White, config);
+        assert_eq!(other.get_action(&game), action);
+        assert_eq!(other.nodes(), engine.nodes());
+    }
+
+    #[test]
+    fn every_search_gets_the_whole_node_limit() {
+        let board =
+            Board::new_minimal(Vec2::new(0, 0).unwrap(), Vec2::new(10, 10).unwrap()).unwrap();
+        let endgame = Game::from_position(board, TimeControl::rapid()).unwrap();
+
+        let mut fresh = Anthony::new(Color::White, TimeControl::rapid());
+        let analysis = fresh.analyze(&endgame);
+        let config = AnthonyConfig {
+            max_nodes: Some(fresh.nodes()),
+            ..Default::default()
+        };
+
+        // The starting position needs more nodes than the endgame, so this uses them all up.
+        let mut engine = Anthony::with_config(Color::White, config);
+        engine.get_action(&Game::new(TimeControl::rapid()));
+        assert!(engine.out_of_nodes);
+
+        assert_eq!(engine.analyze(&endgame), analysis);
+        assert!(!engine.out_of_nodes);
+        assert_eq!(engine.nodes(), fresh.nodes());
+    }
+
+    #[test]
+    fn aggressive_personality_sacrifices_material_to_attack() {
+        let mut board =
+            Board::new_minimal(Vec2::new(0, 0).unwrap(), Vec2::new(10, 10).unwrap()).unwrap();
+        let queen = Vec2::new(1, 3).unwrap();
+        let knight = Vec2::new(9, 8).unwrap();
+        board.get_mut(queen, Color::White).replace(Piece::Queen);
+        board.get_mut(knight, Color::White).replace(Piece::Knight);
+        board
+            .get_mut(Vec2::new(3, 8).unwrap(), Color::Black)
+            .replace(Piece::Rook);
+        let game = Game::from_position(board, TimeControl::rapid()).unwrap();
+
+        // The knight is attacked by the rook, so the materialistic engine saves it...
+        let mut materialistic = Anthony::with_personality(Color::White, Personality::Materialistic);
+        let (mov, _) = materialistic.search_game(&game, Anthony::SEARCH_DEPTH);
+        assert_eq!(mov.unwrap().origin(), knight);
+
+        // ...while the aggressive one leaves it hanging to check the king with the queen.
+        let mut aggressive = Anthony::with_personality(Color::White, Personality::Aggressive);
+        let (mov, _) = aggressive.search_game(&game, Anthony::SEARCH_DEPTH);
+        let mov = mov.unwrap();
+        assert_eq!(mov.origin(), queen);
+        assert!(game.board().gives_check(mov, Color::White));
+    }
+
+    #[test]
+    fn analysis_is_from_whites_perspective() {
+        let mut board =
+            Board::new_minimal(Vec2::new(0, 0).unwrap(), Vec2::new(10, 10).unwrap()).unwrap();
+        board
+            .get_mut(Vec2::new(2, 4).unwrap(), Color::White)
+            .replace(Piece::Knight);
+        let mut game = Game::from_position(board, TimeControl::rapid()).unwrap();
+
+        // White is a knight up, both when white and when black is to move.
+        let mut engine = Anthony::new(Color::Black, TimeControl::rapid());
+        let analysis = engine.analyze(&game);
+        assert!(analysis.score > 0.0);
+
+        let action = Action::Move(analysis.best_move.unwrap());
+        game.apply_action(action, Color::White).unwrap();
+        assert!(engine.analyze(&game).score > 0.0);
     }
 }
@@ -1,2 +1,5 @@
 mod anthony;
-pub use anthony::Anthony;
+mod personality;
+
+pub use anthony::{Anthony, AnthonyConfig};
+pub use personality::{EvalWeights, Personality};
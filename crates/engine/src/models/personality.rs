@@ -0,0 +1,91 @@
+use std::fmt;
+
+/// How much each term of the evaluation of [`Anthony`](super::Anthony) is worth.
+///
+/// Every term is computed from white's perspective and multiplied by its weight, so a weight of
+/// `0.0` disables the term (and skips computing it).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EvalWeights {
+    /// The difference in [value](chessagon_core::piece::Piece::value) of the pieces of each
+    /// color, minus bishops that are redundant with another one.
+    pub material: f64,
+
+    /// A penalty of 100 when white is in check and a bonus of 200 when black is.
+    pub checks: f64,
+
+    /// The difference in how many pieces of each color are next to their own
+    /// [king](chessagon_core::Board::king_zone).
+    pub king_safety: f64,
+
+    /// The difference in how many tiles around the opponent's king each color attacks.
+    pub king_attack: f64,
+}
+
+/// A named preset of [`EvalWeights`], to give [`Anthony`](super::Anthony) some character.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Personality {
+    /// Cares about material and checks, which is how the engine always played.
+    #[default]
+    Balanced,
+
+    /// Goes after the opponent's king, even at the cost of some material.
+    Aggressive,
+
+    /// Keeps pieces close to its own king.
+    Defensive,
+
+    /// Only counts material.
+    Materialistic,
+}
+
+impl Personality {
+    /// Every personality, in the order they are shown to players.
+    pub const ALL: [Personality; 4] = [
+        Personality::Balanced,
+        Personality::Aggressive,
+        Personality::Defensive,
+        Personality::Materialistic,
+    ];
+
+    /// The weights of the evaluation terms for this personality.
+    pub const fn weights(self) -> EvalWeights {
+        match self {
+            Personality::Balanced => EvalWeights {
+                material: 1.0,
+                checks: 1.0,
+                king_safety: 0.0,
+                king_attack: 0.0,
+            },
+            Personality::Aggressive => EvalWeights {
+                material: 1.0,
+                checks: 2.0,
+                king_safety: 0.0,
+                king_attack: 2.0,
+            },
+            Personality::Defensive => EvalWeights {
+                material: 1.0,
+                checks: 1.0,
+                king_safety: 1.0,
+                king_attack: 0.0,
+            },
+            Personality::Materialistic => EvalWeights {
+                material: 1.0,
+                checks: 0.0,
+                king_safety: 0.0,
+                king_attack: 0.0,
+            },
+        }
+    }
+}
+
+impl fmt::Display for Personality {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Personality::Balanced => "Balanced",
+            Personality::Aggressive => "Aggressive",
+            Personality::Defensive => "Defensive",
+            Personality::Materialistic => "Materialistic",
+        })
+    }
+}
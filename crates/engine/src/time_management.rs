@@ -0,0 +1,117 @@
+use std::time::Duration;
+
+use chessagon_core::{Color, Game};
+
+/// Policy for how much time an engine should spend thinking on a move.
+///
+/// The budget is the remaining time (minus a safety buffer) divided over the estimated number
+/// of moves left, plus most of the increment. It is reduced in the opening, where moves are
+/// less critical, and capped to a fraction of the remaining time, so that the engine never
+/// flags by spending too much on a single move.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimeManagement {
+    /// Time that is never used, to account for overhead outside of the search.
+    pub safety_buffer: Duration,
+
+    /// How many moves (of each player) a game is expected to last.
+    pub expected_game_length: u32,
+
+    /// The least amount of moves that are assumed to be left, even late into the game.
+    pub min_moves_left: u32,
+
+    /// How many moves (of each player) are considered to be the opening.
+    pub opening_length: u32,
+
+    /// The fraction of the budget that is used during the opening.
+    pub opening_factor: f64,
+
+    /// The fraction of the increment that is added to the budget.
+    pub increment_factor: f64,
+
+    /// The maximum fraction of the usable remaining time to spend on a single move.
+    pub max_fraction: f64,
+}
+
+impl Default for TimeManagement {
+    fn default() -> Self {
+        Self {
+            safety_buffer: Duration::from_millis(500),
+            expected_game_length: 40,
+            min_moves_left: 10,
+            opening_length: 10,
+            opening_factor: 0.5,
+            increment_factor: 0.8,
+            max_fraction: 0.2,
+        }
+    }
+}
+
+impl TimeManagement {
+    /// How long to think on the next move, given the time remaining, the increment and how many
+    /// moves the player has already played.
+    pub fn budget(&self, remaining: Duration, increment: Duration, moves_played: u32) -> Duration {
+        let usable = remaining.saturating_sub(self.safety_buffer);
+
+        let moves_left = self
+            .expected_game_length
+            .saturating_sub(moves_played)
+            .max(self.min_moves_left)
+            .max(1);
+
+        let mut base = usable / moves_left;
+        if moves_played < self.opening_length {
+            base = base.mul_f64(self.opening_factor);
+        }
+
+        base.saturating_add(increment.mul_f64(self.increment_factor))
+            .min(usable.mul_f64(self.max_fraction))
+    }
+
+    /// The [budget](Self::budget) for the next move of `color` in the given game.
+    pub fn budget_for(&self, game: &Game, color: Color) -> Duration {
+        let moves_played = game.moves_from(color).count() as u32;
+        self.budget(
+            game.time_remaining(color),
+            game.time_control().increment[color],
+            moves_played,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::TimeManagement;
+
+    #[test]
+    fn low_on_time_budget_is_well_under_remaining() {
+        let policy = TimeManagement::default();
+        let remaining = Duration::from_secs(10);
+        let increment = Duration::from_secs(2);
+
+        let budget = policy.budget(remaining, increment, 25);
+        assert!(budget < remaining / 4, "{budget:?}");
+
+        // Even after thinking for the whole budget, the increment makes up for most of it.
+        assert!(budget >= increment.mul_f64(policy.increment_factor));
+        assert!(remaining - budget + increment > remaining / 2);
+    }
+
+    #[test]
+    fn never_uses_the_safety_buffer() {
+        let policy = TimeManagement::default();
+        let budget = policy.budget(Duration::from_millis(300), Duration::from_secs(2), 30);
+        assert_eq!(budget, Duration::ZERO);
+    }
+
+    #[test]
+    fn spends_more_in_the_middlegame_than_in_the_opening() {
+        let policy = TimeManagement::default();
+        let remaining = Duration::from_secs(180);
+
+        let opening = policy.budget(remaining, Duration::ZERO, 2);
+        let middlegame = policy.budget(remaining, Duration::ZERO, 20);
+        assert!(opening < middlegame, "{opening:?} vs {middlegame:?}");
+    }
+}
@@ -0,0 +1,78 @@
+//! Checks that the rules of chessagon work without `std`.
+//!
+//! This crate is `no_std` and depends on `chessagon-core` without default features, so it only
+//! builds if the rules of movement only need `core` and `alloc`. Run it on its own, since
+//! otherwise cargo unifies the features of `chessagon-core` with the rest of the workspace:
+//!
+//! ```sh
+//! cargo test -p chessagon-no-std
+//! ```
+
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use chessagon_core::{Board, Color};
+
+/// Counts the positions reached after every sequence of `depth` legal moves, starting with
+/// `color` to move (see [perft](https://www.chessprogramming.org/Perft)).
+pub fn perft(board: &Board, color: Color, depth: u32) -> u64 {
+    if depth == 0 {
+        return 1;
+    }
+
+    let mut moves = Vec::new();
+    board.collect_moves_into(color, &mut moves);
+    if depth == 1 {
+        return moves.len() as u64;
+    }
+
+    moves
+        .into_iter()
+        .map(|mov| {
+            let mut board = board.clone();
+            board.apply_move_unchecked(mov, color);
+            perft(&board, color.other(), depth - 1)
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use chessagon_core::{Board, BoardStatus, Color, Vec2, piece::Piece};
+
+    use super::perft;
+
+    #[test]
+    fn perft_matches_possible_moves() {
+        let board = Board::default();
+        assert_eq!(
+            perft(&board, Color::White, 1),
+            board.possible_moves(Color::White).count() as u64
+        );
+        assert!(perft(&board, Color::White, 2) > perft(&board, Color::White, 1));
+    }
+
+    #[test]
+    fn moves_are_applied_and_checked() {
+        let mut board = Board::default();
+        let mov = board.possible_moves(Color::White).next().unwrap();
+        board.apply_move(mov, Color::White).unwrap();
+        assert_eq!(board.turn(), Color::Black);
+        assert!(board.apply_move(mov, Color::White).is_err());
+
+        assert_eq!(Board::from_fen(&board.to_fen()), Ok(board));
+    }
+
+    #[test]
+    fn checkmate_is_detected() {
+        let mut board =
+            Board::new_minimal(Vec2::new(0, 0).unwrap(), Vec2::new(10, 10).unwrap()).unwrap();
+        board
+            .get_mut(Vec2::new(8, 8).unwrap(), Color::White)
+            .replace(Piece::Queen);
+
+        assert_eq!(board.status(Color::Black), BoardStatus::Checkmate);
+    }
+}
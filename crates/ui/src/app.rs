@@ -1,9 +1,11 @@
 use egui::{Align, FontFamily, Layout, RichText, Vec2};
 
 use crate::{
-    ColorScheme,
+    ColorScheme, Settings,
     color_scheme::ColorSchemeRgba,
     components,
+    editor::BoardEditor,
+    files,
     game::{self, GameOrInitGameScreen, GameScreen, GameScreenEvent},
     main_menu::MainMenu,
 };
@@ -13,10 +15,23 @@ use crate::{
 pub struct App {
     screen: Screen,
     color_scheme: ColorScheme,
+    settings: Settings,
 
     #[serde(skip)]
     main_menu_screen: MainMenu,
     game_screen: GameOrInitGameScreen,
+
+    #[serde(skip)]
+    editor: BoardEditor,
+
+    /// An error from saving or loading a game, shown until it's dismissed.
+    #[serde(skip)]
+    file_error: Option<String>,
+
+    /// The transcript being pasted to load a game, on the web (where there are no files).
+    #[cfg(target_arch = "wasm32")]
+    #[serde(skip)]
+    pasted_transcript: Option<String>,
     // #[serde(skip)]
     // toasts: egui_notify::Toasts,
 }
@@ -27,6 +42,7 @@ pub enum Screen {
     MainMenu,
     Options,
     Game,
+    Editor,
 }
 
 impl App {
@@ -37,7 +53,8 @@ impl App {
         // TODO: Maybe this should be passed by reference.
         App::set_style(cc, self.color_scheme);
 
-        self.game_screen.map_game(|game| game.connect());
+        let settings = &self.settings;
+        self.game_screen.map_game(|game| game.connect(settings));
     }
 }
 
@@ -104,6 +121,95 @@ impl App {
     }
 }
 
+/// Saving and loading games, see [`files`].
+impl App {
+    #[cfg(not(target_arch = "wasm32"))]
+    fn save_game(&mut self, _ctx: &egui::Context) {
+        let GameOrInitGameScreen::Game(game_screen) = &self.game_screen else {
+            return;
+        };
+
+        if let Err(err) = files::save_game(&game_screen.game) {
+            self.file_error = Some(format!("Couldn't save the game: {err}"));
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn save_game(&mut self, ctx: &egui::Context) {
+        if let GameOrInitGameScreen::Game(game_screen) = &self.game_screen {
+            ctx.copy_text(game_screen.game.to_transcript());
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn load_game(&mut self, frame: &mut eframe::Frame) {
+        match files::load_game() {
+            Ok(Some(game)) => self.review_game(frame, game),
+            Ok(None) => (),
+            Err(err) => self.file_error = Some(err.to_string()),
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn load_game(&mut self, _frame: &mut eframe::Frame) {
+        self.pasted_transcript = Some(String::new());
+    }
+
+    /// Opens a loaded game in review mode.
+    fn review_game(&mut self, frame: &mut eframe::Frame, game: chessagon_core::Game) {
+        // TODO: Maybe we shouldn't unwrap here.
+        self.game_screen =
+            GameOrInitGameScreen::Game(Box::new(GameScreen::review(frame, game).unwrap()));
+        self.screen = Screen::Game;
+    }
+
+    /// Draws the windows to paste a transcript (on the web) and to show errors.
+    fn draw_file_windows(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
+        #[cfg(target_arch = "wasm32")]
+        if let Some(transcript) = &mut self.pasted_transcript {
+            let mut load = false;
+            let mut open = true;
+            egui::Window::new("Load game")
+                .open(&mut open)
+                .collapsible(false)
+                .show(ctx, |ui| {
+                    ui.label("Paste the transcript of a game:");
+                    ui.text_edit_multiline(transcript);
+                    load = ui.add(components::button("Load")).clicked();
+                });
+
+            if load {
+                match files::parse_game(transcript) {
+                    Ok(game) => self.review_game(frame, game),
+                    Err(err) => self.file_error = Some(format!("Invalid game: {err}")),
+                }
+            }
+
+            if load || !open {
+                self.pasted_transcript = None;
+            }
+        }
+
+        #[cfg(not(target_arch = "wasm32"))]
+        let _ = frame;
+
+        if let Some(err) = &self.file_error {
+            let mut dismissed = false;
+            egui::Window::new("Error")
+                .collapsible(false)
+                .resizable(false)
+                .show(ctx, |ui| {
+                    ui.label(err);
+                    dismissed = ui.add(components::button("Ok")).clicked();
+                });
+
+            if dismissed {
+                self.file_error = None;
+            }
+        }
+    }
+}
+
 // impl<GS: fmt::Debug> fmt::Debug for App<GS> {
 //     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
 //         f.debug_struct("App")
@@ -147,6 +253,7 @@ impl eframe::App for App {
                 for (text, screen) in [
                     ("Main menu", Screen::MainMenu),
                     ("Game", Screen::Game),
+                    ("Editor", Screen::Editor),
                     ("Options", Screen::Options),
                 ] {
                     let menu_button = ui.add(components::button(
@@ -163,16 +270,36 @@ impl eframe::App for App {
                         self.screen = screen
                     }
                 }
+
+                ui.menu_button("File", |ui| {
+                    let playing = matches!(self.game_screen, GameOrInitGameScreen::Game(_));
+                    if ui
+                        .add_enabled(playing, egui::Button::new("Save game"))
+                        .clicked()
+                    {
+                        self.save_game(ctx);
+                        ui.close_menu();
+                    }
+
+                    if ui.button("Load game").clicked() {
+                        self.load_game(frame);
+                        ui.close_menu();
+                    }
+                });
             });
         });
 
+        self.draw_file_windows(ctx, frame);
+
         egui::CentralPanel::default().show(ctx, |ui| {
             match self.screen {
                 Screen::MainMenu => {
                     ui.with_layout(
                         Layout::top_down(Align::Center).with_cross_align(Align::Center),
                         |ui| {
-                            if let Some(switch_to) = self.main_menu_screen.draw(ui) {
+                            if let Some(switch_to) =
+                                self.main_menu_screen.draw(ui, &self.color_scheme)
+                            {
                                 self.screen = switch_to;
                             }
                         },
@@ -180,11 +307,17 @@ impl eframe::App for App {
                 }
 
                 Screen::Options => {
-                    ui.label("Work in progress.");
+                    self.settings.draw(ui);
+                }
+                Screen::Editor => {
+                    if let Some(board) = self.editor.draw(ui, frame) {
+                        self.game_screen = GameOrInitGameScreen::from_position(board);
+                        self.screen = Screen::Game;
+                    }
                 }
                 Screen::Game => match &mut self.game_screen {
                     GameOrInitGameScreen::Game(game_screen) => {
-                        let event = game_screen.draw(ui, ctx);
+                        let event = game_screen.draw(ui, ctx, &self.settings);
                         match event {
                             None => (),
                             Some(GameScreenEvent::Reset) => {
@@ -192,12 +325,23 @@ impl eframe::App for App {
                             }
                         }
                     }
-                    GameOrInitGameScreen::InitGame { time_control } => {
-                        if game::draw_init_game_screen(ui, time_control) {
+                    GameOrInitGameScreen::InitGame {
+                        time_control,
+                        mode,
+                        position,
+                    } => {
+                        if game::draw_init_game_screen(ui, time_control, mode, position) {
                             // TODO: Maybe we shouldn't unwrap here.
-                            self.game_screen = GameOrInitGameScreen::Game(
-                                GameScreen::new(frame, *time_control).unwrap(),
-                            )
+                            self.game_screen = GameOrInitGameScreen::Game(Box::new(
+                                GameScreen::new(
+                                    frame,
+                                    *time_control,
+                                    *mode,
+                                    position.take().map(|board| *board),
+                                    &self.settings,
+                                )
+                                .unwrap(),
+                            ))
                         }
                     }
                 },
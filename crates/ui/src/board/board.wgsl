@@ -7,10 +7,10 @@ struct VertexOut {
 struct Uniforms {
     color_scheme: ColorScheme,
 
-    // Really, we just need 91 bytes for this. However, wgsl needs
-    // to have uniforms be 16-byte aligned and 16-byte stride. 92 `u32`s
-    // would align but wouldn't have correct stride. So we need to use
-    // `vec4u`s for the stride
+    // Really, we just need 91 `u32`s for this (`NUMBER_OF_TILES`). However,
+    // wgsl needs to have uniforms be 16-byte aligned and 16-byte stride. 92
+    // `u32`s would align but wouldn't have correct stride. So we need to use
+    // `vec4u`s for the stride. Must match `SHADER_TILE_FLAG_VECTORS` in `./mod.rs`.
     tile_flags: array<vec4u, 23>,
     time_since_last_click: f32,
     _padding1: f32,
@@ -27,6 +27,14 @@ struct ColorScheme {
 
 const SELECTED: u32 = 1 << 0;
 const HIGHLIGHTED: u32 = 1 << 1;
+const CURSOR: u32 = 1 << 2;
+const HOVER: u32 = 1 << 3;
+const HINT: u32 = 1 << 4;
+const CHECK: u32 = 1 << 5;
+
+// Tint of the tile of a king in check. It is not part of the color scheme, since it should read
+// as a warning regardless of the colors of the board.
+const CHECK_COLOR: vec4f = vec4f(0.85, 0.15, 0.15, 0.6);
 
 @group(0) @binding(0)
 var<uniform> uniforms: Uniforms;
@@ -48,6 +56,24 @@ fn fs_main(in: VertexOut) -> @location(0) vec4f {
     out = uniforms.color_scheme.tiles[index];
 
     let flags = get_flags(position);
+
+    // The check tint is part of the tile itself, so the selection and highlights go on top of it.
+    if (flags & CHECK) != 0 {
+        out = CHECK_COLOR * CHECK_COLOR.w + out * (1.0 - CHECK_COLOR.w);
+    }
+
+    // The keyboard cursor is drawn as a ring on the border of the tile.
+    if (flags & CURSOR) != 0 && distance(in.uv, center) > APOTHEM * 0.8 {
+        return uniforms.color_scheme.selected;
+    }
+
+    // Hints are drawn as a ring too, but in the highlight color and slightly inside, so that they
+    // can be told apart from the cursor and stay visible over the selection.
+    let distance_to_center = distance(in.uv, center);
+    if (flags & HINT) != 0 && distance_to_center > APOTHEM * 0.65 && distance_to_center < APOTHEM * 0.85 {
+        return vec4f(uniforms.color_scheme.highlighted.xyz, 1.0);
+    }
+
     if (flags & SELECTED) != 0 {
         let alpha = uniforms.color_scheme.selected.w;
         return uniforms.color_scheme.selected * alpha + out * (1.0 - alpha);
@@ -66,6 +92,12 @@ fn fs_main(in: VertexOut) -> @location(0) vec4f {
         return uniforms.color_scheme.highlighted * alpha + out * (1.0 - alpha);
     }    
 
+    // Hovering shows the moves of a piece more faintly than selecting it, and without fading in.
+    if (flags & HOVER) != 0 {
+        let alpha = uniforms.color_scheme.highlighted.w * 0.5;
+        return uniforms.color_scheme.highlighted * alpha + out * (1.0 - alpha);
+    }
+
     return out;
 }
 
@@ -88,6 +120,9 @@ const STEP_SIZE: vec2f = vec2f(
 ) * 2.0 / f32(MAX_RANK / 2 + 1); // Board takes up more space vertically, so we need to adjust based on the number of ranks.
 const OFFSET: vec2f = STEP_SIZE / 2.0;
 
+// The distance from the center of a tile to its edges.
+const APOTHEM: f32 = 1.0 / f32(MAX_RANK / 2 + 1);
+
 /// Returns the nearest center of a grid centered on `grid_center`
 //
 // Possible values are `grid_center + vec2f(x * step_size.x, y * step_size.y)` for any value `x` and `y`.
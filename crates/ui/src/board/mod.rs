@@ -1,4 +1,4 @@
-mod piece;
+pub(crate) mod piece;
 mod wgpu;
 
 // For when I add new backends
@@ -9,9 +9,12 @@ pub mod gpu {
 use std::time::SystemTime;
 
 use bytemuck::{Pod, Zeroable};
-use chessagon_core::{Board, Color, Move, Vec2};
+use chessagon_core::{
+    Board, Color, Move, Vec2,
+    piece::{Piece, pawn},
+};
 use eframe::egui_wgpu;
-use egui::{Pos2, Rect, Ui, pos2, vec2};
+use egui::{Align2, FontId, Key, Modifiers, Pos2, Rect, Ui, pos2, vec2};
 use piece::GuiPiece;
 use wgpu::CustomBoardCallback;
 
@@ -26,21 +29,65 @@ pub struct GuiBoard {
     /// A vector of pieces, current position
     pieces: Vec<GuiPiece>,
 
-    /// How fast do pieces move in general, when not dragging
+    /// How fast do pieces move in general, when not dragging.
+    ///
+    /// Given as the fraction of the remaining distance covered each frame at 60fps.
     piece_move_speed: f32,
 
     /// How fast do pieces move towards cursor when dragging.
+    ///
+    /// Given as the fraction of the remaining distance covered each frame at 60fps.
     piece_drag_speed: f32,
 
     /// The time the last click happened, used for passing in to uniforms, for gpu animations.
     last_click_time: SystemTime,
 
+    /// The tile the keyboard cursor is on, if the keyboard has been used.
+    #[serde(skip)]
+    cursor: Option<Vec2>,
+
     // To check whether keydown is click or hold.
     #[serde(skip)]
     pointer_pressed_last_frame: bool,
 
     #[serde(skip)]
     uniforms: Uniforms,
+
+    #[serde(skip)]
+    color_scheme: ColorSchemeCache,
+
+    /// Whether to draw a [badge](piece::draw_badge) on each piece.
+    #[serde(skip)]
+    piece_badges: bool,
+
+    /// Whether the [coordinate labels](coordinate_label) are from black's side of the board.
+    #[serde(skip)]
+    flip_coordinates: bool,
+
+    /// The friendly piece under the pointer, if there is one and nothing is selected.
+    #[serde(skip)]
+    hovered_tile: Option<Vec2>,
+
+    /// The destinations of the piece at [`Self::hovered_tile`].
+    #[serde(skip)]
+    hover_highlights: Vec<Vec2>,
+
+    /// Tiles pointed at by external code (e.g., puzzle hints), see [`Self::set_hints`].
+    #[serde(skip)]
+    hints: Vec<Vec2>,
+
+    /// Whether [`Self::hints`] are cleared when deselecting.
+    #[serde(skip)]
+    clear_hints_on_deselect: bool,
+
+    /// The tile of the king of the side to move, if it is in check.
+    #[serde(skip)]
+    checked_king: Option<Vec2>,
+
+    /// A promotion that waits for the player to choose the piece, see
+    /// [`Self::draw_promotion_picker`].
+    #[serde(skip)]
+    pending_promotion: Option<Move>,
 }
 
 impl Default for GuiBoard {
@@ -52,8 +99,18 @@ impl Default for GuiBoard {
             piece_move_speed: 0.2,
             piece_drag_speed: 0.8,
             last_click_time: SystemTime::now(),
+            cursor: None,
             pointer_pressed_last_frame: false,
             uniforms: Uniforms::default(),
+            color_scheme: ColorSchemeCache::default(),
+            piece_badges: false,
+            flip_coordinates: false,
+            hovered_tile: None,
+            hover_highlights: Vec::new(),
+            hints: Vec::new(),
+            clear_hints_on_deselect: false,
+            checked_king: None,
+            pending_promotion: None,
         }
     }
 }
@@ -76,12 +133,53 @@ impl GuiBoard {
         })
     }
 
+    /// Sets how fast pieces move and are dragged, see [`Settings::animation_speeds`].
+    ///
+    /// [`Settings::animation_speeds`]: crate::Settings::animation_speeds
+    pub fn set_animation_speeds(&mut self, move_speed: f32, drag_speed: f32) {
+        self.piece_move_speed = move_speed;
+        self.piece_drag_speed = drag_speed;
+    }
+
+    /// Sets whether to draw a [badge](piece::draw_badge) with the letter of each piece, see
+    /// [`Settings::piece_badges`].
+    ///
+    /// [`Settings::piece_badges`]: crate::Settings::piece_badges
+    pub fn set_piece_badges(&mut self, enabled: bool) {
+        self.piece_badges = enabled;
+    }
+
+    /// Sets the side from which the coordinates on the edge of the board are labeled, see
+    /// [`Settings::notation_perspective`].
+    ///
+    /// [`Settings::notation_perspective`]: crate::Settings::notation_perspective
+    pub fn set_coordinate_perspective(&mut self, perspective: Color) {
+        self.flip_coordinates = perspective == Color::Black;
+    }
+
+    /// Marks the given tiles with a hint, replacing the previous hints.
+    ///
+    /// Hints are drawn independently of the selection, so that other code (such as tutorials or
+    /// puzzles) can point at tiles. Pass an empty slice to remove them.
+    pub fn set_hints(&mut self, tiles: &[Vec2]) {
+        self.hints = tiles.to_vec();
+    }
+
+    /// Sets whether the [hints](Self::set_hints) are removed when the player deselects a piece
+    /// (which includes making a move). By default, they stay until they are replaced.
+    pub fn set_clear_hints_on_deselect(&mut self, enabled: bool) {
+        self.clear_hints_on_deselect = enabled;
+    }
+
     /// Updates the board. Currently, this entails:
     /// - Updating the selection.
+    /// - Forgetting the hover highlights, which get recomputed when drawing.
     pub fn update(&mut self, board: &Board, color: Color, ctx: &egui::Context) {
         if let Some((selected_tile, _)) = self.selected_tile {
             self.select(board, selected_tile, color, ctx);
         }
+        self.hovered_tile = None;
+        self.hover_highlights.clear();
         ctx.request_repaint();
     }
 }
@@ -121,13 +219,76 @@ fn uv_to_hex(uv: Pos2) -> Option<Vec2> {
     // Solving for x and y
     // => `2y = (pointer_pos.x - origin.x) / step_size.x + (pointer_pos.y - origin.y) / step_size.y` same for `x`
     let step_size = vec2(f32::sqrt(3.0), -1.0) * APOTHEM;
-    let n_x = ((uv.x - POSITION_00.x) / step_size.x).round() as i8;
-    let n_y = ((uv.y - POSITION_00.y) / step_size.y).round() as i8;
+    let u = (uv.x - POSITION_00.x) / step_size.x;
+    let v = (uv.y - POSITION_00.y) / step_size.y;
+
+    let y = (u + v) / 2.0;
+    let x = (v - u) / 2.0;
+
+    // Rounding `x` and `y` independently doesn't give the nearest hexagon, since hexagons are not
+    // squares. Instead, we convert to cube coordinates (three axes, one per neighbor direction,
+    // that sum up to zero) and round there. The neighbors `[1, 0]`, `[0, 1]` and `[1, 1]` are at
+    // cube coordinates `(1, 0, -1)`, `(0, -1, 1)` and `(1, -1, 0)` respectively.
+    let (x, y) = cube_round(x, -y, y - x);
+    let (x, y) = (x, -y);
 
-    let y = n_x.wrapping_add(n_y) / 2;
-    let x = y.wrapping_sub(n_x);
+    Vec2::new(u8::try_from(x).ok()?, u8::try_from(y).ok()?)
+}
+
+/// Rounds fractional cube coordinates to the cube coordinates of the hexagon that contains them.
+///
+/// Returns the first two coordinates, since the third one can be deduced from them.
+fn cube_round(a: f32, b: f32, c: f32) -> (i32, i32) {
+    let (mut ra, mut rb, rc) = (a.round(), b.round(), c.round());
+    let (da, db, dc) = ((ra - a).abs(), (rb - b).abs(), (rc - c).abs());
+
+    // The coordinate with the biggest rounding error is the one that's off, so we recompute it
+    // from the others.
+    if da > db && da > dc {
+        ra = -rb - rc;
+    } else if db > dc {
+        rb = -ra - rc;
+    }
 
-    Vec2::new(x as u8, y as u8)
+    (ra as i32, rb as i32)
+}
+
+/// Moves the keyboard cursor one tile in the direction of the given arrow key.
+///
+/// Up and down go along the file. Left and right go to the neighboring file, alternating between
+/// the upper and lower neighbor depending on the rank, so that the cursor stays roughly at the
+/// same height.
+///
+/// Returns `None` if the key is not an arrow or if the cursor would leave the board.
+fn step_cursor(cursor: Vec2, key: Key) -> Option<Vec2> {
+    let step = |dx: i8, dy: i8| {
+        let x = u8::try_from(cursor.x() as i8 + dx).ok()?;
+        let y = u8::try_from(cursor.y() as i8 + dy).ok()?;
+        Vec2::new(x, y)
+    };
+
+    let go_up = cursor.rank().is_multiple_of(2);
+    match key {
+        Key::ArrowUp => step(1, 1),
+        Key::ArrowDown => step(-1, -1),
+        Key::ArrowLeft if go_up => step(1, 0).or_else(|| step(0, -1)),
+        Key::ArrowLeft => step(0, -1).or_else(|| step(1, 0)),
+        Key::ArrowRight if go_up => step(0, 1).or_else(|| step(-1, 0)),
+        Key::ArrowRight => step(-1, 0).or_else(|| step(0, 1)),
+        _ => None,
+    }
+}
+
+/// The label of `tile` on the edge of the board, as seen from the side of `perspective`: the `x`
+/// coordinate along the edge where `y` is 0 and the other way around, like the files and ranks
+/// along the edges of a chess board. Other tiles are not labeled.
+fn coordinate_label(tile: Vec2, perspective: Color) -> Option<u8> {
+    let oriented = tile.oriented(perspective);
+    match (oriented.x(), oriented.y()) {
+        (x, 0) => Some(x),
+        (0, y) => Some(y),
+        _ => None,
+    }
 }
 
 fn uv_to_screen(uv: Pos2, rect: Rect) -> Pos2 {
@@ -168,26 +329,215 @@ impl GuiBoard {
             self.deselect();
         }
 
-        board
-            .get_move(selected_tile, position, color)
-            .ok()
-            .map(|(mov, _meta)| mov)
+        let (mov, _meta) = board.get_move(selected_tile, position, color).ok()?;
+        if let Move::Promotion { .. } = mov {
+            // The move is made once the player chooses the piece.
+            self.pending_promotion = Some(mov);
+            return None;
+        }
+
+        Some(mov)
+    }
+
+    /// Makes the [pending promotion](Self::pending_promotion) with the given piece, if there is
+    /// one.
+    fn choose_promotion(&mut self, promoting_to: Piece) -> Option<Move> {
+        match self.pending_promotion.take()? {
+            Move::Promotion { file, captures, .. } => Some(Move::Promotion {
+                file,
+                captures,
+                promoting_to,
+            }),
+            mov => Some(mov),
+        }
+    }
+
+    /// Draws the pieces that the [pending promotion](Self::pending_promotion) can promote to,
+    /// over its destination, and returns the move once one of them is chosen.
+    ///
+    /// They can also be chosen with the keyboard, by their letter (e.g., `N` for a knight).
+    /// Pressing escape or clicking outside of the picker cancels the promotion.
+    fn draw_promotion_picker(
+        &mut self,
+        ctx: &egui::Context,
+        color: Color,
+        rect: Rect,
+    ) -> Option<Move> {
+        let destination = self.pending_promotion?.destination_for(color);
+        let hexagon_height = rect.height() / Board::NUMBER_OF_RANKS as f32;
+
+        let mut chosen = None;
+        let picker = egui::Area::new(egui::Id::new("promotion picker"))
+            .fixed_pos(uv_to_screen(hex_to_uv(destination), rect))
+            .pivot(Align2::CENTER_CENTER)
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        for piece in pawn::PROMOTION_PIECES {
+                            let icon = piece::icon(piece, color)
+                                .fit_to_exact_size(egui::Vec2::splat(hexagon_height * 0.85));
+                            let button = egui::Button::image(icon);
+                            if ui.add(button).on_hover_text(piece.name()).clicked() {
+                                chosen = Some(piece);
+                            }
+                        }
+                    });
+                });
+            })
+            .response;
+
+        for piece in pawn::PROMOTION_PIECES {
+            let key = Key::from_name(&piece.representing_letter().to_string());
+            if key.is_some_and(|key| ctx.input_mut(|i| i.consume_key(Modifiers::NONE, key))) {
+                chosen = Some(piece);
+            }
+        }
+
+        if let Some(piece) = chosen {
+            return self.choose_promotion(piece);
+        }
+
+        let pressed_elsewhere = ctx.input(|i| {
+            i.pointer.any_pressed()
+                && i.pointer
+                    .interact_pos()
+                    .is_some_and(|position| !picker.rect.contains(position))
+        });
+        if pressed_elsewhere || ctx.input_mut(|i| i.consume_key(Modifiers::NONE, Key::Escape)) {
+            self.pending_promotion = None;
+        }
+
+        ctx.request_repaint();
+        None
+    }
+
+    /// Drops the piece dragged from `origin` at `uv`, returning the move to make, if any.
+    ///
+    /// Only dropping on a highlighted tile makes a move. Dropping anywhere else cancels the drag:
+    /// the piece goes back to its origin without selecting whatever is under it.
+    fn drop_piece(
+        &mut self,
+        board: &Board,
+        color: Color,
+        origin: Vec2,
+        uv: Pos2,
+        ctx: &egui::Context,
+    ) -> Option<Move> {
+        let dropped_on = uv_to_hex(uv);
+
+        // Dropping the piece back on its origin (which is also what a plain click does) keeps it
+        // selected, so that it can be moved by clicking on the destination afterwards.
+        if dropped_on == Some(origin) {
+            return None;
+        }
+
+        tracing::debug!("Dropping piece at {uv}");
+        match dropped_on.filter(|tile| self.highlighted_tiles.contains(tile)) {
+            // Snap to the tile, since the piece might not be at its center.
+            Some(tile) => self.toggle_selection(board, color, hex_to_uv(tile), ctx),
+            None => {
+                self.deselect();
+                None
+            }
+        }
     }
 
     pub fn select(&mut self, board: &Board, position: Vec2, color: Color, ctx: &egui::Context) {
         self.selected_tile = Some((position, ctx.is_using_pointer()));
         self.highlighted_tiles = board
-            .possible_moves(color)
-            .filter(|mov| mov.origin() == position)
-            .map(|mov| mov.destination())
+            .moves_from(position, color)
+            .map(|mov| mov.destination_for(color))
             .collect();
+        // Promotions are yielded once for each piece, but they go to the same tile.
+        self.highlighted_tiles.dedup();
 
         self.last_click_time = SystemTime::now();
     }
 
     pub fn deselect(&mut self) {
         self.selected_tile = None;
+        self.pending_promotion = None;
         self.highlighted_tiles = Vec::new();
+        if self.clear_hints_on_deselect {
+            self.hints.clear();
+        }
+    }
+
+    /// Updates the hover highlights for the pointer being over `position`.
+    ///
+    /// Only pieces of the given color are highlighted, and only while nothing is selected, so
+    /// that the hover highlights don't get mixed up with the ones of the selection.
+    fn hover(&mut self, board: &Board, position: Option<Vec2>, color: Color) {
+        let position = position
+            .filter(|_| self.selected_tile.is_none())
+            .filter(|&position| board.get(position, color).is_some());
+
+        if position == self.hovered_tile {
+            return;
+        }
+
+        self.hovered_tile = position;
+        self.hover_highlights = position
+            .map(|position| {
+                let mut destinations = board
+                    .moves_from(position, color)
+                    .map(|mov| mov.destination_for(color))
+                    .collect::<Vec<_>>();
+                destinations.dedup();
+                destinations
+            })
+            .unwrap_or_default();
+    }
+
+    /// Handles keyboard input for the board.
+    ///
+    /// - Arrow keys move the cursor around.
+    /// - Enter or space select the piece under the cursor (or deselect it if it was already
+    ///   selected), or make a move to the cursor if it is a valid target for the selected piece.
+    /// - Escape deselects.
+    ///
+    /// Returns the move to make, if any.
+    pub fn handle_keyboard(
+        &mut self,
+        board: &Board,
+        color: Color,
+        ctx: &egui::Context,
+    ) -> Option<Move> {
+        let pressed = |key| ctx.input_mut(|i| i.consume_key(Modifiers::NONE, key));
+
+        for key in [
+            Key::ArrowUp,
+            Key::ArrowDown,
+            Key::ArrowLeft,
+            Key::ArrowRight,
+        ] {
+            if pressed(key) {
+                self.cursor = Some(match self.cursor {
+                    Some(cursor) => step_cursor(cursor, key).unwrap_or(cursor),
+                    None => self
+                        .selected_tile
+                        .map(|(tile, _)| tile)
+                        .unwrap_or(Vec2::CENTER),
+                });
+            }
+        }
+
+        if pressed(Key::Escape) {
+            self.deselect();
+        }
+
+        if pressed(Key::Enter) || pressed(Key::Space) {
+            let cursor = self.cursor?;
+            if self.selected_tile.map(|(tile, _)| tile) == Some(cursor) {
+                self.deselect();
+                return None;
+            }
+
+            return self.toggle_selection(board, color, hex_to_uv(cursor), ctx);
+        }
+
+        None
     }
 
     pub fn draw(
@@ -201,10 +551,27 @@ impl GuiBoard {
         let (id, rect) = ui.allocate_space(egui::Vec2::splat(size));
         let response = ui.interact(rect, id, egui::Sense::click_and_drag());
 
+        self.update_check(board);
         self.draw_board(ui, rect);
         self.draw_pieces(ui, ctx, board, size, rect);
 
-        let mov = (|| {
+        // The rest of the board waits until the piece of the promotion is chosen.
+        if self.pending_promotion.is_some() {
+            self.pointer_pressed_last_frame = response.is_pointer_button_down_on();
+            return self.draw_promotion_picker(ctx, color, rect);
+        }
+
+        if let Some(mov) = self.handle_keyboard(board, color, ctx) {
+            return Some(mov);
+        }
+
+        let hovered = ctx
+            .pointer_latest_pos()
+            .filter(|_| response.hovered())
+            .and_then(|position| uv_to_hex(screen_to_uv(position, rect)));
+        self.hover(board, hovered, color);
+
+        (|| {
             let pointer_position = ctx.pointer_latest_pos()?;
             let uv = screen_to_uv(pointer_position, rect);
             let hex = uv_to_hex(uv)?;
@@ -230,40 +597,75 @@ impl GuiBoard {
                 let selected_piece = self
                     .pieces
                     .iter_mut()
-                    .find(|p| p.hex_tile == *selected_tile)
+                    .find(|p| p.hex_tile == *selected_tile && !p.is_captured())
                     .expect("There should be a piece in the selected position");
 
-                selected_piece.move_towards(screen_to_uv(pointer_pos, rect), self.piece_drag_speed);
+                let dt = ctx.input(|i| i.stable_dt);
+                selected_piece.move_towards(
+                    screen_to_uv(pointer_pos, rect),
+                    self.piece_drag_speed,
+                    dt,
+                );
             }
 
-            if !clicking {
-                if let Some((selected_tile, dragging)) = &mut self.selected_tile {
-                    if *dragging {
-                        *dragging = false;
-                    }
-
-                    let selected_piece = self
-                        .pieces
-                        .iter()
-                        .find(|p| p.hex_tile == *selected_tile)
-                        .expect("There should be a piece in the selected position");
-
-                    if Some(*selected_tile) != uv_to_hex(selected_piece.position) {
-                        // drop the picked up piece
-                        tracing::debug!("Dropping piece at {}", selected_piece.position);
-                        return self.toggle_selection(board, color, selected_piece.position, ctx);
-                    }
-                };
+            if !clicking && let Some((selected_tile, dragging)) = &mut self.selected_tile {
+                if !std::mem::take(dragging) {
+                    return None;
+                }
+
+                let selected_piece = self
+                    .pieces
+                    .iter()
+                    .find(|p| p.hex_tile == *selected_tile && !p.is_captured())
+                    .expect("There should be a piece in the selected position");
+
+                let (origin, dropped_at) = (*selected_tile, selected_piece.position);
+                return self.drop_piece(board, color, origin, dropped_at, ctx);
             }
 
             None
-        })();
+        })()
+    }
+
+    /// Draws `board` without animations or moves, for [editing](crate::editor) it.
+    ///
+    /// Returns the tile that was clicked, if any.
+    pub fn draw_editor(&mut self, ui: &mut Ui, board: &Board) -> Option<Vec2> {
+        let size = ui.available_width().min(ui.available_height());
+        let (id, rect) = ui.allocate_space(egui::Vec2::splat(size));
+        let response = ui.interact(rect, id, egui::Sense::click());
+
+        let ctx = ui.ctx().clone();
+        self.deselect();
+        // Positions being edited don't need to be valid, so they might not have kings.
+        self.checked_king = None;
+        self.pieces = GuiPiece::from_board(board).collect();
+        self.draw_board(ui, rect);
+        self.draw_pieces(ui, &ctx, board, size, rect);
+
+        let hovered = response
+            .hover_pos()
+            .and_then(|position| uv_to_hex(screen_to_uv(position, rect)));
+        if hovered.is_some() {
+            ctx.set_cursor_icon(egui::CursorIcon::PointingHand);
+        }
+
+        response
+            .clicked()
+            .then(|| response.interact_pointer_pos())
+            .flatten()
+            .and_then(|position| uv_to_hex(screen_to_uv(position, rect)))
+    }
 
-        mov
+    /// Marks the king of the side to move with [`TileFlags::CHECK`] if it is in check, or
+    /// clears the mark if it isn't.
+    fn update_check(&mut self, board: &Board) {
+        let color = board.turn();
+        self.checked_king = board.in_check(color).map(|_| board.find_king(color));
     }
 
     pub fn draw_board(&mut self, ui: &mut Ui, rect: Rect) {
-        self.uniforms.tile_flags = <[TileFlags; 92]>::zeroed();
+        self.uniforms.tile_flags = <[TileFlags; TILE_FLAGS_LEN]>::zeroed();
         if let Some((selected_tile, _dragging_piece)) = self.selected_tile {
             *self.uniforms.get_flag(selected_tile) |= TileFlags::SELECTED;
         }
@@ -272,8 +674,25 @@ impl GuiBoard {
             *self.uniforms.get_flag(highlighted) |= TileFlags::HIGHLIGHTED;
         }
 
+        for &hovered in &self.hover_highlights {
+            *self.uniforms.get_flag(hovered) |= TileFlags::HOVER;
+        }
+
+        for &hint in &self.hints {
+            *self.uniforms.get_flag(hint) |= TileFlags::HINT;
+        }
+
+        if let Some(cursor) = self.cursor {
+            *self.uniforms.get_flag(cursor) |= TileFlags::CURSOR;
+        }
+
+        if let Some(king) = self.checked_king {
+            *self.uniforms.get_flag(king) |= TileFlags::CHECK;
+        }
+
         // TODO: Get this from configuration
-        self.uniforms.color_scheme = ColorScheme::purple().into_gamma_rgba();
+        self.color_scheme.update(ColorScheme::purple());
+        self.uniforms.color_scheme = self.color_scheme.rgba;
 
         self.uniforms.time_since_last_click = SystemTime::now()
             .duration_since(self.last_click_time)
@@ -286,6 +705,37 @@ impl GuiBoard {
                 uniforms: self.uniforms,
             },
         ));
+
+        self.draw_coordinates(ui, rect);
+    }
+
+    /// Draws the [coordinate labels](coordinate_label), on the outer side of the edge tiles.
+    fn draw_coordinates(&self, ui: &mut Ui, rect: Rect) {
+        let perspective = if self.flip_coordinates {
+            Color::Black
+        } else {
+            Color::White
+        };
+
+        let apothem = APOTHEM * rect.height();
+        let font = FontId::proportional(apothem * 0.5);
+        let color = ui.visuals().weak_text_color();
+        let center = uv_to_screen(hex_to_uv(Vec2::CENTER), rect);
+        for tile in Vec2::iter() {
+            let Some(label) = coordinate_label(tile, perspective) else {
+                continue;
+            };
+
+            let tile_center = uv_to_screen(hex_to_uv(tile), rect);
+            let outwards = (tile_center - center).normalized() * apothem * 0.6;
+            ui.painter().text(
+                tile_center + outwards,
+                Align2::CENTER_CENTER,
+                label,
+                font.clone(),
+                color,
+            );
+        }
     }
 
     pub fn draw_pieces(
@@ -301,30 +751,58 @@ impl GuiBoard {
 
         let hexagon_height = size / Board::NUMBER_OF_RANKS as f32;
         let piece_size = egui::Vec2::splat(hexagon_height * 0.85);
+        let dt = ctx.input(|i| i.stable_dt);
 
         for piece in &mut self.pieces {
             if Some((piece.hex_tile, true)) != self.selected_tile {
-                let moved = piece.move_towards_target(self.piece_move_speed);
+                let moved = piece.move_towards_target(self.piece_move_speed, dt);
                 if moved {
                     ctx.request_repaint();
                 }
             }
 
+            // Captured pieces shrink and fade out until they get removed.
+            let remaining = 1.0 - piece.capture_progress();
+            if piece.is_captured() {
+                ctx.request_repaint();
+            }
+
             let origin = uv_to_screen(piece.position, rect);
-            let piece_rect = Rect::from_center_size(origin, piece_size);
+            let piece_rect = Rect::from_center_size(origin, piece_size * remaining);
+
+            ui.put(
+                piece_rect,
+                piece::icon(piece.kind, piece.color)
+                    .tint(egui::Color32::WHITE.gamma_multiply(remaining)),
+            );
 
-            ui.put(piece_rect, piece::icon(piece.kind, piece.color));
+            if self.piece_badges {
+                piece::draw_badge(ui.painter(), piece_rect, piece.kind, piece.color, remaining);
+            }
         }
     }
 }
 
+/// The number of [`TileFlags`] in [`Uniforms`].
+///
+/// This is [`Board::NUMBER_OF_TILES`] rounded up to a multiple of 4, since the shader reads them
+/// as `vec4u`s to get a 16-byte stride.
+const TILE_FLAGS_LEN: usize = (Board::NUMBER_OF_TILES as usize).next_multiple_of(4);
+
+/// The size of `Uniforms::tile_flags` in the shader, as `array<vec4u, 23>`.
+const SHADER_TILE_FLAG_VECTORS: usize = 23;
+
+static_assertions::const_assert!(TILE_FLAGS_LEN >= Board::NUMBER_OF_TILES as usize);
+static_assertions::const_assert_eq!(TILE_FLAGS_LEN, 4 * SHADER_TILE_FLAG_VECTORS);
+static_assertions::const_assert_eq!(size_of::<Uniforms>() % 16, 0);
+
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Zeroable, Pod)]
 struct Uniforms {
-    // TODO: The color scheme should maybe be in different uniforms, because we don't need to update them
-    // on every draw.
+    /// The color scheme rarely changes, so it's only uploaded when it does (see
+    /// [`Uniforms::per_frame_bytes`]).
     color_scheme: ColorSchemeRgba,
-    tile_flags: [TileFlags; 92],
+    tile_flags: [TileFlags; TILE_FLAGS_LEN],
     time_since_last_click: f32,
     _padding: [f32; 3],
 }
@@ -333,7 +811,7 @@ impl Default for Uniforms {
     fn default() -> Self {
         Self {
             color_scheme: ColorScheme::default().into(),
-            tile_flags: <[TileFlags; 92]>::zeroed(),
+            tile_flags: <[TileFlags; TILE_FLAGS_LEN]>::zeroed(),
             time_since_last_click: 0.0,
             _padding: <[f32; 3]>::zeroed(),
         }
@@ -341,17 +819,343 @@ impl Default for Uniforms {
 }
 
 impl Uniforms {
+    /// The offset in bytes of the fields that change on every frame.
+    const PER_FRAME_OFFSET: usize = std::mem::offset_of!(Uniforms, tile_flags);
+
+    /// The bytes of the fields that change on every frame, which start at
+    /// [`Uniforms::PER_FRAME_OFFSET`].
+    fn per_frame_bytes(&self) -> &[u8] {
+        &bytemuck::bytes_of(self)[Self::PER_FRAME_OFFSET..]
+    }
+
     fn get_flag(&mut self, position: Vec2) -> &mut TileFlags {
         // TODO: This could eventually be `get_unchecked`.
         &mut self.tile_flags[Board::index(position)]
     }
 }
 
+// `wgpu` requires buffer writes to be aligned to 4 bytes.
+static_assertions::const_assert_eq!(Uniforms::PER_FRAME_OFFSET % 4, 0);
+
+/// The color scheme converted to what the shader expects, which is only recomputed when the
+/// color scheme changes.
+#[derive(Debug, Default)]
+struct ColorSchemeCache {
+    source: Option<ColorScheme>,
+    rgba: ColorSchemeRgba,
+}
+
+impl ColorSchemeCache {
+    /// Sets the color scheme, returning whether it had to be recomputed.
+    fn update(&mut self, scheme: ColorScheme) -> bool {
+        if self.source == Some(scheme) {
+            return false;
+        }
+
+        self.rgba = scheme.into_gamma_rgba();
+        self.source = Some(scheme);
+        true
+    }
+}
+
 bitflags::bitflags! {
     #[repr(transparent)]
     #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Zeroable, Pod, serde::Serialize, serde::Deserialize)]
     pub struct TileFlags: u32 {
         const SELECTED = (1 << 0);
         const HIGHLIGHTED = (1 << 1);
+        const CURSOR = (1 << 2);
+        const HOVER = (1 << 3);
+        const HINT = (1 << 4);
+        /// The king on the tile is in check.
+        const CHECK = (1 << 5);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unchanged_color_scheme_is_not_recomputed() {
+        let mut cache = ColorSchemeCache::default();
+        assert!(cache.update(ColorScheme::purple()));
+        let rgba = cache.rgba;
+
+        assert!(!cache.update(ColorScheme::purple()));
+        assert_eq!(cache.rgba, rgba);
+
+        let mut scheme = ColorScheme::purple();
+        scheme.background.v = 0.5;
+        assert!(cache.update(scheme));
+        assert_ne!(cache.rgba, rgba);
+    }
+
+    #[test]
+    fn per_frame_bytes_skip_the_color_scheme() {
+        let uniforms = Uniforms::default();
+        assert_eq!(
+            uniforms.per_frame_bytes().len(),
+            size_of::<Uniforms>() - size_of::<ColorSchemeRgba>()
+        );
+    }
+
+    #[test]
+    fn hex_to_uv_to_hex_is_identity() {
+        for position in Vec2::iter() {
+            assert_eq!(uv_to_hex(hex_to_uv(position)), Some(position));
+        }
+    }
+
+    #[test]
+    fn points_inside_a_hexagon_map_to_it() {
+        // The inscribed circle of a hexagon has radius `APOTHEM`, so every point closer than
+        // that to the center is inside of the hexagon.
+        for position in Vec2::iter() {
+            let center = hex_to_uv(position);
+            for i in 0..24 {
+                let angle = i as f32 / 24.0 * std::f32::consts::TAU;
+                let offset = egui::Vec2::angled(angle) * APOTHEM * 0.98;
+
+                assert_eq!(
+                    uv_to_hex(center + offset),
+                    Some(position),
+                    "{:?} should be in {position}",
+                    center + offset
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn uv_to_hex_picks_the_nearest_center_over_a_grid() {
+        const SAMPLES: usize = 100;
+
+        for i in 0..=SAMPLES {
+            for j in 0..=SAMPLES {
+                let uv = pos2(i as f32 / SAMPLES as f32, j as f32 / SAMPLES as f32);
+
+                // Brute force the nearest hexagon center, including ones off the board.
+                let mut distances = (-3..=13)
+                    .flat_map(|x| (-3..=13).map(move |y| (x, y)))
+                    .map(|(x, y)| {
+                        let center = POSITION_00
+                            + (x as f32 * vec2(-f32::sqrt(3.0), -1.0)
+                                + y as f32 * vec2(f32::sqrt(3.0), -1.0))
+                                * APOTHEM;
+
+                        ((x, y), (center - uv.to_vec2()).length())
+                    })
+                    .collect::<Vec<_>>();
+
+                distances.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+                let ((x, y), nearest) = distances[0];
+                let (_, second_nearest) = distances[1];
+
+                // Skip points that are basically on an edge.
+                if second_nearest - nearest < 1e-4 {
+                    continue;
+                }
+
+                let expected = u8::try_from(x)
+                    .ok()
+                    .zip(u8::try_from(y).ok())
+                    .and_then(|(x, y)| Vec2::new(x, y));
+
+                assert_eq!(uv_to_hex(uv), expected, "at {uv:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn hovering_a_piece_highlights_its_moves_unless_something_is_selected() {
+        let board = Board::default();
+        let (piece, _) = board
+            .piece_positions(Color::White)
+            .find(|&(position, _)| board.moves_from(position, Color::White).next().is_some())
+            .unwrap();
+
+        let mut gui_board = GuiBoard::default();
+        gui_board.hover(&board, Some(piece), Color::White);
+        assert_eq!(
+            gui_board.hover_highlights,
+            board
+                .moves_from(piece, Color::White)
+                .map(|mov| mov.destination())
+                .collect::<Vec<_>>()
+        );
+
+        gui_board.hover(&board, Some(Vec2::CENTER), Color::White);
+        assert!(gui_board.hover_highlights.is_empty(), "Empty tile");
+
+        let (black_piece, _) = board.piece_positions(Color::Black).next().unwrap();
+        gui_board.hover(&board, Some(black_piece), Color::White);
+        assert!(gui_board.hover_highlights.is_empty(), "Enemy piece");
+
+        gui_board.selected_tile = Some((piece, false));
+        gui_board.hover(&board, Some(piece), Color::White);
+        assert!(gui_board.hover_highlights.is_empty(), "Selection");
+    }
+
+    #[test]
+    fn dropping_a_piece_outside_of_its_moves_cancels_the_drag() {
+        let ctx = egui::Context::default();
+        let board = Board::default();
+        let (piece, mov) = board
+            .piece_positions(Color::White)
+            .find_map(|(position, _)| {
+                let mov = board.moves_from(position, Color::White).next()?;
+                Some((position, mov))
+            })
+            .unwrap();
+        let (other_piece, _) = board
+            .piece_positions(Color::White)
+            .find(|&(position, _)| position != piece)
+            .unwrap();
+
+        let mut gui_board = GuiBoard::default();
+        let mut drop_at = |uv| {
+            gui_board.select(&board, piece, Color::White, &ctx);
+            let mov = gui_board.drop_piece(&board, Color::White, piece, uv, &ctx);
+            (mov, gui_board.selected_tile.map(|(tile, _)| tile))
+        };
+
+        assert_eq!(drop_at(hex_to_uv(piece)), (None, Some(piece)), "Origin");
+        assert_eq!(drop_at(hex_to_uv(other_piece)), (None, None), "Own piece");
+        assert_eq!(drop_at(pos2(-1.0, 0.5)), (None, None), "Off the board");
+
+        let empty = Vec2::iter()
+            .find(|&tile| {
+                board.get_either(tile).is_none()
+                    && board.get_move(piece, tile, Color::White).is_err()
+            })
+            .unwrap();
+        assert_eq!(drop_at(hex_to_uv(empty)), (None, None), "Empty tile");
+
+        // Slightly off the center of the destination.
+        let destination = hex_to_uv(mov.destination()) + egui::vec2(APOTHEM / 2.0, 0.0);
+        assert_eq!(drop_at(destination).0, Some(mov));
+    }
+
+    #[test]
+    fn promotions_wait_for_the_piece_to_be_chosen() {
+        let ctx = egui::Context::default();
+        let mut board =
+            Board::new_minimal(Vec2::new(0, 0).unwrap(), Vec2::new(5, 10).unwrap()).unwrap();
+        let pawn = Vec2::new(9, 7).unwrap();
+        let destination = Vec2::new(10, 8).unwrap();
+        board.get_mut(pawn, Color::White).replace(Piece::Pawn);
+
+        let mut gui_board = GuiBoard::default();
+        let promote = |gui_board: &mut GuiBoard| {
+            gui_board.select(&board, pawn, Color::White, &ctx);
+            gui_board.toggle_selection(&board, Color::White, hex_to_uv(destination), &ctx)
+        };
+
+        assert_eq!(promote(&mut gui_board), None);
+        let mov = gui_board.choose_promotion(Piece::Knight).unwrap();
+        assert!(matches!(
+            mov,
+            Move::Promotion {
+                promoting_to: Piece::Knight,
+                ..
+            }
+        ));
+        assert_eq!(mov.destination_for(Color::White), destination);
+        assert!(board.clone().apply_move(mov, Color::White).is_ok());
+        assert_eq!(gui_board.choose_promotion(Piece::Knight), None);
+
+        // Deselecting cancels the promotion.
+        assert_eq!(promote(&mut gui_board), None);
+        gui_board.deselect();
+        assert_eq!(gui_board.choose_promotion(Piece::Queen), None);
+    }
+
+    #[test]
+    fn hints_are_kept_on_deselect_unless_asked_to_clear_them() {
+        let hints = [Vec2::CENTER, Vec2::ZERO];
+        let mut gui_board = GuiBoard::default();
+        gui_board.set_hints(&hints);
+        gui_board.deselect();
+        assert_eq!(gui_board.hints, hints);
+
+        gui_board.set_clear_hints_on_deselect(true);
+        gui_board.deselect();
+        assert!(gui_board.hints.is_empty());
+
+        gui_board.set_hints(&hints);
+        gui_board.set_hints(&[]);
+        assert!(gui_board.hints.is_empty());
+    }
+
+    #[test]
+    fn king_in_check_is_marked_until_the_check_is_resolved() {
+        let mut board = Board::new_minimal(Vec2::ZERO, Vec2::new(10, 10).unwrap()).unwrap();
+        board
+            .get_mut(Vec2::new(0, 5).unwrap(), Color::Black)
+            .replace(chessagon_core::piece::Piece::Rook);
+        assert!(board.in_check(Color::White).is_some());
+
+        let mut gui_board = GuiBoard::default();
+        gui_board.update_check(&board);
+        assert_eq!(gui_board.checked_king, Some(Vec2::ZERO));
+
+        let escape = board.possible_moves(Color::White).next().unwrap();
+        board.apply_move(escape, Color::White).unwrap();
+        gui_board.update_check(&board);
+        assert_eq!(gui_board.checked_king, None);
+    }
+
+    #[test]
+    fn cursor_steps_to_neighbors() {
+        for position in Vec2::iter() {
+            for key in [
+                Key::ArrowUp,
+                Key::ArrowDown,
+                Key::ArrowLeft,
+                Key::ArrowRight,
+            ] {
+                if let Some(next) = step_cursor(position, key) {
+                    assert_eq!(position.distance(next), 1);
+                }
+            }
+
+            if let Some(up) = step_cursor(position, Key::ArrowUp) {
+                assert_eq!(step_cursor(up, Key::ArrowDown), Some(position));
+            }
+        }
+    }
+
+    #[test]
+    fn points_outside_of_the_board_map_to_none() {
+        for uv in [
+            pos2(0.0, 0.0),
+            pos2(1.0, 0.0),
+            pos2(0.0, 1.0),
+            pos2(1.0, 1.0),
+            pos2(0.5, 1.0),
+            pos2(-1.0, 0.5),
+            pos2(2.0, 0.5),
+        ] {
+            assert_eq!(uv_to_hex(uv), None, "at {uv:?}");
+        }
+    }
+
+    #[test]
+    fn coordinate_labels_are_flipped_from_blacks_side() {
+        let labeled = |perspective| {
+            Vec2::iter()
+                .filter(|&tile| coordinate_label(tile, perspective).is_some())
+                .count()
+        };
+        // Both edges next to the corner, which is labeled once.
+        assert_eq!(labeled(Color::White), 11);
+        assert_eq!(labeled(Color::Black), 11);
+
+        let tile = Vec2::new(3, 0).unwrap();
+        assert_eq!(coordinate_label(tile, Color::White), Some(3));
+        assert_eq!(coordinate_label(tile, Color::Black), None);
+        assert_eq!(coordinate_label(tile.flipped(), Color::Black), Some(3));
+        assert_eq!(coordinate_label(Vec2::CENTER, Color::White), None);
     }
 }
@@ -1,5 +1,7 @@
+use std::time::{Duration, SystemTime};
+
 use chessagon_core::{Board, Color, Vec2, piece::Piece};
-use egui::{Image, Pos2};
+use egui::{Color32, FontId, Image, Painter, Pos2, Rect, Stroke, StrokeKind, vec2};
 
 use super::hex_to_uv;
 
@@ -26,12 +28,83 @@ pub fn icon(piece: Piece, color: Color) -> egui::Image<'static> {
     Image::new(ICONS[color][piece].clone())
 }
 
+/// The size of a [badge](draw_badge), relative to the size of its piece.
+pub const BADGE_SCALE: f32 = 0.38;
+
+/// Where the [badge](draw_badge) of a piece drawn in `piece_rect` goes: in its bottom right
+/// corner, scaling with the piece.
+pub fn badge_rect(piece_rect: Rect) -> Rect {
+    let size = piece_rect.size() * BADGE_SCALE;
+    Rect::from_min_size(piece_rect.max - size, size)
+}
+
+/// The letter on the [badge](draw_badge) of a piece: its
+/// [representing letter](Piece::representing_letter), uppercase for white and lowercase for
+/// black.
+pub fn badge_letter(piece: Piece, color: Color) -> char {
+    let letter = piece.representing_letter();
+    color.choose(letter, letter.to_ascii_lowercase())
+}
+
+/// Draws a small badge with the [letter](badge_letter) of the piece, so that color isn't the only
+/// way to tell pieces apart.
+///
+/// The shape of the badge also depends on the color: round for white and square for black.
+pub fn draw_badge(painter: &Painter, piece_rect: Rect, piece: Piece, color: Color, opacity: f32) {
+    let rect = badge_rect(piece_rect);
+    let (fill, text) = color.choose(
+        (Color32::WHITE, Color32::BLACK),
+        (Color32::BLACK, Color32::WHITE),
+    );
+    let (fill, text) = (fill.gamma_multiply(opacity), text.gamma_multiply(opacity));
+    let stroke = Stroke::new(rect.width() * 0.08, text);
+
+    match color {
+        Color::White => {
+            painter.circle(rect.center(), rect.width() / 2.0, fill, stroke);
+        }
+        Color::Black => {
+            painter.rect(rect, 0.0, fill, stroke, StrokeKind::Inside);
+        }
+    }
+
+    painter.text(
+        rect.center() + vec2(0.0, rect.height() * 0.05),
+        egui::Align2::CENTER_CENTER,
+        badge_letter(piece, color),
+        FontId::proportional(rect.height() * 0.75),
+        text,
+    );
+}
+
+/// The frame time that easing factors are expressed in (i.e., 60 fps).
+pub const REFERENCE_FRAME_TIME: f32 = 1.0 / 60.0;
+
+/// The fraction of the remaining distance to cover in a frame that took `dt` seconds, such that
+/// `move_factor` is covered every [`REFERENCE_FRAME_TIME`].
+///
+/// Doing two steps of `dt` is the same as doing one of `2 * dt`, which makes the easing
+/// independent of the framerate.
+pub fn ease_factor(move_factor: f32, dt: f32) -> f32 {
+    1.0 - (1.0 - move_factor.clamp(0.0, 1.0)).powf(dt / REFERENCE_FRAME_TIME)
+}
+
+/// How long it takes for a captured piece to disappear.
+pub const CAPTURE_ANIMATION_DURATION: Duration = Duration::from_millis(200);
+
 #[derive(Debug, Clone, Copy, serde::Deserialize, serde::Serialize)]
 pub struct GuiPiece {
     pub kind: Piece,
     pub color: Color,
     pub hex_tile: Vec2,
     pub position: Pos2,
+
+    /// The moment the piece got captured, if it has been.
+    ///
+    /// Captured pieces are kept around until [`CAPTURE_ANIMATION_DURATION`] has passed, so
+    /// that they can be animated out.
+    #[serde(default)]
+    pub captured_at: Option<SystemTime>,
 }
 
 impl GuiPiece {
@@ -47,34 +120,63 @@ impl GuiPiece {
                 color,
                 hex_tile: position,
                 position: hex_to_uv(position),
+                captured_at: None,
             })
     }
 
-    // TODO: Maybe we should have a `dt` here, but it's unclear with exponential easing
-    /// Moves towards the target.
+    /// Moves towards the target, with exponential easing.
+    ///
+    /// `move_factor` is the fraction of the remaining distance that is covered in one
+    /// [reference frame](REFERENCE_FRAME_TIME), and `dt` is the time that has passed
+    /// since the last frame, in seconds. See [`ease_factor`].
     ///
     /// Returns whether the piece has moved.
-    pub fn move_towards(&mut self, target: Pos2, move_factor: f32) -> bool {
+    pub fn move_towards(&mut self, target: Pos2, move_factor: f32, dt: f32) -> bool {
         let delta = target - self.position;
         if delta.length_sq() < 0.0000005_f32.powi(2) {
             return false;
         }
 
-        self.position += delta * move_factor;
+        self.position += delta * ease_factor(move_factor, dt);
         true
     }
 
-    pub fn move_towards_target(&mut self, move_factor: f32) -> bool {
-        self.move_towards(self.target_position(), move_factor)
+    pub fn move_towards_target(&mut self, move_factor: f32, dt: f32) -> bool {
+        self.move_towards(self.target_position(), move_factor, dt)
+    }
+
+    /// Whether the piece has been captured (and is only kept around to be animated out).
+    #[inline]
+    pub fn is_captured(&self) -> bool {
+        self.captured_at.is_some()
+    }
+
+    /// How far along the capture animation is, from `0.0` (not captured) to `1.0` (fully gone).
+    pub fn capture_progress(&self) -> f32 {
+        let Some(captured_at) = self.captured_at else {
+            return 0.0;
+        };
+
+        let elapsed = SystemTime::now()
+            .duration_since(captured_at)
+            .unwrap_or(Duration::ZERO);
+
+        (elapsed.as_secs_f32() / CAPTURE_ANIMATION_DURATION.as_secs_f32()).min(1.0)
     }
 
     pub fn update(pieces: &mut Vec<Self>, board: &Board) {
+        // Remove captured pieces whose animation has finished.
+        pieces.retain(|piece| piece.capture_progress() < 1.0);
+
         let mut target_unmatched = Vec::new();
-        let mut starting_unmatched = pieces.iter_mut().enumerate().collect::<Vec<_>>();
+        let mut starting_unmatched = pieces
+            .iter_mut()
+            .filter(|piece| !piece.is_captured())
+            .collect::<Vec<_>>();
         for (position, piece, color) in board.all_piece_positions() {
             if let Some(i) = starting_unmatched
                 .iter()
-                .position(|(_, p)| p.kind == piece && p.color == color && p.hex_tile == position)
+                .position(|p| p.kind == piece && p.color == color && p.hex_tile == position)
             {
                 starting_unmatched.swap_remove(i);
             } else {
@@ -82,8 +184,7 @@ impl GuiPiece {
             }
         }
 
-        let mut remove = Vec::new();
-        for (i, piece) in starting_unmatched {
+        for piece in starting_unmatched {
             let candidate = target_unmatched
                 .iter()
                 .filter(|&&(_, piece_kind, color)| piece.kind == piece_kind && piece.color == color)
@@ -92,13 +193,68 @@ impl GuiPiece {
             if let Some((new_hex_tile, _, _)) = candidate {
                 piece.hex_tile = *new_hex_tile;
             } else {
-                // TODO: Do some animation when captured.
-                remove.push(i);
+                piece.captured_at = Some(SystemTime::now());
             }
         }
+    }
+}
 
-        for i in remove {
-            pieces.remove(i);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ease_for(move_factor: f32, fps: u32, seconds: u32) -> f32 {
+        let dt = 1.0 / fps as f32;
+        let mut position = 0.0;
+        for _ in 0..fps * seconds {
+            position += (1.0 - position) * ease_factor(move_factor, dt);
         }
+
+        position
+    }
+
+    #[test]
+    fn badges_scale_with_pieces() {
+        let small = Rect::from_min_size(Pos2::ZERO, vec2(40.0, 40.0));
+        let big = Rect::from_min_size(Pos2::ZERO, vec2(80.0, 80.0));
+
+        assert_eq!(badge_rect(big).size(), badge_rect(small).size() * 2.0);
+        assert!(big.contains_rect(badge_rect(big)));
+        assert_eq!(badge_rect(big).max, big.max);
+
+        assert_eq!(badge_letter(Piece::Knight, Color::White), 'N');
+        assert_eq!(badge_letter(Piece::Knight, Color::Black), 'n');
+    }
+
+    #[test]
+    fn easing_covers_move_factor_in_a_reference_frame() {
+        let factor = ease_factor(0.2, REFERENCE_FRAME_TIME);
+        assert!((factor - 0.2).abs() < 1e-6);
+    }
+
+    #[test]
+    fn easing_is_independent_of_framerate() {
+        for move_factor in [0.05, 0.2, 0.8] {
+            let at_60 = ease_for(move_factor, 60, 1);
+            let at_144 = ease_for(move_factor, 144, 1);
+            let at_30 = ease_for(move_factor, 30, 1);
+
+            assert!((at_60 - at_144).abs() < 1e-4, "{at_60} != {at_144}");
+            assert!((at_60 - at_30).abs() < 1e-4, "{at_60} != {at_30}");
+        }
+    }
+
+    #[test]
+    fn easing_converges_at_a_fixed_rate() {
+        let dts = [0.016, 0.007, 0.033, 0.010, 0.050, 0.004];
+        let total: f32 = dts.iter().sum();
+
+        let mut position = 0.0;
+        for dt in dts {
+            position += (1.0 - position) * ease_factor(0.2, dt);
+        }
+
+        let expected = 1.0 - 0.8_f32.powf(total / REFERENCE_FRAME_TIME);
+        assert!((position - expected).abs() < 1e-5);
     }
 }
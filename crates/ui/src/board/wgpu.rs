@@ -4,6 +4,7 @@ use eframe::egui_wgpu::{self, RenderState};
 use std::num::NonZeroU64;
 
 use super::Uniforms;
+use crate::color_scheme::ColorSchemeRgba;
 
 /// Prepares the gpu in the correct state.
 ///
@@ -24,7 +25,7 @@ pub fn prepare(wgpu_render_state: &RenderState) {
             ty: wgpu::BindingType::Buffer {
                 ty: wgpu::BufferBindingType::Uniform,
                 has_dynamic_offset: false,
-                min_binding_size: NonZeroU64::new(480),
+                min_binding_size: NonZeroU64::new(size_of::<Uniforms>() as u64),
             },
             count: None,
         }],
@@ -86,6 +87,7 @@ pub fn prepare(wgpu_render_state: &RenderState) {
             pipeline,
             bind_group,
             uniform_buffer,
+            uploaded_color_scheme: None,
         });
 }
 
@@ -93,12 +95,23 @@ pub struct BoardRenderResources {
     pub pipeline: wgpu::RenderPipeline,
     pub bind_group: wgpu::BindGroup,
     pub uniform_buffer: wgpu::Buffer,
+
+    /// The color scheme currently in the uniform buffer, so that it's only uploaded on change.
+    uploaded_color_scheme: Option<ColorSchemeRgba>,
 }
 
 impl BoardRenderResources {
-    fn prepare(&self, _device: &wgpu::Device, queue: &wgpu::Queue, uniforms: &Uniforms) {
-        let bytes = bytemuck::bytes_of(uniforms);
-        queue.write_buffer(&self.uniform_buffer, 0, bytes);
+    fn prepare(&mut self, _device: &wgpu::Device, queue: &wgpu::Queue, uniforms: &Uniforms) {
+        if self.uploaded_color_scheme == Some(uniforms.color_scheme) {
+            queue.write_buffer(
+                &self.uniform_buffer,
+                Uniforms::PER_FRAME_OFFSET as u64,
+                uniforms.per_frame_bytes(),
+            );
+        } else {
+            queue.write_buffer(&self.uniform_buffer, 0, bytemuck::bytes_of(uniforms));
+            self.uploaded_color_scheme = Some(uniforms.color_scheme);
+        }
     }
 
     fn paint(&self, render_pass: &mut wgpu::RenderPass<'_>) {
@@ -142,7 +155,7 @@ impl egui_wgpu::CallbackTrait for CustomBoardCallback {
         _egui_encoder: &mut wgpu::CommandEncoder,
         resources: &mut egui_wgpu::CallbackResources,
     ) -> Vec<wgpu::CommandBuffer> {
-        let resources: &BoardRenderResources = resources.get().unwrap();
+        let resources: &mut BoardRenderResources = resources.get_mut().unwrap();
         resources.prepare(device, queue, &self.uniforms);
         Vec::new()
     }
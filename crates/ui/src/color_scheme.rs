@@ -1,7 +1,7 @@
 use bytemuck::{Pod, Zeroable};
 use egui::{Rgba, ecolor, epaint::HsvaGamma};
 
-#[derive(Debug, Clone, Copy, serde::Deserialize, serde::Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, serde::Deserialize, serde::Serialize)]
 #[serde(from = "ColorSchemeRgba", into = "ColorSchemeRgba")]
 pub struct ColorScheme {
     pub tiles: [HsvaGamma; 3],
@@ -64,7 +64,9 @@ impl Default for ColorScheme {
 }
 
 #[repr(C)]
-#[derive(Debug, Clone, Copy, Zeroable, Pod, serde::Deserialize, serde::Serialize, PartialEq)]
+#[derive(
+    Debug, Default, Clone, Copy, Zeroable, Pod, serde::Deserialize, serde::Serialize, PartialEq,
+)]
 pub struct ColorSchemeRgba {
     pub tiles: [Rgba; 3],
     pub background: Rgba,
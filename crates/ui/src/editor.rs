@@ -0,0 +1,225 @@
+//! A screen to set up positions piece by piece, to play games from them.
+
+use chessagon_core::{Board, Color, Vec2, piece::Piece};
+use egui::{Align, Layout, RichText, Ui, vec2};
+
+use crate::{GuiBoard, board::piece, components};
+
+/// The pieces in the palette, in the order they are shown.
+const PIECES: [Piece; 6] = [
+    Piece::King,
+    Piece::Queen,
+    Piece::Rook,
+    Piece::Bishop,
+    Piece::Knight,
+    Piece::Pawn,
+];
+
+/// What clicking on a tile does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Brush {
+    /// Places the piece on the tile, replacing whatever was there (or removes it, if it was
+    /// already there).
+    Place(Piece, Color),
+
+    /// Removes the piece on the tile.
+    Erase,
+}
+
+#[derive(Debug)]
+pub struct BoardEditor {
+    /// The board being edited, which is not necessarily [valid](Board::validate).
+    board: Board,
+    pub brush: Brush,
+
+    /// Created on the first draw, since it needs the render state of the frame.
+    gui_board: Option<GuiBoard>,
+}
+
+impl Default for BoardEditor {
+    fn default() -> Self {
+        Self {
+            board: Board::default(),
+            brush: Brush::Place(Piece::Pawn, Color::White),
+            gui_board: None,
+        }
+    }
+}
+
+impl BoardEditor {
+    /// Uses the [brush](Self::brush) on the given tile.
+    pub fn paint(&mut self, tile: Vec2) {
+        let current = self.board.get_either(tile);
+        for color in [Color::White, Color::Black] {
+            self.board.get_mut(tile, color).take();
+        }
+
+        match self.brush {
+            Brush::Place(piece, color) if current != Some((piece, color)) => {
+                self.board.get_mut(tile, color).replace(piece);
+            }
+            Brush::Place(..) | Brush::Erase => (),
+        }
+    }
+
+    /// Removes every piece.
+    pub fn clear(&mut self) {
+        self.board = Board::empty();
+    }
+
+    /// Sets the pieces back to the starting position.
+    pub fn reset(&mut self) {
+        self.board = Board::default();
+    }
+
+    /// Draws the editor.
+    ///
+    /// Returns the board to start a game from, once the player asks to and it is valid.
+    pub fn draw(&mut self, ui: &mut Ui, frame: &eframe::Frame) -> Option<Board> {
+        if self.gui_board.is_none() {
+            self.gui_board = GuiBoard::new(frame, &self.board);
+        }
+
+        let mut start = None;
+        ui.with_layout(Layout::left_to_right(Align::Center), |ui| {
+            let panel_size = 200.0;
+            let board_width = (ui.available_width() - panel_size).min(600.0);
+
+            ui.allocate_ui_with_layout(
+                vec2(board_width, ui.available_height()),
+                Layout::left_to_right(Align::Center),
+                |ui| match &mut self.gui_board {
+                    Some(gui_board) => {
+                        if let Some(tile) = gui_board.draw_editor(ui, &self.board) {
+                            self.paint(tile);
+                        }
+                    }
+                    None => {
+                        ui.label("The board can't be drawn without a render state");
+                    }
+                },
+            );
+
+            ui.allocate_ui_with_layout(
+                vec2(panel_size, ui.available_height()),
+                Layout::top_down(Align::Center),
+                |ui| start = self.draw_sidebar(ui),
+            );
+        });
+
+        start
+    }
+
+    /// Draws the palette, the actions and whether the position is valid.
+    fn draw_sidebar(&mut self, ui: &mut Ui) -> Option<Board> {
+        for color in [Color::White, Color::Black] {
+            ui.horizontal(|ui| {
+                for piece in PIECES {
+                    let brush = Brush::Place(piece, color);
+                    let button = egui::Button::image(
+                        piece::icon(piece, color).fit_to_exact_size(vec2(24.0, 24.0)),
+                    )
+                    .corner_radius(0)
+                    .selected(self.brush == brush);
+
+                    if ui.add(button).on_hover_text(piece.name()).clicked() {
+                        self.brush = brush;
+                    }
+                }
+            });
+        }
+
+        let eraser = components::button("Eraser").selected(self.brush == Brush::Erase);
+        if ui.add(eraser).clicked() {
+            self.brush = Brush::Erase;
+        }
+
+        ui.add_space(8.0);
+        ui.horizontal(|ui| {
+            if ui.add(components::button("Clear")).clicked() {
+                self.clear();
+            }
+
+            if ui.add(components::button("Reset to start")).clicked() {
+                self.reset();
+            }
+        });
+
+        ui.add_space(8.0);
+        let validation = self.board.validate(Color::White);
+        match &validation {
+            Ok(()) => ui.label("Valid position, white to move"),
+            Err(err) => ui.label(RichText::new(err.to_string()).color(ui.visuals().error_fg_color)),
+        };
+
+        let valid = validation.is_ok();
+        if ui
+            .add_enabled(valid, components::button("Copy FEN"))
+            .clicked()
+        {
+            ui.ctx().copy_text(self.board.to_fen());
+        }
+
+        let play_button = components::button(RichText::new("Play from here").strong());
+        ui.add_enabled(valid, play_button)
+            .clicked()
+            .then(|| self.board.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chessagon_core::{Board, Color, Vec2, piece::Piece};
+
+    use super::{BoardEditor, Brush};
+
+    #[test]
+    fn painting_places_replaces_and_removes_pieces() {
+        let mut editor = BoardEditor::default();
+        editor.clear();
+
+        editor.brush = Brush::Place(Piece::Knight, Color::White);
+        editor.paint(Vec2::CENTER);
+        assert_eq!(
+            editor.board.get_either(Vec2::CENTER),
+            Some((Piece::Knight, Color::White))
+        );
+
+        editor.brush = Brush::Place(Piece::Rook, Color::Black);
+        editor.paint(Vec2::CENTER);
+        assert_eq!(
+            editor.board.get_either(Vec2::CENTER),
+            Some((Piece::Rook, Color::Black))
+        );
+
+        // Painting the same piece again removes it.
+        editor.paint(Vec2::CENTER);
+        assert_eq!(editor.board.get_either(Vec2::CENTER), None);
+
+        editor.paint(Vec2::CENTER);
+        editor.brush = Brush::Erase;
+        editor.paint(Vec2::CENTER);
+        assert_eq!(editor.board, Board::empty());
+    }
+
+    #[test]
+    fn edited_positions_are_validated() {
+        let mut editor = BoardEditor::default();
+        assert_eq!(editor.board.validate(Color::White), Ok(()));
+
+        editor.clear();
+        assert!(editor.board.validate(Color::White).is_err());
+
+        for (tile, color) in [
+            (Vec2::ZERO, Color::White),
+            (Vec2::new(10, 10).unwrap(), Color::Black),
+        ] {
+            editor.brush = Brush::Place(Piece::King, color);
+            editor.paint(tile);
+        }
+        assert_eq!(editor.board.validate(Color::White), Ok(()));
+
+        editor.reset();
+        assert_eq!(editor.board, Board::default());
+    }
+}
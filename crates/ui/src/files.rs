@@ -0,0 +1,105 @@
+//! Saving and loading games as [transcripts](chessagon_core::game::transcript).
+//!
+//! On native, games are saved to `.chessagon` files through file dialogs. On the web, there is no
+//! file system, so transcripts are copied to and pasted from the clipboard instead.
+
+use chessagon_core::{
+    Game,
+    game::{TimeControl, TranscriptError},
+};
+
+/// The extension of saved games.
+pub const EXTENSION: &str = "chessagon";
+
+#[allow(missing_docs)]
+#[derive(Debug, thiserror::Error)]
+pub enum LoadError {
+    #[error("Couldn't read the file: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Invalid game file: {0}")]
+    Transcript(#[from] TranscriptError),
+}
+
+/// Reads a game from the contents of a saved game.
+///
+/// Transcripts don't include the time control, and loaded games are only
+/// [reviewed](crate::game::GameMode::Review), so it's always [`TimeControl::rapid`].
+pub fn parse_game(transcript: &str) -> Result<Game, TranscriptError> {
+    Game::from_transcript(transcript, TimeControl::rapid())
+}
+
+/// Asks where to save the game and saves it there.
+///
+/// Does nothing if the dialog is cancelled.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save_game(game: &Game) -> std::io::Result<()> {
+    let Some(path) = rfd::FileDialog::new()
+        .add_filter("Chessagon game", &[EXTENSION])
+        .set_file_name(format!("game.{EXTENSION}"))
+        .save_file()
+    else {
+        return Ok(());
+    };
+
+    std::fs::write(path, game.to_transcript())
+}
+
+/// Asks for a saved game and loads it.
+///
+/// Returns `None` if the dialog is cancelled.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn load_game() -> Result<Option<Game>, LoadError> {
+    let Some(path) = rfd::FileDialog::new()
+        .add_filter("Chessagon game", &[EXTENSION])
+        .pick_file()
+    else {
+        return Ok(None);
+    };
+
+    let transcript = std::fs::read_to_string(path)?;
+    Ok(Some(parse_game(&transcript)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use chessagon_core::{Board, Color, Game, Vec2, game::Action, game::TimeControl, piece::Piece};
+
+    use super::parse_game;
+
+    #[test]
+    fn saved_games_load_back() {
+        let mut game = Game::new(TimeControl::rapid());
+        for _ in 0..4 {
+            let color = game.turn();
+            let mov = game.board().possible_moves(color).next().unwrap();
+            game.apply_action(Action::Move(mov), color).unwrap();
+        }
+        game.apply_action(Action::OfferDraw, game.turn()).unwrap();
+
+        let loaded = parse_game(&game.to_transcript()).unwrap();
+        assert_eq!(loaded.board(), game.board());
+        assert_eq!(loaded.draw_offer(), game.draw_offer());
+        assert_eq!(loaded.to_transcript(), game.to_transcript());
+    }
+
+    #[test]
+    fn games_from_edited_positions_load_back() {
+        let mut board =
+            Board::new_minimal(Vec2::new(0, 0).unwrap(), Vec2::new(10, 10).unwrap()).unwrap();
+        board
+            .get_mut(Vec2::new(5, 5).unwrap(), Color::White)
+            .replace(Piece::Knight);
+        let mut game = Game::from_position(board.clone(), TimeControl::rapid()).unwrap();
+        for _ in 0..2 {
+            let color = game.turn();
+            let mov = game.board().possible_moves(color).next().unwrap();
+            game.apply_action(Action::Move(mov), color).unwrap();
+        }
+
+        let loaded = parse_game(&game.to_transcript()).unwrap();
+        assert_eq!(loaded.initial_board(), &board);
+        assert_eq!(loaded.board(), game.board());
+        assert_eq!(loaded.to_transcript(), game.to_transcript());
+    }
+}
@@ -1,20 +1,39 @@
-use std::sync::mpsc::{self, Receiver, Sender};
+use std::{
+    sync::mpsc::{self, Receiver, Sender, TryRecvError},
+    thread::JoinHandle,
+    time::Duration,
+};
 
-use crate::{GuiBoard, components};
+use crate::{GuiBoard, Settings, components};
 use chessagon_core::{
-    Color, Game,
-    game::{Action, TimeControl},
+    Board, Color, Game,
+    game::{Action, EndReason, Outcome, TimeControl},
+};
+use chessagon_engine::{
+    Engine as _,
+    analysis::{Analysis, SearchProgress},
+    models::Anthony,
 };
-use chessagon_engine::{Engine as _, models::Anthony};
-use egui::{Align, Context, Layout, Margin, RichText, Spacing, Ui, Vec2, vec2};
+use egui::{Align, Context, DragValue, Layout, Margin, RichText, Spacing, Ui, Vec2, vec2};
 
+mod eval_bar;
+mod spectate;
 mod timer;
 
 // TODO: Fix this god awful name.
 #[derive(Debug, serde::Deserialize, serde::Serialize)]
 pub enum GameOrInitGameScreen {
-    InitGame { time_control: TimeControl },
-    Game(GameScreen),
+    InitGame {
+        time_control: TimeControl,
+        #[serde(default)]
+        mode: GameMode,
+
+        /// The position to start from, if not the starting one (e.g., from the
+        /// [editor](crate::editor)).
+        #[serde(default)]
+        position: Option<Box<Board>>,
+    },
+    Game(Box<GameScreen>),
 }
 impl GameOrInitGameScreen {
     /// Applies a function if the variant is `Self::Game`.
@@ -30,24 +49,185 @@ impl Default for GameOrInitGameScreen {
     fn default() -> Self {
         Self::InitGame {
             time_control: TimeControl::blitz(),
+            mode: GameMode::default(),
+            position: None,
+        }
+    }
+}
+
+impl GameOrInitGameScreen {
+    /// The screen to start a game from the given position.
+    pub fn from_position(board: Board) -> Self {
+        match Self::default() {
+            Self::InitGame {
+                time_control, mode, ..
+            } => Self::InitGame {
+                time_control,
+                mode,
+                position: Some(Box::new(board)),
+            },
+            Self::Game(_) => unreachable!("The default screen initializes a game"),
         }
     }
 }
 
+/// Who is playing the game.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub enum GameMode {
+    /// The player plays against an engine, which runs in another thread.
+    #[default]
+    VsEngine,
+
+    /// Two players take turns on the same device (also known as hotseat).
+    LocalTwoPlayer,
+
+    /// A game that was loaded from a file, which can be looked at but not played.
+    Review,
+
+    /// Two engines play against each other, one move at a time, without time limits.
+    Spectate,
+}
+
+impl GameMode {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::VsEngine => "Against engine",
+            Self::LocalTwoPlayer => "Local two-player",
+            Self::Review => "Review",
+            Self::Spectate => "Engine vs engine",
+        }
+    }
+
+    /// Whether the player can make moves and take actions in this mode.
+    pub fn is_playable(self) -> bool {
+        matches!(self, Self::VsEngine | Self::LocalTwoPlayer)
+    }
+}
+
 #[derive(Debug, serde::Deserialize, serde::Serialize)]
 pub struct GameScreen {
-    /// The color of the player
+    /// The color of the player.
+    ///
+    /// In [`GameMode::LocalTwoPlayer`] both colors are played from this screen, so this is only
+    /// the color at the bottom of the screen.
     pub color: Color,
+    #[serde(default)]
+    pub mode: GameMode,
     pub game: Game,
     pub gui_board: GuiBoard,
     #[serde(skip)]
     pub connection: Option<GameConnection>,
+
+    /// An action that has been clicked once and is waiting for confirmation.
+    #[serde(skip)]
+    pending_confirmation: Option<ConfirmableAction>,
+
+    /// Set when the opponent disconnected before the game finished (e.g., because the engine
+    /// panicked).
+    #[serde(skip)]
+    opponent_error: Option<OpponentDisconnected>,
+
+    /// The engines playing the game, in [`GameMode::Spectate`].
+    #[serde(skip)]
+    spectator: Option<spectate::Spectator>,
+
+    /// Whether the player dismissed the current draw offer of the opponent, which hides it
+    /// until a new one is made.
+    #[serde(skip)]
+    draw_offer_declined: bool,
+
+    /// The latest evaluation of the opponent engine, see [`Settings::eval_bar`].
+    #[serde(skip)]
+    eval_bar: eval_bar::EvalBar,
+
+    /// How far the opponent engine has searched for its next move, while it is thinking.
+    #[serde(skip)]
+    search_progress: Option<SearchProgress>,
+}
+
+/// Actions that might need to be confirmed with a second click, see [`Settings::confirm_actions`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ConfirmableAction {
+    Resign,
+    Abort,
+    NewGame,
+}
+
+/// What the opponent sends through the [connection](GameConnection).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OpponentMessage {
+    /// An action, which has to be applied to the game.
+    Action(Action),
+
+    /// The engine's analysis of the current position, to show in the
+    /// [eval bar](Settings::eval_bar).
+    Analysis(Analysis),
+
+    /// How far the engine has searched for its next move, to show that it is thinking.
+    Progress(SearchProgress),
 }
 
 #[derive(Debug)]
 pub struct GameConnection {
     pub action_sender: Sender<Action>,
-    pub opponent_action_receiver: Receiver<Action>,
+    pub opponent_receiver: Receiver<OpponentMessage>,
+    /// The thread where the opponent engine runs, if any.
+    pub engine_thread: Option<JoinHandle<()>>,
+}
+
+/// The opponent stopped responding before the game was over.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("The opponent disconnected unexpectedly: {reason}")]
+pub struct OpponentDisconnected {
+    /// The panic message of the engine thread, if it panicked.
+    pub reason: String,
+}
+
+impl GameConnection {
+    /// Checks whether the opponent has sent a message, without blocking.
+    ///
+    /// If the opponent disconnected while the game is still in progress, the connection is
+    /// dropped and the reason is returned as an error.
+    fn poll(
+        connection: &mut Option<Self>,
+        game: &Game,
+    ) -> Result<Option<OpponentMessage>, OpponentDisconnected> {
+        let Some(this) = connection else {
+            return Ok(None);
+        };
+
+        match this.opponent_receiver.try_recv() {
+            Ok(message) => Ok(Some(message)),
+            Err(TryRecvError::Empty) => Ok(None),
+            // The engine thread returns normally once the game is over.
+            Err(TryRecvError::Disconnected) if game.is_finished() => Ok(None),
+            Err(TryRecvError::Disconnected) => {
+                let this = connection.take().expect("Checked to be `Some` above");
+                let reason = match this.engine_thread.map(JoinHandle::join) {
+                    Some(Err(payload)) => payload
+                        .downcast_ref::<&str>()
+                        .map(|msg| msg.to_string())
+                        .or_else(|| payload.downcast_ref::<String>().cloned())
+                        .unwrap_or_else(|| "the engine panicked".to_string()),
+                    _ => "the engine stopped without finishing the game".to_string(),
+                };
+
+                Err(OpponentDisconnected { reason })
+            }
+        }
+    }
+}
+
+/// Describes what the engine is thinking, like `Thinking at depth 2: +1.5 (1,2 3,4)`, where the
+/// engine plays against `player` and the move is seen from the side of `perspective`.
+fn format_search_progress(progress: SearchProgress, player: Color, perspective: Color) -> String {
+    let Analysis { best_move, score } = progress.analysis;
+    let mut text = format!("Thinking at depth {}: {score:+.1}", progress.depth);
+    if let Some(mov) = best_move {
+        text.push_str(&format!(" ({})", mov.notation(player.other(), perspective)));
+    }
+
+    text
 }
 
 pub enum GameScreenEvent {
@@ -57,37 +237,75 @@ pub enum GameScreenEvent {
 impl GameScreen {
     fn connect_to_channel(
         &mut self,
-        opponent_action_receiver: Receiver<Action>,
+        opponent_receiver: Receiver<OpponentMessage>,
     ) -> Receiver<Action> {
         let (sender, receiver) = mpsc::channel();
         self.connection = Some(GameConnection {
             action_sender: sender,
-            opponent_action_receiver,
+            opponent_receiver,
+            engine_thread: None,
         });
 
         receiver
     }
 
-    pub fn connect(&mut self) {
-        let mut opponent = Anthony::new(self.color.other(), self.game.time_control());
+    /// Starts the opponent engine, if the game is [against an engine](GameMode::VsEngine).
+    ///
+    /// The engine plays with the [personality](Settings::engine_personality) of the settings,
+    /// and [thinks on the player's time](chessagon_engine::Engine::ponder) if
+    /// [`Settings::engine_ponder`] is set. It also analyzes the positions where the player is
+    /// to move if [`Settings::eval_bar`] is set, otherwise the eval bar is only updated by the
+    /// searches of its own moves.
+    pub fn connect(&mut self, settings: &Settings) {
+        self.opponent_error = None;
+        if self.mode == GameMode::Spectate {
+            self.spectator = Some(spectate::Spectator::start(self.game.clone()));
+            return;
+        }
+
+        if self.mode != GameMode::VsEngine {
+            return;
+        }
+
+        let mut opponent =
+            Anthony::with_personality(self.color.other(), settings.engine_personality);
+        opponent.ponder = settings.engine_ponder;
 
         let (opponent_sender, opponent_receiver) = mpsc::channel();
         let player_receiver = self.connect_to_channel(opponent_receiver);
 
-        {
+        let engine_thread = {
             let mut game = self.game.clone();
             let player_color = self.color;
+            let eval_bar = settings.eval_bar;
             std::thread::spawn(move || {
                 let span = tracing::info_span!("Opponent engine");
                 let _guard = span.enter();
 
                 tracing::info!("Starting engine in other thread");
+                let send = |message| {
+                    opponent_sender
+                        .send(message)
+                        .unwrap_or_else(|_| tracing::warn!("Opponent sender disconnectd"));
+                };
+
+                let mut analyzed_plies = None;
                 loop {
                     if game.is_finished() {
                         return;
                     }
 
+                    // Analyze each position once, for the eval bar. Positions where the engine
+                    // is to move are scored by the progress of its search instead.
+                    let plies = game.moves().len();
+                    if eval_bar && game.turn() == player_color && analyzed_plies != Some(plies) {
+                        send(OpponentMessage::Analysis(opponent.analyze(&game)));
+                        analyzed_plies = Some(plies);
+                    }
+
                     if game.turn() == player_color {
+                        opponent.ponder(&game);
+
                         tracing::debug!("Waiting for player action");
                         let Ok(player_action) = player_receiver.recv() else {
                             tracing::debug!("`player_receiver` channel was closed");
@@ -97,77 +315,209 @@ impl GameScreen {
                         tracing::debug!("Got {player_action:?} from player");
                         game.apply_action(player_action, player_color)
                             .expect("Actions received should be valid.");
+
+                        if player_action == Action::OfferDraw && opponent.accept_draw_offer(&game) {
+                            send(OpponentMessage::Action(Action::AcceptDraw));
+                            game.apply_action(Action::AcceptDraw, player_color.other())
+                                .expect("The draw was just offered.");
+                        }
                     } else {
                         tracing::debug!("getting engine action");
-                        let action = opponent.get_action(&game);
+                        let action = opponent.get_action_with_progress(&game, &mut |progress| {
+                            send(OpponentMessage::Progress(progress))
+                        });
 
                         tracing::debug!(?action);
-                        opponent_sender
-                            .send(action)
-                            .unwrap_or_else(|_| tracing::warn!("Opponent sender disconnectd"));
+                        send(OpponentMessage::Action(action));
                         game.apply_action(action, player_color.other())
                             .expect("Actions received should be valid.");
                     }
                 }
-            });
+            })
+        };
+
+        if let Some(connection) = &mut self.connection {
+            connection.engine_thread = Some(engine_thread);
         }
     }
 
-    /// Creates a new game screen.
+    /// Creates a new game screen, starting from `position` (or the starting position if it's
+    /// `None`).
+    ///
+    /// Returns `None` when [`GuiBoard::new`] does (no wgpu render state available).
+    ///
+    /// # Panics
+    ///
+    /// If `position` is not [valid](Board::validate).
+    pub fn new(
+        frame: &mut eframe::Frame,
+        time_control: TimeControl,
+        mode: GameMode,
+        position: Option<Board>,
+        settings: &Settings,
+    ) -> Option<GameScreen> {
+        // Spectated games are played one move at a time, so they can't be timed.
+        let time_control = match mode {
+            GameMode::Spectate => TimeControl::max(),
+            _ => time_control,
+        };
+
+        let game = match position {
+            Some(board) => Game::from_position(board, time_control)
+                .expect("Positions to start games from should be validated"),
+            None => Game::new(time_control),
+        };
+
+        let mut output = Self::from_game(frame, game, mode)?;
+        output.connect(settings);
+
+        Some(output)
+    }
+
+    /// Creates a screen to [review](GameMode::Review) an existing game, such as one loaded from
+    /// a file.
     ///
     /// Returns `None` when [`GuiBoard::new`] does (no wgpu render state available).
-    pub fn new(frame: &mut eframe::Frame, time_control: TimeControl) -> Option<GameScreen> {
-        let game = Game::new(time_control);
+    pub fn review(frame: &mut eframe::Frame, game: Game) -> Option<GameScreen> {
+        Self::from_game(frame, game, GameMode::Review)
+    }
+
+    fn from_game(frame: &mut eframe::Frame, game: Game, mode: GameMode) -> Option<GameScreen> {
         let gui_board = GuiBoard::new(frame, game.board())?;
 
-        let mut output = GameScreen {
+        Some(GameScreen {
             color: Color::White,
+            mode,
             game,
             gui_board,
             connection: None,
-        };
+            pending_confirmation: None,
+            opponent_error: None,
+            spectator: None,
+            draw_offer_declined: false,
+            eval_bar: eval_bar::EvalBar::default(),
+            search_progress: None,
+        })
+    }
 
-        output.connect();
+    /// The color that input from this screen plays as right now.
+    ///
+    /// In [`GameMode::LocalTwoPlayer`] this is whoever's turn it is.
+    pub fn active_color(&self) -> Color {
+        match self.mode {
+            GameMode::VsEngine => self.color,
+            GameMode::LocalTwoPlayer | GameMode::Review | GameMode::Spectate => self.game.turn(),
+        }
+    }
 
-        Some(output)
+    /// The side from which coordinates are shown, see [`Settings::notation_perspective`].
+    ///
+    /// It is the side of whoever is playing from this screen: the player in games against the
+    /// engine and the player to move in local two-player games. Nobody plays in reviewed and
+    /// spectated games, so they are always shown from white's side.
+    fn notation_perspective(&self, settings: &Settings) -> Color {
+        match self.mode {
+            GameMode::VsEngine | GameMode::LocalTwoPlayer => {
+                settings.notation_perspective(self.active_color())
+            }
+            GameMode::Review | GameMode::Spectate => Color::White,
+        }
+    }
+
+    /// How to refer to the player of the given color in the sidebar.
+    fn player_name(&self, color: Color) -> &'static str {
+        match self.mode {
+            GameMode::VsEngine if color == self.color => "You",
+            GameMode::VsEngine => "Opponent",
+            GameMode::LocalTwoPlayer | GameMode::Review => color.choose("White", "Black"),
+            GameMode::Spectate => color.choose("White engine", "Black engine"),
+        }
     }
 }
 
 impl GameScreen {
-    pub fn draw(&mut self, ui: &mut Ui, ctx: &Context) -> Option<GameScreenEvent> {
-        let Some(connection) = &self.connection else {
+    pub fn draw(
+        &mut self,
+        ui: &mut Ui,
+        ctx: &Context,
+        settings: &Settings,
+    ) -> Option<GameScreenEvent> {
+        if self.mode == GameMode::VsEngine
+            && self.connection.is_none()
+            && self.opponent_error.is_none()
+        {
             tracing::warn!(
                 "Trying to draw game screen but connections has not been established yet."
             );
             return None;
-        };
+        }
 
-        if let Ok(action) = connection.opponent_action_receiver.try_recv() {
-            tracing::debug!("got action {action:?} from opponent");
-            // TODO: Should we somehow handle invalid actions?
-            self.game
-                .apply_action(action, self.color.other())
-                .expect("Action received from opponent should be valid.");
+        match GameConnection::poll(&mut self.connection, &self.game) {
+            Ok(None) => (),
+            Ok(Some(OpponentMessage::Analysis(analysis))) => self.eval_bar.set_eval(analysis.score),
+            Ok(Some(OpponentMessage::Progress(progress))) => {
+                self.eval_bar.set_eval(progress.analysis.score);
+                self.search_progress = Some(progress);
+            }
+            Ok(Some(OpponentMessage::Action(action))) => {
+                tracing::debug!("got action {action:?} from opponent");
+                self.receive_opponent_action(action);
+                if matches!(action, Action::Move(_)) {
+                    self.gui_board.update(self.game.board(), self.color, ctx);
+                }
+            }
+            Err(err) => {
+                tracing::error!("{err}");
+                self.opponent_error = Some(err);
+            }
+        }
 
-            self.gui_board.update(self.game.board(), self.color, ctx);
+        if let Some(spectator) = &mut self.spectator {
+            if let Some(game) = spectator.poll() {
+                self.game = game;
+                self.gui_board
+                    .update(self.game.board(), self.game.turn(), ctx);
+            }
+
+            if !self.game.is_finished() {
+                ctx.request_repaint_after(std::time::Duration::from_millis(50));
+            }
         }
 
         let mut event = None;
         ui.with_layout(Layout::left_to_right(Align::Center), |ui| {
             let panel_size = 200.0;
             let max_board_width = 600.0;
-            let width = ui.available_width();
+            let eval_bar_width = if settings.eval_bar && self.mode == GameMode::VsEngine {
+                16.0
+            } else {
+                0.0
+            };
+
+            let width = ui.available_width() - eval_bar_width;
             let padding = (width - (panel_size + max_board_width)).max(0.0) / 2.0;
             let board_width = width - panel_size - 2.0 * padding;
 
             ui.allocate_space(vec2(padding, ui.available_height()));
+            if eval_bar_width > 0.0 {
+                self.eval_bar.draw(ui, ctx, eval_bar_width, self.color);
+            }
 
             // Board
             let mov = ui
                 .allocate_ui_with_layout(
                     vec2(board_width, ui.available_height()),
                     Layout::left_to_right(Align::Center),
-                    |ui| self.gui_board.draw(ui, ctx, self.game.board(), self.color),
+                    |ui| {
+                        let (move_speed, drag_speed) = settings.animation_speeds();
+                        self.gui_board.set_animation_speeds(move_speed, drag_speed);
+                        self.gui_board.set_piece_badges(settings.piece_badges);
+                        self.gui_board
+                            .set_coordinate_perspective(self.notation_perspective(settings));
+
+                        let color = self.active_color();
+                        self.gui_board.draw(ui, ctx, self.game.board(), color)
+                    },
                 )
                 .inner;
 
@@ -176,12 +526,16 @@ impl GameScreen {
                 vec2(panel_size, ui.available_height()),
                 Layout::top_down(Align::Center),
                 |ui| {
-                    event = self.draw_sidebar(ui, ctx);
+                    event = self.draw_sidebar(ui, ctx, settings);
                 },
             );
 
             if let Some(mov) = mov {
                 self.apply_action(Action::Move(mov));
+                if self.mode == GameMode::LocalTwoPlayer {
+                    self.gui_board
+                        .update(self.game.board(), self.active_color(), ctx);
+                }
             }
 
             ui.allocate_space(vec2(padding, ui.available_height()));
@@ -190,23 +544,24 @@ impl GameScreen {
         event
     }
 
-    pub fn draw_sidebar(&mut self, ui: &mut Ui, ctx: &Context) -> Option<GameScreenEvent> {
+    pub fn draw_sidebar(
+        &mut self,
+        ui: &mut Ui,
+        ctx: &Context,
+        settings: &Settings,
+    ) -> Option<GameScreenEvent> {
         let mut event = None;
 
         ui.allocate_ui_with_layout(
             vec2(ui.available_width(), ui.available_height() / 2.0),
             Layout::bottom_up(Align::Center),
             |ui| {
-                timer::draw(ui, ctx, self.game.time_remaining(self.color.other()));
-                if self.game.winner() == Some(Some(self.color.other())) {
-                    ui.label("Opponent (winner)");
-                } else if self.game.winner() == Some(Some(self.color)) {
-                    ui.label("Opponent (loser)");
-                } else if self.game.winner() == Some(None) {
-                    ui.label("Opponent (draw)");
-                } else {
-                    ui.label("Opponent");
+                let color = self.color.other();
+                if self.mode.is_playable() {
+                    let increment = timer::recent_increment(&self.game, color);
+                    timer::draw(ui, ctx, self.game.time_remaining(color), increment);
                 }
+                self.draw_player_label(ui, self.color.other());
             },
         );
 
@@ -216,8 +571,30 @@ impl GameScreen {
             ..Default::default()
         };
 
+        let opponent_draw_offer = self
+            .game
+            .draw_offer()
+            .filter(|&offered_by| offered_by != self.active_color());
+        if let Some(offered_by) = opponent_draw_offer
+            && !self.draw_offer_declined
+            && !self.game.is_finished()
+        {
+            ui.label(format!("{} offers a draw", self.player_name(offered_by)));
+        }
+
+        if let Some(progress) = self.search_progress
+            && self.game.turn() != self.color
+            && !self.game.is_finished()
+        {
+            let perspective = self.notation_perspective(settings);
+            let text = format_search_progress(progress, self.color, perspective);
+            ui.label(RichText::new(text).weak());
+        }
+
         ui.horizontal(|ui| {
-            let in_progress = !self.game.is_finished();
+            let in_progress = !self.game.is_finished() && self.mode.is_playable();
+            let pending = self.pending_confirmation;
+            let mut confirm_hovered = false;
             let mut button = |text, always_enabled| {
                 ui.add_enabled(
                     in_progress || always_enabled,
@@ -225,8 +602,24 @@ impl GameScreen {
                 )
             };
 
-            if button("Resign", false).clicked() {
-                self.apply_action(Action::Resign);
+            // Before the first move there is nothing to resign, so the game is aborted instead.
+            let (quit, quit_action) = if self.game.moves().is_empty() {
+                (ConfirmableAction::Abort, Action::Abort)
+            } else {
+                (ConfirmableAction::Resign, Action::Resign)
+            };
+
+            let quit_text = match (quit, pending == Some(quit)) {
+                (ConfirmableAction::Abort, false) => "Abort",
+                (ConfirmableAction::Abort, true) => "Confirm abort",
+                (_, false) => "Resign",
+                (_, true) => "Confirm resign",
+            };
+
+            let quit_button = button(quit_text, false);
+            confirm_hovered |= quit_button.hovered();
+            if quit_button.clicked() && self.confirm(quit, settings.confirm_actions) {
+                self.apply_action(quit_action);
             }
 
             match self.game.draw_offer() {
@@ -235,58 +628,224 @@ impl GameScreen {
                         self.apply_action(Action::OfferDraw);
                     }
                 }
-                Some(color) if color == self.color => {
+                Some(color) if color == self.active_color() => {
                     if button("Retract draw", false).clicked() {
                         self.apply_action(Action::RetractDraw);
                     }
                 }
+                Some(_) if self.draw_offer_declined => (),
                 Some(_) => {
                     if button("Accept draw", false).clicked() {
                         self.apply_action(Action::AcceptDraw);
                     }
+
+                    if button("Decline draw", false).clicked() {
+                        self.draw_offer_declined = true;
+                    }
+                }
+            }
+
+            let claimable = self
+                .game
+                .can_claim_draw()
+                .filter(|_| self.game.turn() == self.active_color());
+            if let Some(reason) = claimable
+                && button("Claim draw", false)
+                    .on_hover_text(format!("By {reason}"))
+                    .clicked()
+            {
+                self.apply_action(Action::ClaimDraw);
+            }
+
+            if !in_progress {
+                let new_game_text = if pending == Some(ConfirmableAction::NewGame) {
+                    "Confirm new game"
+                } else {
+                    "New game"
+                };
+
+                let new_game_button = button(new_game_text, true);
+                confirm_hovered |= new_game_button.hovered();
+                if new_game_button.clicked()
+                    && self.confirm(ConfirmableAction::NewGame, settings.confirm_actions)
+                {
+                    event = Some(GameScreenEvent::Reset);
                 }
             }
 
-            if self.game.is_finished() && button("New game", true).clicked() {
-                event = Some(GameScreenEvent::Reset);
+            // Clicking anywhere else (or pressing escape) cancels the pending confirmation.
+            let cancel = ui.input(|i| {
+                (i.pointer.any_pressed() && !confirm_hovered) || i.key_pressed(egui::Key::Escape)
+            });
+
+            if cancel {
+                self.pending_confirmation = None;
             }
         });
 
+        if let Some(spectator) = &mut self.spectator {
+            spectator.draw_controls(ui, self.game.is_finished());
+        }
+
+        if let Some(opening) = self.game.opening_name() {
+            ui.label(RichText::new(opening).size(12.0).italics());
+        }
+
+        self.draw_halfmove_clock(ui);
+        if let Some(outcome) = self.game.outcome() {
+            ui.label(RichText::new(outcome.to_string()).strong());
+        }
+
+        if self.game.is_finished() {
+            self.draw_time_stats(ui);
+        }
+
+        if let Some(err) = &self.opponent_error {
+            ui.label(RichText::new(err.to_string()).color(ui.visuals().error_fg_color));
+            ui.horizontal(|ui| {
+                if ui.add(components::button("Restart engine")).clicked() {
+                    self.connect(settings);
+                }
+
+                if ui.add(components::button("New game")).clicked() {
+                    event = Some(GameScreenEvent::Reset);
+                }
+            });
+        }
+
         ui.allocate_ui_with_layout(
             vec2(ui.available_width(), ui.available_height() / 2.0),
             Layout::top_down(Align::Center),
             |ui| {
-                timer::draw(ui, ctx, self.game.time_remaining(self.color));
-                if self.game.winner() == Some(Some(self.color)) {
-                    ui.label("You (winner)");
-                } else if self.game.winner() == Some(Some(self.color.other())) {
-                    ui.label("You (loser)");
-                } else if self.game.winner() == Some(None) {
-                    ui.label("You (draw)");
-                } else {
-                    ui.label("You");
+                if self.mode.is_playable() {
+                    let increment = timer::recent_increment(&self.game, self.color);
+                    timer::draw(ui, ctx, self.game.time_remaining(self.color), increment);
                 }
+                self.draw_player_label(ui, self.color);
             },
         );
 
         event
     }
 
+    /// Draws the name of the player of the given color, with the result if the game is over.
+    fn draw_player_label(&self, ui: &mut Ui, color: Color) {
+        let name = self.player_name(color);
+        match self.game.outcome() {
+            Some(Outcome {
+                reason: EndReason::Aborted { .. },
+                ..
+            })
+            | None => ui.label(name),
+            Some(Outcome {
+                winner: Some(winner),
+                ..
+            }) if winner == color => ui.label(format!("{name} (winner)")),
+            Some(Outcome {
+                winner: Some(_), ..
+            }) => ui.label(format!("{name} (loser)")),
+            Some(Outcome { winner: None, .. }) => ui.label(format!("{name} (draw)")),
+        };
+    }
+
+    /// Registers a click on a button that might need confirmation.
+    ///
+    /// Returns whether the action should be done, which is either when confirmation is not
+    /// required or when it was already pending confirmation.
+    fn confirm(&mut self, action: ConfirmableAction, require_confirmation: bool) -> bool {
+        if !require_confirmation || self.pending_confirmation == Some(action) {
+            self.pending_confirmation = None;
+            return true;
+        }
+
+        self.pending_confirmation = Some(action);
+        false
+    }
+
+    /// Draws how much time each player spent, for the summary of a finished game.
+    fn draw_time_stats(&self, ui: &mut Ui) {
+        let format = |duration: std::time::Duration| {
+            let secs = duration.as_secs();
+            format!("{}:{:0>2}", secs / 60, secs % 60)
+        };
+
+        for color in [Color::White, Color::Black] {
+            let mut text = format!(
+                "{}: {} in total",
+                self.player_name(color),
+                format(self.game.total_time(color))
+            );
+
+            if let Some(average) = self.game.average_move_time(color) {
+                text += &format!(", {:.1}s per move", average.as_secs_f32());
+            }
+
+            ui.label(RichText::new(text).size(12.0));
+        }
+    }
+
+    /// Draws the counter for the fifty-move rule, highlighting it when it's getting close.
+    fn draw_halfmove_clock(&self, ui: &mut Ui) {
+        /// From how many moves on the counter gets highlighted.
+        const WARNING_THRESHOLD: u8 = 80;
+
+        let clock = self.game.halfmove_clock();
+        let mut text = RichText::new(format!("Fifty-move counter: {clock}/100")).size(12.0);
+        if clock >= WARNING_THRESHOLD {
+            text = text.color(ui.visuals().warn_fg_color).strong();
+        }
+
+        ui.label(text);
+    }
+
+    /// Applies an action that the opponent sent through the [connection](GameConnection).
+    ///
+    /// Besides moves, the opponent can offer, retract and accept draws. A new draw offer is shown
+    /// again even if the player declined the previous one.
+    fn receive_opponent_action(&mut self, action: Action) {
+        // TODO: Should we somehow handle invalid actions?
+        self.game
+            .apply_action(action, self.color.other())
+            .expect("Action received from opponent should be valid.");
+
+        if matches!(action, Action::Move(_)) {
+            self.search_progress = None;
+        }
+
+        match action {
+            Action::OfferDraw => self.draw_offer_declined = false,
+            Action::AcceptDraw => tracing::info!("Opponent accepted the draw"),
+            Action::ClaimDraw => tracing::info!("Opponent claimed a draw"),
+            Action::Abort => tracing::info!("Opponent aborted the game"),
+            Action::Move(_) | Action::Resign | Action::RetractDraw => (),
+        }
+    }
+
     /// Applies a valid action from the player while sending it to the sender.
     ///
     /// # Panics
     ///
     /// If the action is invalid.
     pub fn apply_action(&mut self, action: Action) {
-        // Only play on your turn
-        if self.game.turn() != self.color {
+        let color = self.active_color();
+
+        // Only play on your turn, and only if the game is being played.
+        if self.game.turn() != color || !self.mode.is_playable() {
             return;
         }
 
         self.game
-            .apply_action(action, self.color)
+            .apply_action(action, color)
             .unwrap_or_else(|err| tracing::error!(?err));
 
+        if action == Action::OfferDraw {
+            self.draw_offer_declined = false;
+        }
+
+        if self.mode == GameMode::LocalTwoPlayer {
+            return;
+        }
+
         let Some(connection) = &self.connection else {
             tracing::warn!("Trying to send action {action:?} but game is not connected yet.");
             return;
@@ -299,10 +858,15 @@ impl GameScreen {
     }
 }
 
-/// Draw the game initialization screen where you select the time control.
+/// Draw the game initialization screen where you select the time control and game mode.
 ///
 /// Returns whether to start the game.
-pub fn draw_init_game_screen(ui: &mut Ui, time_control: &mut TimeControl) -> bool {
+pub fn draw_init_game_screen(
+    ui: &mut Ui,
+    time_control: &mut TimeControl,
+    mode: &mut GameMode,
+    position: &mut Option<Box<Board>>,
+) -> bool {
     const MAX_WIDTH: f32 = 300.0;
     let margin = ((ui.available_width() - MAX_WIDTH) / 2.0).max(0.0);
     // effective width, in case the screen is smaller than 360.
@@ -315,6 +879,19 @@ pub fn draw_init_game_screen(ui: &mut Ui, time_control: &mut TimeControl) -> boo
             ui.label(RichText::new("New game").strong().size(16.0));
             ui.add_space(8.0);
 
+            if position.is_some() {
+                ui.horizontal(|ui| {
+                    ui.label("Starting from the position in the editor.");
+                    if ui
+                        .add(components::button("Use the starting position"))
+                        .clicked()
+                    {
+                        *position = None;
+                    }
+                });
+                ui.add_space(8.0);
+            }
+
             ui.label("Select a time control:");
 
             const SPACING: f32 = 4.0;
@@ -357,6 +934,29 @@ pub fn draw_init_game_screen(ui: &mut Ui, time_control: &mut TimeControl) -> boo
                 tc_button(ui, TimeControl::mps(30, 20));
             });
 
+            draw_time_handicap(ui, time_control, *mode);
+
+            ui.add_space(8.0);
+            ui.label("Select who plays:");
+            ui.horizontal(|ui| {
+                for option in [
+                    GameMode::VsEngine,
+                    GameMode::LocalTwoPlayer,
+                    GameMode::Spectate,
+                ] {
+                    ui.add_enabled_ui(*mode != option, |ui| {
+                        let button = ui.add_sized(
+                            Vec2::new(width / 3.0 - SPACING, 48.0),
+                            components::button(RichText::new(option.name()).size(16.0)),
+                        );
+
+                        if button.clicked() {
+                            *mode = option;
+                        }
+                    });
+                }
+            });
+
             ui.add_sized(
                 Vec2::new(width, 64.0),
                 components::button(RichText::new("Start game").strong().size(16.0)),
@@ -367,3 +967,339 @@ pub fn draw_init_game_screen(ui: &mut Ui, time_control: &mut TimeControl) -> boo
     })
     .inner
 }
+
+/// Draws the advanced options to give each player a different time (e.g., for odds games).
+fn draw_time_handicap(ui: &mut Ui, time_control: &mut TimeControl, mode: GameMode) {
+    egui::CollapsingHeader::new("Advanced").show(ui, |ui| {
+        ui.label("Time for each player:");
+        for color in [Color::White, Color::Black] {
+            let name = match mode {
+                GameMode::VsEngine => color.choose("You", "Engine"),
+                _ => color.choose("White", "Black"),
+            };
+
+            ui.horizontal(|ui| {
+                ui.label(name);
+
+                let mut minutes = time_control.base_time[color].as_secs() / 60;
+                let mut increment = time_control.increment[color].as_secs();
+                let base_changed = ui
+                    .add(DragValue::new(&mut minutes).range(0..=180).suffix(" min"))
+                    .changed();
+                let increment_changed = ui
+                    .add(
+                        DragValue::new(&mut increment)
+                            .range(0..=180)
+                            .prefix("+")
+                            .suffix(" s"),
+                    )
+                    .changed();
+
+                if base_changed || increment_changed {
+                    time_control.base_time[color] = Duration::from_secs(minutes * 60);
+                    time_control.increment[color] = Duration::from_secs(increment);
+                }
+            });
+        }
+
+        if time_control.base_time().is_none() || time_control.increment().is_none() {
+            ui.label(RichText::new(time_control.formatted()).strong());
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::mpsc;
+
+    use chessagon_core::{
+        Color, Game, Vec2,
+        game::{Action, DrawReason, GameResult, TimeControl},
+    };
+    use chessagon_engine::{Engine, models::Anthony};
+
+    use super::{GameConnection, GameMode, GameScreen, OpponentDisconnected, OpponentMessage};
+    use crate::{GuiBoard, Settings};
+
+    fn vs_engine_screen() -> GameScreen {
+        GameScreen {
+            color: Color::White,
+            mode: GameMode::VsEngine,
+            game: Game::new(TimeControl::blitz()),
+            gui_board: GuiBoard::default(),
+            connection: None,
+            pending_confirmation: None,
+            opponent_error: None,
+            spectator: None,
+            draw_offer_declined: false,
+            eval_bar: Default::default(),
+            search_progress: None,
+        }
+    }
+
+    /// Creates a connection whose engine thread runs `engine` and then drops its sender.
+    fn simulated_connection(engine: impl FnOnce() + Send + 'static) -> Option<GameConnection> {
+        let (action_sender, _) = mpsc::channel();
+        let (opponent_sender, opponent_receiver) = mpsc::channel::<OpponentMessage>();
+        let engine_thread = std::thread::spawn(move || {
+            let _sender = opponent_sender;
+            engine();
+        });
+
+        while !engine_thread.is_finished() {
+            std::thread::yield_now();
+        }
+
+        Some(GameConnection {
+            action_sender,
+            opponent_receiver,
+            engine_thread: Some(engine_thread),
+        })
+    }
+
+    #[test]
+    fn engine_panic_is_reported_and_drops_connection() {
+        let game = Game::new(TimeControl::blitz());
+        let mut connection = simulated_connection(|| panic!("en passant is not implemented"));
+
+        let result = GameConnection::poll(&mut connection, &game);
+        assert_eq!(
+            result,
+            Err(OpponentDisconnected {
+                reason: "en passant is not implemented".to_string()
+            })
+        );
+        assert!(connection.is_none());
+    }
+
+    #[test]
+    fn disconnect_after_game_is_finished_is_fine() {
+        let mut game = Game::new(TimeControl::blitz());
+        game.apply_action(Action::Resign, game.turn()).unwrap();
+        let mut connection = simulated_connection(|| ());
+
+        assert_eq!(GameConnection::poll(&mut connection, &game), Ok(None));
+        assert!(connection.is_some());
+    }
+
+    #[test]
+    fn local_two_player_plays_both_colors_without_connection() {
+        let mut screen = GameScreen {
+            color: Color::White,
+            mode: GameMode::LocalTwoPlayer,
+            game: Game::new(TimeControl::blitz()),
+            gui_board: GuiBoard::default(),
+            connection: None,
+            pending_confirmation: None,
+            opponent_error: None,
+            spectator: None,
+            draw_offer_declined: false,
+            eval_bar: Default::default(),
+            search_progress: None,
+        };
+
+        screen.connect(&Settings::default());
+        assert!(screen.connection.is_none());
+
+        for (origin, destination, color) in [
+            (
+                Vec2::new(0, 2).unwrap(),
+                Vec2::new(3, 3).unwrap(),
+                Color::White,
+            ),
+            (
+                Vec2::new(10, 8).unwrap(),
+                Vec2::new(7, 7).unwrap(),
+                Color::Black,
+            ),
+        ] {
+            assert_eq!(screen.active_color(), color);
+            let (mov, _) = screen
+                .game
+                .board()
+                .get_move(origin, destination, color)
+                .unwrap();
+            screen.apply_action(Action::Move(mov));
+        }
+
+        assert_eq!(screen.game.moves_from(Color::White).count(), 1);
+        assert_eq!(screen.game.moves_from(Color::Black).count(), 1);
+        assert_eq!(screen.active_color(), Color::White);
+    }
+
+    #[test]
+    fn local_two_player_notation_is_from_the_side_of_the_player_to_move() {
+        let mut screen = GameScreen {
+            mode: GameMode::LocalTwoPlayer,
+            ..vs_engine_screen()
+        };
+        let oriented = Settings {
+            oriented_notation: true,
+            ..Settings::default()
+        };
+        assert_eq!(screen.notation_perspective(&oriented), Color::White);
+
+        let (mov, _) = screen
+            .game
+            .board()
+            .get_move(
+                Vec2::new(0, 2).unwrap(),
+                Vec2::new(3, 3).unwrap(),
+                Color::White,
+            )
+            .unwrap();
+        screen.apply_action(Action::Move(mov));
+        assert_eq!(screen.notation_perspective(&oriented), Color::Black);
+        assert_eq!(
+            screen.notation_perspective(&Settings::default()),
+            Color::White
+        );
+
+        screen.mode = GameMode::Review;
+        assert_eq!(screen.notation_perspective(&oriented), Color::White);
+    }
+
+    #[test]
+    fn opponent_draw_offer_then_acceptance_ends_the_game() {
+        let mut screen = vs_engine_screen();
+        let (opponent_sender, opponent_receiver) = mpsc::channel();
+        let player_receiver = screen.connect_to_channel(opponent_receiver);
+
+        opponent_sender
+            .send(OpponentMessage::Action(Action::OfferDraw))
+            .unwrap();
+        let message = GameConnection::poll(&mut screen.connection, &screen.game).unwrap();
+        assert_eq!(message, Some(OpponentMessage::Action(Action::OfferDraw)));
+        screen.receive_opponent_action(Action::OfferDraw);
+        assert_eq!(screen.game.draw_offer(), Some(Color::Black));
+        assert!(!screen.game.is_finished());
+
+        screen.apply_action(Action::AcceptDraw);
+        assert_eq!(player_receiver.try_recv(), Ok(Action::AcceptDraw));
+        assert!(matches!(
+            screen.game.result(),
+            Some(GameResult::Draw {
+                reason: DrawReason::Agreement {
+                    offered_by: Color::Black,
+                    ..
+                }
+            })
+        ));
+    }
+
+    #[test]
+    fn opponent_accepting_a_draw_offer_ends_the_game() {
+        let mut screen = vs_engine_screen();
+        let (opponent_sender, opponent_receiver) = mpsc::channel();
+        let player_receiver = screen.connect_to_channel(opponent_receiver);
+
+        screen.apply_action(Action::OfferDraw);
+        assert_eq!(player_receiver.try_recv(), Ok(Action::OfferDraw));
+        assert_eq!(screen.game.draw_offer(), Some(Color::White));
+
+        opponent_sender
+            .send(OpponentMessage::Action(Action::AcceptDraw))
+            .unwrap();
+        let message = GameConnection::poll(&mut screen.connection, &screen.game).unwrap();
+        assert_eq!(message, Some(OpponentMessage::Action(Action::AcceptDraw)));
+        screen.receive_opponent_action(Action::AcceptDraw);
+        assert!(matches!(
+            screen.game.result(),
+            Some(GameResult::Draw {
+                reason: DrawReason::Agreement {
+                    offered_by: Color::White,
+                    ..
+                }
+            })
+        ));
+    }
+
+    #[test]
+    fn engine_search_progress_is_streamed_before_its_move() {
+        let mut screen = vs_engine_screen();
+        let (opponent_sender, opponent_receiver) = mpsc::channel();
+        screen.connect_to_channel(opponent_receiver);
+
+        let game = Game::new(TimeControl::rapid());
+        let engine_thread = std::thread::spawn(move || {
+            let mut engine = Anthony::new(Color::White, TimeControl::rapid());
+            let action = engine.get_action_with_progress(&game, &mut |progress| {
+                opponent_sender
+                    .send(OpponentMessage::Progress(progress))
+                    .unwrap();
+            });
+            opponent_sender
+                .send(OpponentMessage::Action(action))
+                .unwrap();
+        });
+        engine_thread.join().unwrap();
+
+        let mut messages = Vec::new();
+        // The engine thread has finished, so the connection is dropped once every message is read.
+        while let Ok(Some(message)) = GameConnection::poll(&mut screen.connection, &screen.game) {
+            messages.push(message);
+        }
+
+        let Some((&OpponentMessage::Action(action), progress)) = messages.split_last() else {
+            panic!("The last message should be the move, got {messages:?}");
+        };
+
+        // One update per depth, the last one with the move that was played.
+        assert!(!progress.is_empty());
+        for (i, message) in progress.iter().enumerate() {
+            let OpponentMessage::Progress(progress) = message else {
+                panic!("Expected progress, got {message:?}");
+            };
+
+            assert_eq!(progress.depth, i + 1);
+            if i + 1 == messages.len() - 1 {
+                assert_eq!(progress.analysis.best_move.map(Action::Move), Some(action));
+            }
+        }
+    }
+
+    #[test]
+    fn engine_only_analyzes_positions_when_the_eval_bar_is_shown() {
+        for eval_bar in [false, true] {
+            let mut screen = vs_engine_screen();
+            screen.connect(&Settings {
+                eval_bar,
+                ..Settings::default()
+            });
+
+            let (mov, _) = screen
+                .game
+                .board()
+                .get_move(
+                    Vec2::new(0, 2).unwrap(),
+                    Vec2::new(3, 3).unwrap(),
+                    Color::White,
+                )
+                .unwrap();
+            screen.apply_action(Action::Move(mov));
+
+            // Without more actions from the player, the engine stops after its reply.
+            let GameConnection {
+                action_sender,
+                opponent_receiver,
+                engine_thread,
+            } = screen.connection.take().unwrap();
+            drop(action_sender);
+            engine_thread.unwrap().join().unwrap();
+
+            let messages = opponent_receiver.try_iter().collect::<Vec<_>>();
+            assert!(
+                messages
+                    .iter()
+                    .any(|message| matches!(message, OpponentMessage::Action(_)))
+            );
+            assert_eq!(
+                messages
+                    .iter()
+                    .any(|message| matches!(message, OpponentMessage::Analysis(_))),
+                eval_bar,
+                "{messages:?}"
+            );
+        }
+    }
+}
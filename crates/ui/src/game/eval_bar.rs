@@ -0,0 +1,139 @@
+use chessagon_core::Color;
+use egui::{Color32, Context, CornerRadius, Rect, Sense, Ui, pos2, vec2};
+
+use crate::board::piece::ease_factor;
+
+/// The evaluation (in pawns) at which the bar is completely filled by one color. Evaluations
+/// beyond it, including mates, are clamped.
+pub const MAX_EVAL: f64 = 10.0;
+
+/// The fraction of the remaining distance to the latest evaluation that the bar moves each
+/// frame, at 60fps.
+const SMOOTHING: f32 = 0.1;
+
+/// A vertical bar that shows how good the position is for each color, according to the engine.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct EvalBar {
+    /// The latest evaluation received, from white's perspective. `None` until the first one.
+    eval: Option<f64>,
+
+    /// The fraction of the bar that is filled by white, which moves smoothly towards
+    /// [`white_fraction`] of [`Self::eval`].
+    shown: f32,
+}
+
+/// The fraction of the bar that white fills for the given evaluation, from `0.0` (black is
+/// winning by [`MAX_EVAL`] or more) to `1.0`.
+pub fn white_fraction(eval: f64) -> f32 {
+    let clamped = if eval.is_nan() {
+        0.0
+    } else {
+        eval.clamp(-MAX_EVAL, MAX_EVAL)
+    };
+
+    (0.5 + clamped / MAX_EVAL / 2.0) as f32
+}
+
+impl EvalBar {
+    /// Sets the latest evaluation, from white's perspective.
+    ///
+    /// The first one is shown right away, afterwards the bar moves to it over a few frames.
+    pub fn set_eval(&mut self, eval: f64) {
+        if self.eval.is_none() {
+            self.shown = white_fraction(eval);
+        }
+
+        self.eval = Some(eval);
+    }
+
+    /// Moves the shown fraction towards the latest evaluation, for a frame that took `dt`
+    /// seconds. Returns whether it still has to move.
+    fn step(&mut self, dt: f32) -> bool {
+        let Some(eval) = self.eval else {
+            return false;
+        };
+
+        let target = white_fraction(eval);
+        self.shown += (target - self.shown) * ease_factor(SMOOTHING, dt);
+        if (target - self.shown).abs() < 1e-3 {
+            self.shown = target;
+        }
+
+        self.shown != target
+    }
+
+    /// Draws the bar with the given width, filling the available height, with `bottom` being the
+    /// color whose side is at the bottom.
+    ///
+    /// Nothing is drawn until the first evaluation is set.
+    pub fn draw(&mut self, ui: &mut Ui, ctx: &Context, width: f32, bottom: Color) {
+        let (rect, response) =
+            ui.allocate_exact_size(vec2(width, ui.available_height()), Sense::hover());
+        let Some(eval) = self.eval else {
+            return;
+        };
+
+        if self.step(ctx.input(|i| i.stable_dt)) {
+            ctx.request_repaint();
+        }
+
+        let bottom_fraction = bottom.choose(self.shown, 1.0 - self.shown);
+        let split = rect.bottom() - rect.height() * bottom_fraction;
+        let (top_color, bottom_color) = bottom.choose(
+            (Color32::BLACK, Color32::WHITE),
+            (Color32::WHITE, Color32::BLACK),
+        );
+
+        let painter = ui.painter();
+        painter.rect_filled(rect, CornerRadius::ZERO, top_color);
+        painter.rect_filled(
+            Rect::from_min_max(pos2(rect.left(), split), rect.right_bottom()),
+            CornerRadius::ZERO,
+            bottom_color,
+        );
+
+        response.on_hover_text(format_eval(eval));
+    }
+}
+
+/// Formats an evaluation from white's perspective, like `+1.5` or `-3.0`, or `#` for mates.
+fn format_eval(eval: f64) -> String {
+    if eval.is_infinite() {
+        format!("{}#", if eval > 0.0 { '+' } else { '-' })
+    } else {
+        format!("{eval:+.1}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EvalBar, MAX_EVAL, format_eval, white_fraction};
+
+    #[test]
+    fn evaluations_are_clamped() {
+        assert_eq!(white_fraction(0.0), 0.5);
+        assert_eq!(white_fraction(MAX_EVAL / 2.0), 0.75);
+        assert_eq!(white_fraction(f64::INFINITY), 1.0);
+        assert_eq!(white_fraction(-1000.0), 0.0);
+        assert_eq!(white_fraction(f64::NAN), 0.5);
+
+        assert_eq!(format_eval(1.5), "+1.5");
+        assert_eq!(format_eval(f64::NEG_INFINITY), "-#");
+    }
+
+    #[test]
+    fn bar_moves_smoothly_to_the_latest_evaluation() {
+        let mut bar = EvalBar::default();
+        assert!(!bar.step(1.0 / 60.0));
+
+        bar.set_eval(0.0);
+        assert_eq!(bar.shown, 0.5);
+
+        bar.set_eval(MAX_EVAL);
+        assert!(bar.step(1.0 / 60.0));
+        assert!(bar.shown > 0.5 && bar.shown < 1.0, "{}", bar.shown);
+
+        while bar.step(1.0 / 60.0) {}
+        assert_eq!(bar.shown, 1.0);
+    }
+}
@@ -0,0 +1,184 @@
+use std::{
+    ops::RangeInclusive,
+    sync::mpsc::{self, Receiver, Sender, TryRecvError},
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
+
+use chessagon_core::Game;
+use chessagon_engine::{
+    matcher::{EngineMatch, MatchLimits},
+    models::Anthony,
+};
+use egui::{RichText, Slider, Ui};
+
+use crate::components;
+
+/// The allowed delays between moves, in seconds.
+pub const DELAY_RANGE: RangeInclusive<f32> = 0.0..=5.0;
+
+/// A match between two engines running in another thread, which plays a move whenever it is
+/// asked to, so that it can be watched on the board.
+#[derive(Debug)]
+pub struct Spectator {
+    step_sender: Sender<()>,
+    game_receiver: Receiver<Game>,
+    engine_thread: Option<JoinHandle<()>>,
+
+    /// Whether moves are played automatically, every [`Spectator::delay`] seconds.
+    pub playing: bool,
+
+    /// How long to wait between moves while [playing](Spectator::playing), in seconds.
+    pub delay: f32,
+
+    /// Whether a move has been asked for but not received yet.
+    waiting: bool,
+
+    /// When the last move was received.
+    last_step: Instant,
+}
+
+impl Spectator {
+    /// Starts a match that continues `game`, paused.
+    pub fn start(game: Game) -> Self {
+        let (step_sender, step_receiver) = mpsc::channel::<()>();
+        let (game_sender, game_receiver) = mpsc::channel();
+
+        let engine_thread = std::thread::spawn(move || {
+            let span = tracing::info_span!("Spectated engines");
+            let _guard = span.enter();
+
+            let mut engine_match =
+                EngineMatch::<Anthony, Anthony>::from_game(game, MatchLimits::default());
+
+            // Each message asks for one more action, until the channel is closed.
+            while step_receiver.recv().is_ok() {
+                let finished = engine_match.step().is_none();
+                if game_sender.send(engine_match.game().clone()).is_err() || finished {
+                    return;
+                }
+            }
+        });
+
+        Self {
+            step_sender,
+            game_receiver,
+            engine_thread: Some(engine_thread),
+            playing: false,
+            delay: 1.0,
+            waiting: false,
+            last_step: Instant::now(),
+        }
+    }
+
+    /// Asks the engines for the next action, unless one has already been asked for.
+    pub fn step(&mut self) {
+        if self.waiting {
+            return;
+        }
+
+        self.waiting = self.step_sender.send(()).is_ok();
+    }
+
+    /// Whether it's time to play the next move automatically.
+    pub fn is_due(&self, now: Instant) -> bool {
+        self.playing
+            && !self.waiting
+            && now.duration_since(self.last_step) >= Duration::from_secs_f32(self.delay)
+    }
+
+    /// Returns the game after the last action, if it has been played since the last poll.
+    ///
+    /// Also asks for the next action, if it [is due](Self::is_due).
+    pub fn poll(&mut self) -> Option<Game> {
+        let game = match self.game_receiver.try_recv() {
+            Ok(game) => {
+                self.waiting = false;
+                self.last_step = Instant::now();
+                Some(game)
+            }
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => {
+                self.playing = false;
+                self.waiting = false;
+                if let Some(Err(_)) = self.engine_thread.take().map(JoinHandle::join) {
+                    tracing::error!("Spectated engine panicked");
+                }
+
+                None
+            }
+        };
+
+        if self.is_due(Instant::now()) {
+            self.step();
+        }
+
+        game
+    }
+
+    /// Draws the play/pause and step buttons, and the delay slider.
+    pub fn draw_controls(&mut self, ui: &mut Ui, finished: bool) {
+        ui.horizontal(|ui| {
+            let play_text = if self.playing { "Pause" } else { "Play" };
+            let play_button = components::button(RichText::new(play_text).size(12.0));
+            if ui.add_enabled(!finished, play_button).clicked() {
+                self.playing = !self.playing;
+            }
+
+            let step_button = components::button(RichText::new("Step").size(12.0));
+            if ui
+                .add_enabled(!finished && !self.playing && !self.waiting, step_button)
+                .clicked()
+            {
+                self.step();
+            }
+        });
+
+        ui.add(
+            Slider::new(&mut self.delay, DELAY_RANGE)
+                .text("Delay")
+                .suffix("s"),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use chessagon_core::{Color, Game, game::TimeControl};
+
+    use super::Spectator;
+
+    #[test]
+    fn stepping_plays_one_move() {
+        let mut spectator = Spectator::start(Game::new(TimeControl::max()));
+        assert!(spectator.poll().is_none(), "Should start paused");
+
+        spectator.step();
+        let start = Instant::now();
+        let game = loop {
+            if let Some(game) = spectator.poll() {
+                break game;
+            }
+
+            assert!(start.elapsed() < Duration::from_secs(30));
+            std::thread::yield_now();
+        };
+
+        assert_eq!(game.moves_from(Color::White).count(), 1);
+        assert_eq!(game.turn(), Color::Black);
+    }
+
+    #[test]
+    fn plays_automatically_after_the_delay() {
+        let mut spectator = Spectator::start(Game::new(TimeControl::max()));
+        spectator.delay = 0.5;
+        let now = spectator.last_step;
+        assert!(!spectator.is_due(now + Duration::from_secs(1)), "Paused");
+
+        spectator.playing = true;
+        assert!(!spectator.is_due(now));
+        assert!(spectator.is_due(now + Duration::from_secs(1)));
+    }
+}
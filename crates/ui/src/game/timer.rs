@@ -1,8 +1,16 @@
 use std::time::Duration;
 
-use egui::{Context, FontFamily, RichText, Ui};
+use chessagon_core::{Color, Game};
+use egui::{Color32, Context, FontFamily, RichText, Ui};
 
-pub fn draw(ui: &mut Ui, ctx: &Context, duration: Duration) {
+/// How long the increment is shown next to the timer after a move.
+const INCREMENT_FLASH_DURATION: Duration = Duration::from_millis(1500);
+
+/// Draws the remaining time of a player.
+///
+/// `recent_increment` is the increment that was just added to the timer (see
+/// [`recent_increment`]), which is shown fading out next to the time.
+pub fn draw(ui: &mut Ui, ctx: &Context, duration: Duration, recent_increment: Option<Increment>) {
     let millis = duration.subsec_millis();
     let secs = duration.as_secs() % 60;
     let mins = duration.as_secs() / 60;
@@ -22,6 +30,20 @@ pub fn draw(ui: &mut Ui, ctx: &Context, duration: Duration) {
         if duration.as_secs() < 20 {
             ui.label(extra);
         }
+
+        if let Some(increment) = recent_increment {
+            let opacity =
+                1.0 - increment.since.as_secs_f32() / INCREMENT_FLASH_DURATION.as_secs_f32();
+            let color = Color32::LIGHT_GREEN.gamma_multiply(opacity.clamp(0.0, 1.0));
+            ui.label(
+                RichText::new(format_increment(increment.amount))
+                    .size(16.0)
+                    .strong()
+                    .color(color),
+            );
+
+            ctx.request_repaint();
+        }
     });
 
     // TODO: Request repaints only after refresh rate of timer.
@@ -29,3 +51,79 @@ pub fn draw(ui: &mut Ui, ctx: &Context, duration: Duration) {
         ctx.request_repaint();
     }
 }
+
+/// An increment that has been added to a timer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Increment {
+    /// How much time was added.
+    pub amount: Duration,
+
+    /// How long ago it was added.
+    pub since: Duration,
+}
+
+/// The increment that was added to the timer of `color`, if it happened recently enough to
+/// still be shown.
+///
+/// Increments are added after each move (see [`Game::time_remaining`]).
+pub fn recent_increment(game: &Game, color: Color) -> Option<Increment> {
+    let amount = game.time_control().increment[color];
+    if amount.is_zero() {
+        return None;
+    }
+
+    let since = game.time_since_last_move(color)?;
+    (since < INCREMENT_FLASH_DURATION).then_some(Increment { amount, since })
+}
+
+/// Formats an increment as `+2s` or, if it isn't a whole number of seconds, `+0.5s`.
+fn format_increment(increment: Duration) -> String {
+    if increment.subsec_millis() == 0 {
+        format!("+{}s", increment.as_secs())
+    } else {
+        format!("+{}s", increment.as_secs_f32())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use chessagon_core::{
+        Color, Game,
+        game::{Action, TimeControl},
+    };
+
+    use super::{format_increment, recent_increment};
+
+    #[test]
+    fn increments_are_formatted_in_seconds() {
+        assert_eq!(format_increment(Duration::from_secs(2)), "+2s");
+        assert_eq!(format_increment(Duration::from_millis(500)), "+0.5s");
+    }
+
+    #[test]
+    fn increment_is_shown_after_every_move() {
+        let mut game = Game::new(TimeControl::new_asymetric(
+            [Duration::from_secs(180); 2],
+            [Duration::from_secs(2), Duration::ZERO],
+        ));
+
+        let play = |game: &mut Game| {
+            let color = game.turn();
+            let mov = game.board().possible_moves(color).next().unwrap();
+            game.apply_action(Action::Move(mov), color).unwrap();
+        };
+
+        assert_eq!(recent_increment(&game, Color::White), None);
+
+        play(&mut game);
+        play(&mut game);
+        let increment = recent_increment(&game, Color::White).unwrap();
+        assert_eq!(increment.amount, Duration::from_secs(2));
+
+        // Black has no increment.
+        play(&mut game);
+        assert_eq!(recent_increment(&game, Color::Black), None);
+    }
+}
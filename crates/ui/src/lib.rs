@@ -1,11 +1,15 @@
 mod app;
 mod board;
 mod components;
+mod editor;
+mod files;
 mod game;
 mod main_menu;
+mod settings;
 
 pub mod color_scheme;
 
 pub use app::App;
 pub use board::GuiBoard;
 pub use color_scheme::ColorScheme;
+pub use settings::Settings;
@@ -1,14 +1,25 @@
-use egui::{Align, FontFamily, Label, Layout, RichText, Vec2, vec2};
+use std::time::Duration;
 
-use crate::{app::Screen, components};
+use egui::{
+    Align, Color32, FontFamily, Label, Layout, Pos2, RichText, Shape, Stroke, Vec2, pos2, vec2,
+};
+
+use crate::{ColorScheme, app::Screen, components};
 
 #[derive(Debug, Default)]
 pub struct MainMenu {
-    // decoration_tile_angle: f32,
+    decoration_tile_angle: f32,
 }
 
+/// How fast the decoration behind the title rotates, in radians per second.
+const DECORATION_ROTATION_SPEED: f32 = 0.15;
+
+/// How often the decoration is redrawn. It moves slowly, so there is no need to redraw it every
+/// frame.
+const DECORATION_REPAINT_INTERVAL: Duration = Duration::from_millis(50);
+
 impl MainMenu {
-    pub fn draw(&self, ui: &mut egui::Ui) -> Option<Screen> {
+    pub fn draw(&mut self, ui: &mut egui::Ui, color_scheme: &ColorScheme) -> Option<Screen> {
         let label = Label::new(
             RichText::new("Chessagon")
                 .font(egui::FontId {
@@ -18,11 +29,18 @@ impl MainMenu {
                 .strong(),
         );
 
-        ui.allocate_ui_with_layout(
-            vec2(ui.available_width(), ui.available_height() / 2.0),
-            Layout::bottom_up(Align::Center),
-            |ui| ui.add(label),
-        );
+        // Reserve a spot for the decoration, so that it's drawn behind the title.
+        let decoration = ui.painter().add(Shape::Noop);
+
+        let title = ui
+            .allocate_ui_with_layout(
+                vec2(ui.available_width(), ui.available_height() / 2.0),
+                Layout::bottom_up(Align::Center),
+                |ui| ui.add(label),
+            )
+            .inner;
+
+        self.draw_decoration(ui, decoration, title.rect.center(), color_scheme);
 
         ui.spacing_mut().button_padding = Vec2::splat(12.0);
         if ui
@@ -40,4 +58,73 @@ impl MainMenu {
 
         None
     }
+
+    /// Draws a few slowly rotating hexagons, in the colors of the board, at `center`.
+    fn draw_decoration(
+        &mut self,
+        ui: &egui::Ui,
+        index: egui::layers::ShapeIdx,
+        center: Pos2,
+        color_scheme: &ColorScheme,
+    ) {
+        let (dt, focused) = ui.input(|i| (i.stable_dt, i.focused));
+        self.decoration_tile_angle =
+            (self.decoration_tile_angle + dt * DECORATION_ROTATION_SPEED) % std::f32::consts::TAU;
+
+        let shapes = color_scheme
+            .tiles
+            .iter()
+            .enumerate()
+            .map(|(i, &color)| {
+                let radius = 160.0 - 40.0 * i as f32;
+                let direction = if i % 2 == 0 { 1.0 } else { -1.0 };
+                let points = hexagon_points(center, radius, direction * self.decoration_tile_angle);
+
+                Shape::convex_polygon(
+                    points.to_vec(),
+                    Color32::from(color).gamma_multiply(0.25),
+                    Stroke::new(2.0, Color32::from(color).gamma_multiply(0.6)),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        ui.painter().set(index, shapes);
+
+        // Don't keep redrawing when nobody is looking.
+        if focused {
+            ui.ctx().request_repaint_after(DECORATION_REPAINT_INTERVAL);
+        }
+    }
+}
+
+/// The vertices of a regular hexagon with the given circumradius, rotated by `angle` radians.
+fn hexagon_points(center: Pos2, radius: f32, angle: f32) -> [Pos2; 6] {
+    std::array::from_fn(|i| {
+        let vertex_angle = angle + std::f32::consts::FRAC_PI_3 * i as f32;
+        pos2(
+            center.x + radius * vertex_angle.cos(),
+            center.y + radius * vertex_angle.sin(),
+        )
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use egui::pos2;
+
+    use super::hexagon_points;
+
+    #[test]
+    fn hexagon_points_are_equidistant() {
+        let center = pos2(10.0, 20.0);
+        let points = hexagon_points(center, 5.0, 0.3);
+
+        for (i, point) in points.iter().enumerate() {
+            assert!((point.distance(center) - 5.0).abs() < 1e-4);
+
+            // The sides of a regular hexagon are as long as its circumradius.
+            let next = points[(i + 1) % 6];
+            assert!((point.distance(next) - 5.0).abs() < 1e-4);
+        }
+    }
 }
@@ -0,0 +1,188 @@
+use std::ops::RangeInclusive;
+
+use chessagon_core::Color;
+use chessagon_engine::models::Personality;
+use egui::{RichText, Slider, Ui};
+
+/// User preferences, editable from the options screen.
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Whether destructive actions (resigning, starting a new game) need to be clicked twice.
+    pub confirm_actions: bool,
+
+    /// How fast pieces move to their tile, as the fraction of the remaining distance covered
+    /// each frame at 60fps.
+    pub piece_move_speed: f32,
+
+    /// How fast a dragged piece follows the cursor, in the same units as
+    /// [`Settings::piece_move_speed`].
+    pub piece_drag_speed: f32,
+
+    /// Whether pieces jump to their tile instead of moving there smoothly.
+    pub instant_animations: bool,
+
+    /// Whether pieces have a badge with their letter, so that color isn't the only way to tell
+    /// them apart.
+    pub piece_badges: bool,
+
+    /// Whether coordinates, in move notation and on the board, are shown from the player's side
+    /// of the board instead of white's (see [`Move::notation`]). In local two-player games, the
+    /// player is the one to move. Transcripts always use white's.
+    ///
+    /// [`Move::notation`]: chessagon_core::Move::notation
+    pub oriented_notation: bool,
+
+    /// Whether the engine thinks on the player's time. See [`Engine::ponder`].
+    ///
+    /// [`Engine::ponder`]: chessagon_engine::Engine::ponder
+    pub engine_ponder: bool,
+
+    /// Whether to show the evaluation of the engine next to the board, in games against it.
+    pub eval_bar: bool,
+
+    /// How the engine plays, see [`Personality`].
+    pub engine_personality: Personality,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            confirm_actions: true,
+            piece_move_speed: 0.2,
+            piece_drag_speed: 0.8,
+            instant_animations: false,
+            piece_badges: false,
+            oriented_notation: false,
+            engine_ponder: false,
+            eval_bar: false,
+            engine_personality: Personality::default(),
+        }
+    }
+}
+
+/// The allowed animation speeds, so that pieces never stop moving nor move absurdly fast (that's
+/// what [`Settings::instant_animations`] is for).
+pub const ANIMATION_SPEED_RANGE: RangeInclusive<f32> = 0.05..=0.95;
+
+impl Settings {
+    /// Draws the options screen, where the settings can be modified.
+    pub fn draw(&mut self, ui: &mut Ui) {
+        ui.label(RichText::new("Options").strong().size(16.0));
+        ui.add_space(8.0);
+
+        ui.checkbox(
+            &mut self.confirm_actions,
+            "Ask for confirmation before resigning or starting a new game",
+        );
+
+        ui.add_space(8.0);
+        ui.label(RichText::new("Animations").strong());
+        ui.checkbox(&mut self.instant_animations, "Move pieces instantly");
+
+        ui.add_enabled_ui(!self.instant_animations, |ui| {
+            ui.add(
+                Slider::new(&mut self.piece_move_speed, ANIMATION_SPEED_RANGE).text("Piece speed"),
+            );
+            ui.add(
+                Slider::new(&mut self.piece_drag_speed, ANIMATION_SPEED_RANGE)
+                    .text("Dragging speed"),
+            );
+        });
+
+        ui.add_space(8.0);
+        ui.label(RichText::new("Accessibility").strong());
+        ui.checkbox(&mut self.piece_badges, "Show letters on pieces")
+            .on_hover_text(
+                "Uppercase in round badges for white, lowercase in square ones for black",
+            );
+        ui.checkbox(
+            &mut self.oriented_notation,
+            "Show coordinates from your side of the board",
+        );
+
+        ui.add_space(8.0);
+        ui.label(RichText::new("Engine").strong());
+        ui.checkbox(&mut self.engine_ponder, "Think on your time")
+            .on_hover_text("Takes effect on the next game");
+        ui.checkbox(&mut self.eval_bar, "Show the engine's evaluation")
+            .on_hover_text("Positions where you are to move are evaluated from the next game on");
+        egui::ComboBox::from_label("Personality")
+            .selected_text(self.engine_personality.to_string())
+            .show_ui(ui, |ui| {
+                for personality in Personality::ALL {
+                    ui.selectable_value(
+                        &mut self.engine_personality,
+                        personality,
+                        personality.to_string(),
+                    );
+                }
+            })
+            .response
+            .on_hover_text("Takes effect on the next game");
+    }
+
+    /// The speeds to move and drag pieces with, as `(move, drag)`.
+    ///
+    /// The speeds are clamped to [`ANIMATION_SPEED_RANGE`], in case they were persisted out of
+    /// bounds, or `1.0` if animations are instant.
+    pub fn animation_speeds(&self) -> (f32, f32) {
+        if self.instant_animations {
+            return (1.0, 1.0);
+        }
+
+        let clamp =
+            |speed: f32| speed.clamp(*ANIMATION_SPEED_RANGE.start(), *ANIMATION_SPEED_RANGE.end());
+        (clamp(self.piece_move_speed), clamp(self.piece_drag_speed))
+    }
+
+    /// The side from which coordinates are shown to the player of the given color, see
+    /// [`Settings::oriented_notation`].
+    pub fn notation_perspective(&self, player: Color) -> Color {
+        if self.oriented_notation {
+            player
+        } else {
+            Color::White
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chessagon_core::Color;
+
+    use super::{ANIMATION_SPEED_RANGE, Settings};
+
+    #[test]
+    fn animation_speeds_are_bounded() {
+        let settings = Settings {
+            piece_move_speed: 0.0,
+            piece_drag_speed: 100.0,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            settings.animation_speeds(),
+            (*ANIMATION_SPEED_RANGE.start(), *ANIMATION_SPEED_RANGE.end())
+        );
+
+        let instant = Settings {
+            instant_animations: true,
+            ..settings
+        };
+        assert_eq!(instant.animation_speeds(), (1.0, 1.0));
+    }
+
+    #[test]
+    fn notation_is_from_whites_side_unless_oriented() {
+        let settings = Settings::default();
+        assert_eq!(settings.notation_perspective(Color::Black), Color::White);
+
+        let oriented = Settings {
+            oriented_notation: true,
+            ..settings
+        };
+        assert_eq!(oriented.notation_perspective(Color::White), Color::White);
+        assert_eq!(oriented.notation_perspective(Color::Black), Color::Black);
+    }
+}
@@ -0,0 +1,304 @@
+//! Interop layer to drive the rules of chessagon from JavaScript.
+//!
+//! Positions are passed around as [FEN](chessagon_core::Board::to_fen) strings, and everything
+//! else as JSON:
+//!
+//! - A [`Vec2`] is `{ "x": 0, "y": 2 }`, and JSON with tiles off the board is rejected.
+//! - A [`MoveJson`] is `{ "origin": <Vec2>, "destination": <Vec2>, "captures": false }`, plus
+//!   `"promoting_to": "Queen"` (or any other [promotion piece](pawn::PROMOTION_PIECES)) for
+//!   promotions. When applying a move, `captures` can be left out, since it is inferred from the
+//!   board.
+//!
+//! The functions in this module are plain Rust, so that they can be tested natively. On
+//! `wasm32`, they are exported to JavaScript with JSON strings for arguments and return values.
+
+use chessagon_core::{
+    Board, Color, FenError, Move, Vec2,
+    piece::{MoveError, Piece, pawn},
+};
+
+/// A move, as it is sent to and from JavaScript.
+///
+/// Regular moves and promotions can be represented, but not en passant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct MoveJson {
+    pub origin: Vec2,
+    pub destination: Vec2,
+    #[serde(default)]
+    pub captures: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub promoting_to: Option<Piece>,
+}
+
+impl MoveJson {
+    /// The JSON of `mov`, made by `color`.
+    ///
+    /// The tiles of [promotions](Move::Promotion) depend on the color, so it is needed to
+    /// resolve them.
+    pub fn from_move(mov: Move, color: Color) -> Result<Self, Error> {
+        match mov {
+            Move::Regular {
+                origin,
+                destination,
+                captures,
+            } => Ok(Self {
+                origin,
+                destination,
+                captures,
+                promoting_to: None,
+            }),
+            Move::Promotion {
+                captures,
+                promoting_to,
+                ..
+            } => Ok(Self {
+                origin: mov.origin_for(color),
+                destination: mov.destination_for(color),
+                captures: captures.is_some(),
+                promoting_to: Some(promoting_to),
+            }),
+            mov => Err(Error::UnsupportedMove(mov)),
+        }
+    }
+
+    /// The move of `color` on `board` that this describes.
+    ///
+    /// Whether it [captures](Self::captures) comes from JavaScript, so it is not trusted: it is
+    /// inferred from the board instead. For promotions, the side it captures towards is inferred
+    /// from the origin and the destination, and it is an error if they are not the tiles of a
+    /// promotion of `color`.
+    pub fn to_move(self, board: &Board, color: Color) -> Result<Move, Error> {
+        let Some(promoting_to) = self.promoting_to else {
+            return Ok(Move::regular(self.origin, self.destination, board, color));
+        };
+
+        let file = self.origin.file();
+        let captures = pawn::capture_side(color, self.destination - self.origin);
+        let mov = Move::Promotion {
+            file,
+            captures,
+            promoting_to,
+        };
+
+        // `Move::Promotion` only has the file, so the tiles have to be checked here.
+        let is_promotion = pawn::promotion_origin(file, captures, color) == Some(self.origin)
+            && mov.destination_for(color) == self.destination;
+        if !is_promotion {
+            return Err(MoveError::InvalidPromotion { mov }.into());
+        }
+
+        Ok(mov)
+    }
+}
+
+#[allow(missing_docs)]
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error(transparent)]
+    Fen(#[from] FenError),
+
+    #[error(transparent)]
+    IllegalMove(#[from] MoveError),
+
+    #[error("Invalid JSON: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("{0:?} can't be represented in JSON yet")]
+    UnsupportedMove(Move),
+}
+
+/// The FEN of the starting position.
+pub fn start_position() -> String {
+    Board::default().to_fen()
+}
+
+/// The legal moves of the piece at `origin`, for the player whose turn it is.
+///
+/// Promotions are listed once for each [promotion piece](pawn::PROMOTION_PIECES).
+pub fn legal_moves_from(fen: &str, origin: Vec2) -> Result<Vec<MoveJson>, Error> {
+    let board = Board::from_fen(fen)?;
+    let color = board.turn();
+
+    board
+        .moves_from(origin, color)
+        .map(|mov| MoveJson::from_move(mov, color))
+        .collect()
+}
+
+/// Applies a move for the player whose turn it is, returning the FEN of the resulting position.
+pub fn apply_move(fen: &str, mov: MoveJson) -> Result<String, Error> {
+    let mut board = Board::from_fen(fen)?;
+    let color = board.turn();
+    board.apply_move(mov.to_move(&board, color)?, color)?;
+
+    Ok(board.to_fen())
+}
+
+#[cfg(target_arch = "wasm32")]
+mod bindings {
+    use wasm_bindgen::prelude::*;
+
+    use super::Error;
+
+    fn to_js_error(error: Error) -> JsError {
+        JsError::new(&error.to_string())
+    }
+
+    #[wasm_bindgen(js_name = startPosition)]
+    pub fn start_position() -> String {
+        super::start_position()
+    }
+
+    /// Takes the FEN and a JSON `Vec2`, and returns a JSON array of moves.
+    #[wasm_bindgen(js_name = legalMovesFrom)]
+    pub fn legal_moves_from(fen: &str, origin: &str) -> Result<String, JsError> {
+        let origin = serde_json::from_str(origin).map_err(|err| to_js_error(err.into()))?;
+        let moves = super::legal_moves_from(fen, origin).map_err(to_js_error)?;
+        serde_json::to_string(&moves).map_err(|err| to_js_error(err.into()))
+    }
+
+    /// Takes the FEN and a JSON move, and returns the resulting FEN.
+    #[wasm_bindgen(js_name = applyMove)]
+    pub fn apply_move(fen: &str, mov: &str) -> Result<String, JsError> {
+        let mov = serde_json::from_str(mov).map_err(|err| to_js_error(err.into()))?;
+        super::apply_move(fen, mov).map_err(to_js_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chessagon_core::{
+        Board, Color, Vec2,
+        piece::{MoveError, Piece},
+    };
+
+    use super::{Error, MoveJson, apply_move, legal_moves_from, start_position};
+
+    #[test]
+    fn moves_go_through_json() {
+        let fen = start_position();
+        let origin: Vec2 = serde_json::from_str(r#"{ "x": 1, "y": 0 }"#).unwrap();
+        let moves = legal_moves_from(&fen, origin).unwrap();
+        assert!(!moves.is_empty());
+
+        let json = serde_json::to_string(&moves[0]).unwrap();
+        assert!(json.starts_with(r#"{"origin":{"x":1,"y":0},"destination":{"#));
+
+        let mov: MoveJson = serde_json::from_str(&json).unwrap();
+        let fen = apply_move(&fen, mov).unwrap();
+
+        let board = Board::from_fen(&fen).unwrap();
+        assert_eq!(board.turn(), Color::Black);
+        assert!(board.get(mov.destination, Color::White).is_some());
+    }
+
+    #[test]
+    fn capture_flag_from_json_is_not_trusted() {
+        let fen = start_position();
+        let origin = Vec2::new(1, 0).unwrap();
+        let quiet = legal_moves_from(&fen, origin).unwrap()[0];
+        assert!(!quiet.captures);
+
+        let lying = MoveJson {
+            captures: true,
+            ..quiet
+        };
+        assert_eq!(
+            apply_move(&fen, lying).unwrap(),
+            apply_move(&fen, quiet).unwrap()
+        );
+
+        let mut json = serde_json::to_value(quiet).unwrap();
+        json.as_object_mut().unwrap().remove("captures");
+        let omitted: MoveJson = serde_json::from_value(json).unwrap();
+        assert_eq!(omitted, quiet);
+    }
+
+    #[test]
+    fn tiles_off_the_board_are_rejected() {
+        let json = r#"{ "origin": { "x": 20, "y": 20 }, "destination": { "x": 1, "y": 1 } }"#;
+        assert!(matches!(
+            serde_json::from_str::<MoveJson>(json),
+            Err(error) if error.to_string().contains("(20, 20) is not a tile")
+        ));
+    }
+
+    #[test]
+    fn illegal_moves_are_rejected() {
+        let mov = MoveJson {
+            origin: Vec2::new(1, 0).unwrap(),
+            destination: Vec2::new(10, 10).unwrap(),
+            captures: false,
+            promoting_to: None,
+        };
+
+        assert!(matches!(
+            apply_move(&start_position(), mov),
+            Err(Error::IllegalMove(_))
+        ));
+    }
+
+    #[test]
+    fn promotions_go_through_json() {
+        let mut board =
+            Board::new_minimal(Vec2::new(0, 0).unwrap(), Vec2::new(5, 10).unwrap()).unwrap();
+        let origin = Vec2::new(9, 7).unwrap();
+        let capture = Vec2::new(10, 7).unwrap();
+        board.get_mut(origin, Color::White).replace(Piece::Pawn);
+        board.get_mut(capture, Color::Black).replace(Piece::Rook);
+        let fen = board.to_fen();
+
+        let moves = legal_moves_from(&fen, origin).unwrap();
+        assert_eq!(moves.len(), 8);
+        assert!(moves.iter().all(|mov| mov.promoting_to.is_some()));
+
+        let knight = moves
+            .iter()
+            .find(|mov| mov.captures && mov.promoting_to == Some(Piece::Knight))
+            .unwrap();
+        assert_eq!(knight.destination, capture);
+
+        let json = serde_json::to_string(knight).unwrap();
+        assert!(json.ends_with(r#""captures":true,"promoting_to":"Knight"}"#));
+        let mov: MoveJson = serde_json::from_str(&json).unwrap();
+
+        let board = Board::from_fen(&apply_move(&fen, mov).unwrap()).unwrap();
+        assert_eq!(board.get(capture, Color::White), Some(Piece::Knight));
+        assert_eq!(board.get(origin, Color::White), None);
+    }
+
+    #[test]
+    fn promotions_need_the_tiles_of_a_promotion() {
+        let mut board =
+            Board::new_minimal(Vec2::new(0, 0).unwrap(), Vec2::new(5, 10).unwrap()).unwrap();
+        let origin = Vec2::new(9, 7).unwrap();
+        board.get_mut(origin, Color::White).replace(Piece::Pawn);
+        let fen = board.to_fen();
+
+        let forward = MoveJson {
+            origin,
+            destination: Vec2::new(10, 8).unwrap(),
+            captures: false,
+            promoting_to: None,
+        };
+        assert!(matches!(
+            apply_move(&fen, forward),
+            Err(Error::IllegalMove(MoveError::PromotionRequired { .. }))
+        ));
+
+        let promotion = MoveJson {
+            promoting_to: Some(Piece::Queen),
+            ..forward
+        };
+        assert!(apply_move(&fen, promotion).is_ok());
+
+        let elsewhere = MoveJson {
+            destination: Vec2::new(10, 9).unwrap(),
+            ..promotion
+        };
+        assert!(matches!(
+            apply_move(&fen, elsewhere),
+            Err(Error::IllegalMove(MoveError::InvalidPromotion { .. }))
+        ));
+    }
+}